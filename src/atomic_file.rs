@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+/// Replace `path` with whatever `write` produces. The content goes to a temporary file in
+/// the same directory, which is renamed over `path` only after `write` succeeded, so a
+/// failure or interruption leaves the original file intact.
+pub fn write_atomic<F>(path: &Path, write: F) -> Result<()>
+where
+    F: FnOnce(&mut dyn Write) -> Result<()>,
+{
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let temp = NamedTempFile::new_in(dir)
+        .with_context(|| format!("Failed to create a temporary file in {}", dir.display()))?;
+
+    let mut writer = BufWriter::new(temp);
+    write(&mut writer)?;
+    let temp = writer.into_inner().map_err(|error| error.into_error())?;
+    temp.as_file().sync_all()?;
+
+    // The temporary file is created 0600; keep the permissions of the file it replaces
+    if let Ok(metadata) = fs::metadata(path) {
+        fs::set_permissions(temp.path(), metadata.permissions())?;
+    }
+    temp.persist(path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_write_preserves_original() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("pyproject.toml");
+        fs::write(&path, "original").unwrap();
+
+        let result = write_atomic(&path, |writer| {
+            writer.write_all(b"half of the new con")?;
+            anyhow::bail!("serialization failed")
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "original");
+        // The temporary file is cleaned up
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+
+        write_atomic(&path, |writer| Ok(writer.write_all(b"updated")?)).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "updated");
+    }
+}
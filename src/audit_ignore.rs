@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::license::PackageLicense;
+use crate::uv_lock::normalize_package_name;
+
+/// Ignore file picked up from the project root when `--ignore-file` is not given
+pub const AUDIT_IGNORE_FILE: &str = ".auditignore";
+
+/// Package name patterns excluded from the audit, in gitignore syntax: one glob per
+/// line, `#` comments, `!` re-includes, and the last matching line wins
+#[derive(Debug, Clone)]
+pub struct AuditIgnore {
+    rules: Gitignore,
+}
+
+impl Default for AuditIgnore {
+    fn default() -> Self {
+        AuditIgnore { rules: Gitignore::empty() }
+    }
+}
+
+impl AuditIgnore {
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new("");
+        // Names are compared PEP 503 normalized, so `[A-Z]` classes must still match them
+        builder.case_insensitive(true)?;
+        builder.allow_unclosed_class(false);
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            builder
+                .add_line(None, &normalize_pattern(line))
+                .with_context(|| format!("line {}: invalid pattern '{}'", index + 1, line))?;
+        }
+        Ok(AuditIgnore { rules: builder.build()? })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read ignore file: {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid ignore file: {}", path.display()))
+    }
+
+    /// `--ignore-file` when given, otherwise `.auditignore` in `project_root` if present
+    pub fn resolve(path: Option<&Path>, project_root: &Path) -> Result<Option<(PathBuf, Self)>> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let default = project_root.join(AUDIT_IGNORE_FILE);
+                if !default.is_file() {
+                    return Ok(None);
+                }
+                default
+            }
+        };
+        let ignore = Self::load(&path)?;
+        Ok(Some((path, ignore)))
+    }
+
+    pub fn is_ignored(&self, package_name: &str) -> bool {
+        self.rules.matched(normalize_package_name(package_name), false).is_ignore()
+    }
+
+    /// Drop ignored packages, returning how many were removed
+    pub fn apply(&self, packages: &mut Vec<PackageLicense>) -> usize {
+        let before = packages.len();
+        packages.retain(|package| !self.is_ignored(&package.name));
+        before - packages.len()
+    }
+}
+
+/// Apply PEP 503 normalization to the literal parts of a pattern, so `Typing_Extensions`
+/// also covers `typing-extensions`; escapes and `[...]` classes are kept as written
+fn normalize_pattern(pattern: &str) -> String {
+    let mut normalized = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    let mut in_class = false;
+    let mut after_separator = false;
+    while let Some(c) = chars.next() {
+        let separator = !in_class && matches!(c, '-' | '_' | '.');
+        match c {
+            '\\' => {
+                normalized.push(c);
+                normalized.extend(chars.next());
+            }
+            '[' if !in_class => {
+                in_class = true;
+                normalized.push(c);
+            }
+            ']' if in_class => {
+                in_class = false;
+                normalized.push(c);
+            }
+            _ if in_class => normalized.push(c),
+            _ if separator => {
+                if !after_separator {
+                    normalized.push('-');
+                }
+            }
+            _ => normalized.extend(c.to_lowercase()),
+        }
+        after_separator = separator;
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_ignore() {
+        let ignore = AuditIgnore::parse(concat!(
+            "# internal build tooling\n",
+            "acme-*\n",
+            "!acme-runtime\n",
+            "\n",
+            "Typing_Extensions\n",
+            "\\#weird\n",
+        )).unwrap();
+
+        assert!(ignore.is_ignored("acme-build"));
+        assert!(ignore.is_ignored("acme_lint"));
+        assert!(!ignore.is_ignored("acme-runtime"));
+        assert!(ignore.is_ignored("typing-extensions"));
+        assert!(ignore.is_ignored("#weird"));
+        assert!(!ignore.is_ignored("requests"));
+
+        let error = AuditIgnore::parse("ok\n[broken\n").unwrap_err().to_string();
+        assert!(error.contains("line 2"));
+    }
+
+    #[test]
+    fn test_audit_ignore_character_classes() {
+        let ignore = AuditIgnore::parse("[A-Z]*-Internal\nlib[0-9]\n").unwrap();
+        assert!(ignore.is_ignored("Acme_Internal"));
+        assert!(ignore.is_ignored("acme-internal"));
+        assert!(!ignore.is_ignored("3d-internal"));
+        assert!(ignore.is_ignored("lib2"));
+        assert!(!ignore.is_ignored("libx"));
+
+        assert_eq!(normalize_pattern("Typing_Extensions"), "typing-extensions");
+        assert_eq!(normalize_pattern("zope..[A-Z_]*"), "zope-[A-Z_]*");
+        assert_eq!(normalize_pattern("\\#Weird"), "\\#weird");
+    }
+
+    #[test]
+    fn test_resolve_from_project_root() {
+        let root = tempfile::tempdir().unwrap();
+        assert!(AuditIgnore::resolve(None, root.path()).unwrap().is_none());
+
+        fs::write(root.path().join(AUDIT_IGNORE_FILE), "acme-*\n").unwrap();
+        let (path, ignore) = AuditIgnore::resolve(None, root.path()).unwrap().unwrap();
+        assert_eq!(path, root.path().join(AUDIT_IGNORE_FILE));
+        assert!(ignore.is_ignored("acme-build"));
+
+        // An explicit --ignore-file wins over the project's
+        let explicit = root.path().join("other-ignore");
+        fs::write(&explicit, "requests\n").unwrap();
+        let (path, ignore) = AuditIgnore::resolve(Some(&explicit), root.path()).unwrap().unwrap();
+        assert_eq!(path, explicit);
+        assert!(!ignore.is_ignored("acme-build"));
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use crate::license::normalize_license_name;
+use crate::policy::{Violation, ViolationSummary};
+use crate::uv_lock::normalize_package_name;
+
+/// On-disk format of a baseline file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BaselineFormat {
+    Json,
+    Toml,
+}
+
+impl BaselineFormat {
+    /// Guess the format from the file extension (`.toml` → TOML, anything else → JSON)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => BaselineFormat::Toml,
+            _ => BaselineFormat::Json,
+        }
+    }
+}
+
+/// A violation accepted into the baseline, keyed by name + version + normalized license
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub license: String,
+}
+
+impl BaselineEntry {
+    pub fn from_violation(violation: &Violation) -> Self {
+        BaselineEntry {
+            name: normalize_package_name(&violation.package_name),
+            version: violation.package_version.clone(),
+            license: violation
+                .license
+                .as_deref()
+                .map(normalize_license_name)
+                .unwrap_or_else(|| "Unknown".to_string()),
+        }
+    }
+}
+
+/// Known violations that should not fail the check
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default)]
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// What `--update-baseline` changed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BaselineUpdate {
+    pub added: usize,
+    pub pruned: usize,
+}
+
+impl Baseline {
+    pub fn contains(&self, violation: &Violation) -> bool {
+        self.entries.contains(&BaselineEntry::from_violation(violation))
+    }
+
+    /// Drop baselined violations; returns the remaining summary and how many were suppressed
+    pub fn suppress(&self, violations: ViolationSummary) -> (ViolationSummary, usize) {
+        let total = violations.details.len();
+        let remaining: Vec<Violation> = violations
+            .details
+            .into_iter()
+            .filter(|violation| !self.contains(violation))
+            .collect();
+        let suppressed = total - remaining.len();
+        (ViolationSummary::from_details(remaining), suppressed)
+    }
+
+    /// Replace the entries with the current violations, pruning ones that no longer occur
+    pub fn update(&mut self, violations: &ViolationSummary) -> BaselineUpdate {
+        let current: BTreeSet<BaselineEntry> = violations.details.iter().map(BaselineEntry::from_violation).collect();
+        let previous: BTreeSet<BaselineEntry> = self.entries.drain(..).collect();
+
+        let update = BaselineUpdate {
+            added: current.difference(&previous).count(),
+            pruned: previous.difference(&current).count(),
+        };
+        self.entries = current.into_iter().collect();
+        update
+    }
+}
+
+/// Load a baseline file; a missing file is an empty baseline
+pub fn load_baseline(path: &Path, format: BaselineFormat) -> Result<Baseline> {
+    if !path.exists() {
+        return Ok(Baseline::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read baseline: {}", path.display()))?;
+    let baseline = match format {
+        BaselineFormat::Json => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline JSON: {}", path.display()))?,
+        BaselineFormat::Toml => toml::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline TOML: {}", path.display()))?,
+    };
+
+    Ok(baseline)
+}
+
+pub fn save_baseline(path: &Path, baseline: &Baseline, format: BaselineFormat) -> Result<()> {
+    let content = match format {
+        BaselineFormat::Json => serde_json::to_string_pretty(baseline)? + "\n",
+        BaselineFormat::Toml => toml::to_string_pretty(baseline)?,
+    };
+    fs::write(path, content)
+        .with_context(|| format!("Failed to write baseline: {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::ViolationLevel;
+
+    fn violation(name: &str, version: &str, license: &str) -> Violation {
+        Violation {
+            package_name: name.to_string(),
+            package_version: Some(version.to_string()),
+            license: Some(license.to_string()),
+            violation_level: ViolationLevel::Forbidden,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_update_prunes_stale_entries() {
+        let mut baseline = Baseline::default();
+        let first = ViolationSummary::from_details(vec![
+            violation("gpl-thing", "1.0", "GPL-3.0"),
+            violation("old-lib", "0.1", "AGPL-3.0"),
+        ]);
+        assert_eq!(baseline.update(&first), BaselineUpdate { added: 2, pruned: 0 });
+
+        // old-lib was removed; gpl-thing was upgraded, so its old entry is stale too
+        let second = ViolationSummary::from_details(vec![violation("gpl-thing", "1.1", "GPL-3.0")]);
+        assert_eq!(baseline.update(&second), BaselineUpdate { added: 1, pruned: 2 });
+        assert_eq!(baseline.entries.len(), 1);
+
+        // A re-added package is no longer masked
+        let (remaining, suppressed) = baseline.suppress(ViolationSummary::from_details(vec![
+            violation("Gpl_Thing", "1.1", "GPL-3.0"),
+            violation("old-lib", "0.1", "AGPL-3.0"),
+        ]));
+        assert_eq!(suppressed, 1);
+        assert_eq!(remaining.errors, 1);
+        assert_eq!(remaining.details[0].package_name, "old-lib");
+    }
+
+    #[test]
+    fn test_baseline_round_trips_in_both_formats() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut baseline = Baseline::default();
+        baseline.update(&ViolationSummary::from_details(vec![violation("gpl-thing", "1.0", "GPL-3.0")]));
+
+        for (file, format) in [("baseline.json", BaselineFormat::Json), ("baseline.toml", BaselineFormat::Toml)] {
+            let path = temp_dir.path().join(file);
+            assert_eq!(BaselineFormat::from_path(&path), format);
+            save_baseline(&path, &baseline, format).unwrap();
+            assert_eq!(load_baseline(&path, format).unwrap(), baseline);
+        }
+
+        assert_eq!(load_baseline(&temp_dir.path().join("missing.json"), BaselineFormat::Json).unwrap(), Baseline::default());
+    }
+}
@@ -1,5 +1,11 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use py_license_auditor::i18n::Locale;
+use py_license_auditor::output::JsonIndent;
+use py_license_auditor::license::DisplayName;
+use py_license_auditor::policy::ViolationLevel;
 
 #[derive(Parser)]
 #[command(name = "py-license-auditor")]
@@ -21,34 +27,7 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Run license audit on packages
-    Check {
-        /// Path to site-packages directory or virtual environment
-        path: Option<PathBuf>,
-
-        /// Output format
-        #[arg(short, long)]
-        format: Option<OutputFormat>,
-
-        /// Output file (default: stdout)
-        #[arg(short, long)]
-        output: Option<PathBuf>,
-
-        /// Include packages without license information
-        #[arg(long)]
-        include_unknown: bool,
-
-        /// Show errors only
-        #[arg(short, long)]
-        quiet: bool,
-
-        /// Show detailed information
-        #[arg(short, long)]
-        verbose: bool,
-
-        /// Exit with code 0 even on violations
-        #[arg(long)]
-        exit_zero: bool,
-    },
+    Check(Box<CheckArgs>),
     /// Initialize configuration with preset policy
     Init {
         /// Policy preset
@@ -70,6 +49,36 @@ pub enum Commands {
         /// Output format for changes
         #[arg(short, long)]
         format: Option<OutputFormat>,
+
+        /// Merge duplicate or overlapping entries in .exceptions.toml
+        #[arg(long)]
+        dedup_exceptions: bool,
+
+        /// Take violations from a saved `check --format json` report instead of scanning
+        #[arg(long, value_name = "REPORT", conflicts_with = "path")]
+        from_report: Option<PathBuf>,
+
+        /// Violation levels to except, comma-separated (default: all)
+        #[arg(long, value_delimiter = ',', value_name = "LEVELS")]
+        levels: Option<Vec<FailOnArg>>,
+
+        /// Versions each new exception covers: the installed one, or a range up to the next minor/major release
+        #[arg(long, value_enum, default_value = "exact")]
+        version_scope: VersionScopeArg,
+
+        /// Also auto-except forbidden licenses (refused by default)
+        #[arg(long)]
+        include_forbidden: bool,
+    },
+    /// Manage recorded exceptions (.exceptions.toml)
+    Exceptions {
+        /// Append exceptions from a CSV file (name, version, reason, added_by, expires)
+        #[arg(long, value_name = "CSV")]
+        import: PathBuf,
+
+        /// Show what would be imported without writing
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Show or validate configuration
     Config {
@@ -80,14 +89,463 @@ pub enum Commands {
         /// Validate configuration file
         #[arg(long)]
         validate: bool,
+
+        /// Print the effective license policy (for compliance docs)
+        #[arg(long)]
+        show_policy: bool,
+
+        /// Compare two policy files (pyproject.toml, preset, or policy TOML/JSON)
+        #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+        diff_policy: Option<Vec<PathBuf>>,
+
+        /// Scan the environment and report how many violations the policy exceptions suppress
+        #[arg(long)]
+        exception_coverage: bool,
+
+        /// Copy exceptions from .exceptions.toml into pyproject.toml, or the other way round
+        #[arg(long, value_name = "DIRECTION")]
+        migrate_exceptions: Option<MigrateExceptionsArg>,
+
+        /// Check the policy against sample licenses ([[cases]] with license and expected level); exits 1 on any mismatch
+        #[arg(long, value_name = "CASES")]
+        test_policy: Option<PathBuf>,
+
+        /// Format for --show-policy, --diff-policy, --exception-coverage and --test-policy (markdown = human-readable)
+        #[arg(long, default_value = "markdown")]
+        format: PolicyFormatArg,
     },
 }
 
-#[derive(Clone, ValueEnum)]
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Path to site-packages directory or virtual environment
+    pub path: Option<PathBuf>,
+
+    /// Output format
+    #[arg(short, long)]
+    pub format: Option<OutputFormat>,
+
+    /// Output file (default: stdout)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Include packages without license information
+    #[arg(long)]
+    pub include_unknown: bool,
+
+    /// Show errors only
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Show detailed information
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Exit with code 0 even on violations
+    #[arg(long)]
+    pub exit_zero: bool,
+
+    /// Component order for SBOM formats (cyclonedx, spdx)
+    #[arg(long, default_value = "name")]
+    pub sbom_sort: SbomSortArg,
+
+    /// Produce reproducible output (content-derived ids, no wall-clock timestamps)
+    #[arg(long)]
+    pub deterministic: bool,
+
+    /// Record the scanned environment (uv.lock, Python, site-packages, tool version)
+    #[arg(long)]
+    pub include_environment: bool,
+
+    /// How JUnit output reports review-required and unknown licenses
+    #[arg(long, default_value = "skipped")]
+    pub junit_warnings: JunitWarningsArg,
+
+    /// Write packages as they are extracted (json-lines and csv only; rows in completion order)
+    #[arg(long, conflicts_with_all = ["baseline", "diff_base"])]
+    pub stream: bool,
+
+    /// Write an NDJSON event log to stdout while the audit runs (one event per package, then a summary);
+    /// the report itself goes to --output
+    #[arg(long, conflicts_with_all = ["stream", "tui", "count_only", "explain"])]
+    pub events: bool,
+
+    /// List only packages with OSI-approved or non-OSI licenses (summary still covers all)
+    #[arg(long)]
+    pub only: Option<OnlyArg>,
+
+    /// Package name spelling in reports; matching always uses normalized names
+    #[arg(long, value_enum, default_value = "dist-info")]
+    pub display_name: DisplayNameArg,
+
+    /// Ignore violations recorded in this baseline file
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Rewrite the baseline with the current violations, pruning entries that no longer occur
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// Baseline file format (default: from the file extension)
+    #[arg(long, requires = "baseline")]
+    pub baseline_format: Option<BaselineFormatArg>,
+
+    /// Print a PR comment listing only violations absent from the baseline; the exit status depends on those alone
+    #[arg(long, requires = "baseline", conflicts_with_all = ["update_baseline", "count_only", "tui", "list_licenses", "group_by_license", "flatten_violations", "events"])]
+    pub comment: bool,
+
+    /// Only fail on violations introduced since this git ref (compares uv.lock)
+    #[arg(long, value_name = "GIT_REF")]
+    pub diff_base: Option<String>,
+
+    /// Print only the number of violations (exit code as usual)
+    #[arg(long, conflicts_with = "stream")]
+    pub count_only: bool,
+
+    /// Inventory vendored license files in this directory (file name = package name) instead of a Python environment
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["path", "stream", "diff_base"])]
+    pub license_dir: Option<PathBuf>,
+
+    /// Scan this site-packages directly, without uv.lock (reports what is installed, not what is pinned)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["path", "stream", "diff_base", "license_dir"])]
+    pub scan_site_packages: Option<PathBuf>,
+
+    /// Audit downloaded wheels (`pip download -d DIR`) offline, without installing them
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["path", "stream", "diff_base", "license_dir", "scan_site_packages", "python_version"])]
+    pub wheels_dir: Option<PathBuf>,
+
+    /// Scan a conda/mamba environment via its conda-meta records (default: $CONDA_PREFIX when there is no uv.lock)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["path", "stream", "diff_base", "license_dir", "scan_site_packages", "wheels_dir", "python_version"])]
+    pub conda_prefix: Option<PathBuf>,
+
+    /// Audit a zipapp (.pyz) or zipped site-packages via the *.dist-info/METADATA entries inside
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["path", "stream", "diff_base", "license_dir", "scan_site_packages", "wheels_dir", "conda_prefix", "python_version", "explain"])]
+    pub zip: Option<PathBuf>,
+
+    /// Check the components of an existing CycloneDX JSON or SPDX SBOM instead of scanning
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["path", "stream", "diff_base", "license_dir", "scan_site_packages", "wheels_dir", "conda_prefix", "zip", "python_version", "explain", "resolve_license_urls"])]
+    pub sbom: Option<PathBuf>,
+
+    /// Violation categories that fail the run, comma-separated (default: forbidden when fail_on_violations is set)
+    #[arg(long, value_delimiter = ',', value_name = "LEVELS")]
+    pub fail_on: Option<Vec<FailOnArg>>,
+
+    /// Fail when more than N packages require review (excepted packages not counted)
+    #[arg(long, value_name = "N")]
+    pub max_review: Option<usize>,
+
+    /// Gitignore-style list of package name patterns to leave out of the audit (default: .auditignore if present)
+    #[arg(long, value_name = "PATH")]
+    pub ignore_file: Option<PathBuf>,
+
+    /// Don't allow the built-in stdlib backports (typing-extensions, zipp, ...) regardless of license; a configured backport_allowlist still applies
+    #[arg(long)]
+    pub no_builtin_allowlist: bool,
+
+    /// POST the JSON report to this URL (retried; a failed delivery only warns unless --webhook-required)
+    #[arg(long, value_name = "URL", conflicts_with_all = ["stream", "count_only"])]
+    pub webhook: Option<String>,
+
+    /// Extra header for --webhook requests, e.g. 'Authorization: Bearer TOKEN' (repeatable)
+    #[arg(long, value_name = "HEADER", requires = "webhook")]
+    pub webhook_header: Vec<String>,
+
+    /// Fail the run when the --webhook delivery fails
+    #[arg(long, requires = "webhook")]
+    pub webhook_required: bool,
+
+    /// Fetch and classify `Project-URL: License` links of packages without license metadata (network, cached)
+    #[arg(long, conflicts_with = "stream")]
+    pub resolve_license_urls: bool,
+
+    /// Keep the project's own package (virtual or editable "." source in uv.lock) in the audit
+    #[arg(long)]
+    pub include_self: bool,
+
+    /// Refuse to scan a uv.lock listing more than N packages (guards against corrupted or untrusted lockfiles)
+    #[arg(long, value_name = "N")]
+    pub max_packages: Option<usize>,
+
+    /// Threads for extracting licenses from uv.lock packages (default: one per CPU; 1 = sequential)
+    #[arg(long, value_name = "N", conflicts_with = "stream")]
+    pub parallelism: Option<NonZeroUsize>,
+
+    /// Python version of the venv to scan (e.g. 3.12) when it has several lib/pythonX.Y directories
+    #[arg(long, value_name = "X.Y", conflicts_with = "license_dir")]
+    pub python_version: Option<String>,
+
+    /// Append to --output instead of overwriting (csv and json-lines; the CSV header is written once)
+    #[arg(long, requires = "output")]
+    pub append: bool,
+
+    /// Browse the report in an interactive terminal UI (build with `--features tui`)
+    #[arg(long, conflicts_with_all = ["stream", "count_only", "output"])]
+    pub tui: bool,
+
+    /// Language of table output and violation messages: en or ja (default: en)
+    #[arg(long, value_name = "LOCALE")]
+    pub locale: Option<Locale>,
+
+    /// Borderless one-line-per-issue table (`package  version  license  STATUS`), for CI logs
+    #[arg(long)]
+    pub compact: bool,
+
+    /// Disable ANSI colors in table output
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Print only the distinct licenses with package counts (one per line, or a JSON array with --format json)
+    #[arg(long, conflicts_with_all = ["stream", "tui", "count_only", "flatten_violations"])]
+    pub list_licenses: bool,
+
+    /// Print each violating license once with its affected packages (JSON array with --format json)
+    #[arg(long, conflicts_with_all = ["stream", "tui", "count_only", "flatten_violations", "list_licenses"])]
+    pub group_by_license: bool,
+
+    /// Fail instead of warning when the tool version does not match min_version / required_version
+    #[arg(long)]
+    pub enforce_version: bool,
+
+    /// Explain the license decision for one installed package (metadata, rule, exception)
+    #[arg(long, value_name = "PACKAGE")]
+    pub explain: Option<String>,
+
+    /// Compress --output (also implied by a .gz output file name); stdout is never compressed
+    #[arg(long, value_enum, requires = "output", conflicts_with_all = ["append", "stream"])]
+    pub compress: Option<CompressArg>,
+
+    #[command(flatten)]
+    pub csv: CsvArgs,
+
+    #[command(flatten)]
+    pub json: JsonArgs,
+
+    #[command(flatten)]
+    pub html: HtmlArgs,
+}
+
+impl CheckArgs {
+    /// Options that only apply to some output formats, when given, with those formats
+    fn format_specific_options(&self) -> Vec<(&'static str, &'static [OutputFormat])> {
+        use OutputFormat::*;
+        let options: [(bool, &'static str, &'static [OutputFormat]); 16] = [
+            (self.csv.columns.is_some(), "--columns", &[Csv]),
+            (self.csv.only_violations, "--only-violations", &[Csv]),
+            (self.csv.summary_csv.is_some(), "--summary-csv", &[Csv]),
+            (self.csv.delimiter.is_some(), "--delimiter", &[Csv]),
+            (self.json.include_raw_metadata, "--include-raw-metadata", &[Json, JsonLines]),
+            (self.json.flatten_violations, "--flatten-violations", &[Json, JsonLines]),
+            (self.json.indent.is_some(), "--indent", &[Json]),
+            (self.json.sort_keys, "--sort-keys", &[Json]),
+            (self.html.report_title.is_some(), "--report-title", &[Html]),
+            (self.html.report_logo.is_some(), "--report-logo", &[Html]),
+            (self.compact, "--compact", &[Table]),
+            (self.list_licenses, "--list-licenses", &[Table, Json]),
+            (self.group_by_license, "--group-by-license", &[Table, Json]),
+            (self.comment, "--comment", &[Table, PrComment]),
+            (self.stream, "--stream", &[JsonLines, Csv]),
+            (self.append, "--append", &[Csv, JsonLines]),
+        ];
+        options.into_iter().filter(|(given, _, _)| *given).map(|(_, flag, formats)| (flag, formats)).collect()
+    }
+
+    /// Reject options that do not apply to `format`. The format may come from pyproject.toml,
+    /// so this runs once it is resolved rather than while parsing; the error reads like clap's own.
+    pub fn check_format(&self, format: &OutputFormat) -> Result<(), clap::Error> {
+        let Some((flag, formats)) = self.format_specific_options().into_iter().find(|(_, formats)| !formats.contains(format)) else {
+            return Ok(());
+        };
+        let formats = formats
+            .iter()
+            .filter_map(|format| format.to_possible_value())
+            .map(|value| format!("--format {}", value.get_name()))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        let mut cli = Cli::command();
+        cli.build();
+        let check = cli.find_subcommand_mut("check").expect("check subcommand");
+        Err(check.error(ErrorKind::ArgumentConflict, format!("{} is only supported with {}", flag, formats)))
+    }
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "CSV output")]
+pub struct CsvArgs {
+    /// CSV columns, comma-separated (name, version, license, classifiers, osi, status, source, size, direct)
+    #[arg(long, value_name = "LIST")]
+    pub columns: Option<String>,
+
+    /// CSV of policy violations only (package, version, license, level, rule, message), for ticketing systems
+    #[arg(long, conflicts_with_all = ["stream", "columns", "append"])]
+    pub only_violations: bool,
+
+    /// Also write a per-license summary CSV (license, count, osi_approved) to this path
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["stream", "only_violations", "count_only"])]
+    pub summary_csv: Option<PathBuf>,
+
+    /// CSV field separator: a single character (e.g. ';') or 'tab' (default: ',')
+    #[arg(long, value_name = "CHAR")]
+    pub delimiter: Option<String>,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "JSON output")]
+pub struct JsonArgs {
+    /// Keep each package's verbatim `License:` metadata in JSON output, for detection disputes
+    #[arg(long)]
+    pub include_raw_metadata: bool,
+
+    /// Emit one flat record per violation instead of the report (json or json-lines, for log shippers)
+    #[arg(long, conflicts_with_all = ["stream", "tui", "count_only"])]
+    pub flatten_violations: bool,
+
+    /// Indentation of --format json output: a number of spaces or "tab" (default: 2)
+    #[arg(long, value_name = "N|tab")]
+    pub indent: Option<JsonIndent>,
+
+    /// Order license_types maps alphabetically in JSON output (stable diffs of committed reports)
+    #[arg(long)]
+    pub sort_keys: bool,
+
+    /// Add a SHA-256 of the package inventory (name, version, license) to JSON output, for change detection
+    #[arg(long, conflicts_with = "stream")]
+    pub include_hash: bool,
+
+    /// Add a license → package names index to JSON output (`packages_by_license`)
+    #[arg(long, conflicts_with = "stream")]
+    pub include_license_index: bool,
+}
+
+#[derive(Args)]
+#[command(next_help_heading = "HTML output")]
+pub struct HtmlArgs {
+    /// Title shown at the top of --format html output (default: "License Report")
+    #[arg(long, value_name = "TITLE")]
+    pub report_title: Option<String>,
+
+    /// PNG or SVG logo inlined into --format html output
+    #[arg(long, value_name = "PATH")]
+    pub report_logo: Option<PathBuf>,
+}
+
+#[derive(Clone, PartialEq, ValueEnum)]
 pub enum OutputFormat {
     Table,
     Json,
+    /// One JSON object per package, then a summary object
+    JsonLines,
     Csv,
+    /// CycloneDX 1.5 JSON SBOM
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// SPDX 2.3 JSON SBOM
+    Spdx,
+    /// JUnit XML for CI test report dashboards
+    Junit,
+    /// KDL document (package, summary and violations nodes)
+    Kdl,
+    Html,
+    /// Plain `name version license [STATUS]` lines for logs and email
+    Text,
+    /// Markdown pull request comment (summary, violations, collapsed package list)
+    PrComment,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum SbomSortArg {
+    /// Sort components by name, then version
+    Name,
+    /// Keep extraction order
+    None,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DisplayNameArg {
+    /// `Name:` from METADATA (canonical PyPI spelling)
+    Pypi,
+    /// PEP 503 normalized (lowercase, dashes)
+    Normalized,
+    /// As in the .dist-info directory name
+    DistInfo,
+}
+
+impl From<DisplayNameArg> for DisplayName {
+    fn from(arg: DisplayNameArg) -> Self {
+        match arg {
+            DisplayNameArg::Pypi => DisplayName::Pypi,
+            DisplayNameArg::Normalized => DisplayName::Normalized,
+            DisplayNameArg::DistInfo => DisplayName::DistInfo,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum OnlyArg {
+    /// OSI-approved licenses
+    Osi,
+    /// Non-OSI, custom or unknown licenses
+    NonOsi,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum CompressArg {
+    Gzip,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum MigrateExceptionsArg {
+    /// .exceptions.toml → [tool.py-license-auditor.policy] exceptions
+    ToPyproject,
+    /// pyproject.toml policy exceptions → .exceptions.toml
+    ToFile,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum PolicyFormatArg {
+    Markdown,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum VersionScopeArg {
+    /// Only the installed version (`1.4.2`)
+    Exact,
+    /// Patch releases of the installed minor version (`>=1.4.2,<1.5`)
+    Minor,
+    /// Releases up to the next major version (`>=1.4.2,<2`)
+    Major,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum FailOnArg {
+    Forbidden,
+    #[value(alias = "review")]
+    ReviewRequired,
+    Unknown,
+}
+
+impl From<FailOnArg> for ViolationLevel {
+    fn from(level: FailOnArg) -> Self {
+        match level {
+            FailOnArg::Forbidden => ViolationLevel::Forbidden,
+            FailOnArg::ReviewRequired => ViolationLevel::ReviewRequired,
+            FailOnArg::Unknown => ViolationLevel::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum BaselineFormatArg {
+    Json,
+    Toml,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum JunitWarningsArg {
+    /// Report as <skipped>
+    Skipped,
+    /// Report as <error>
+    Error,
 }
 
 #[derive(Clone, ValueEnum)]
@@ -96,3 +554,73 @@ pub enum InitPreset {
     Yellow,
     Red,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_check(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(["py-license-auditor", "check"].iter().chain(args))
+    }
+
+    #[test]
+    fn test_summary_csv_rejects_stream_and_count_only() {
+        assert!(parse_check(&["--summary-csv", "s.csv"]).is_ok());
+        assert!(parse_check(&["--summary-csv", "s.csv", "--stream"]).is_err());
+        assert!(parse_check(&["--summary-csv", "s.csv", "--count-only"]).is_err());
+    }
+
+    #[test]
+    fn test_only_violations_rejects_stream() {
+        assert!(parse_check(&["--format", "csv", "--only-violations"]).is_ok());
+        assert!(parse_check(&["--format", "csv", "--only-violations", "--stream"]).is_err());
+    }
+
+    #[test]
+    fn test_include_hash_rejects_stream() {
+        assert!(parse_check(&["--include-hash"]).is_ok());
+        assert!(parse_check(&["--include-hash", "--stream"]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_license_urls_rejects_stream() {
+        assert!(parse_check(&["--resolve-license-urls"]).is_ok());
+        assert!(parse_check(&["--resolve-license-urls", "--stream"]).is_err());
+    }
+
+    fn check_args(args: &[&str]) -> CheckArgs {
+        match parse_check(args).unwrap().command {
+            Commands::Check(args) => *args,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_stream_rejects_baseline_and_diff_base() {
+        assert!(parse_check(&["--stream", "--format", "csv"]).is_ok());
+        assert!(parse_check(&["--stream", "--baseline", "b.json"]).is_err());
+        assert!(parse_check(&["--stream", "--diff-base", "main"]).is_err());
+    }
+
+    #[test]
+    fn test_format_specific_options() {
+        assert!(check_args(&[]).check_format(&OutputFormat::Spdx).is_ok());
+
+        // The format may come from pyproject.toml, so it is checked against the resolved one
+        let args = check_args(&["--columns", "name", "--delimiter", "tab"]);
+        assert!(args.check_format(&OutputFormat::Csv).is_ok());
+        let error = args.check_format(&OutputFormat::Json).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+        assert!(error.to_string().contains("--columns is only supported with --format csv"), "{}", error);
+
+        let args = check_args(&["--flatten-violations"]);
+        assert!(args.check_format(&OutputFormat::JsonLines).is_ok());
+        let error = args.check_format(&OutputFormat::Table).unwrap_err().to_string();
+        assert!(error.contains("--format json or --format json-lines"), "{}", error);
+
+        assert!(check_args(&["--comment", "--baseline", "b.json"]).check_format(&OutputFormat::PrComment).is_ok());
+        assert!(check_args(&["--report-logo", "logo.png"]).check_format(&OutputFormat::Table).is_err());
+        assert!(check_args(&["--append", "--output", "audit.csv"]).check_format(&OutputFormat::Csv).is_ok());
+        assert!(check_args(&["--compact"]).check_format(&OutputFormat::Text).is_err());
+    }
+}
@@ -1,78 +1,889 @@
 use anyhow::Result;
 use std::fs;
-use std::path::PathBuf;
-use crate::cli::OutputFormat;
-use py_license_auditor::license::{extract_licenses_auto, create_report};
-use py_license_auditor::output::format_table_output;
-use py_license_auditor::config::load_config;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use crate::cli::{BaselineFormatArg, CheckArgs, FailOnArg, JsonArgs, JunitWarningsArg, OnlyArg, OutputFormat, SbomSortArg};
+use py_license_auditor::explain::{explain_package, format_explanation};
+use py_license_auditor::license::{
+    extract_all_licenses, extract_licenses_auto, with_parallelism, extract_licenses_from_dir, extract_licenses_from_wheels, extract_licenses_from_zip, extract_licenses_from_conda, detect_conda_prefix, create_report_with, collect_environment, finalize_package, stream_licenses_auto,
+    find_site_packages_path, find_site_packages_path_for, DisplayName, empty_environment_hint, missing_uv_lock_error, OsiFilter, SummaryAccumulator,
+};
+use py_license_auditor::diff_base::{diff_against_base, read_lock_at_ref, LicenseDiff};
+use py_license_auditor::python_version::{detect_python_version, requires_python_note};
+use py_license_auditor::uv_lock::{UvLockParser, PACKAGE_COUNT_WARNING};
+use py_license_auditor::audit_ignore::AuditIgnore;
+use py_license_auditor::i18n::Locale;
+use py_license_auditor::webhook::{check_url, parse_header, post_report};
+use py_license_auditor::output::{
+    format_csv_output, format_html_output, EventLog, format_json_lines_output, format_junit_output, format_kdl_output, format_license_groups, format_new_violations_comment, format_license_list, format_table_output, format_compact_output, format_plain_output, format_pr_comment, strip_ansi, load_logo, to_json_string, write_output_file, is_gzip_path, HtmlOptions, JunitWarnings,
+};
+use py_license_auditor::output::{csv, json_lines};
+use py_license_auditor::config::{apply_annotations, load_config, tool_version_mismatch, Config, PythonMismatch};
+use py_license_auditor::policy::{ViolationLevel, ViolationSummary};
+use py_license_auditor::baseline::{load_baseline, save_baseline, BaselineFormat};
+use py_license_auditor::sbom::{format_cyclonedx, format_spdx, read_sbom, SbomOptions, SbomSort};
 
-pub fn handle_check(
-    path: Option<PathBuf>,
-    format: Option<OutputFormat>,
-    output: Option<PathBuf>,
-    include_unknown: bool,
-    quiet: bool,
-    verbose: bool,
-    exit_zero: bool,
-) -> Result<()> {
+pub fn handle_check(args: CheckArgs) -> Result<()> {
     // Load configuration from pyproject.toml
-    let config = load_config()?;
-    
+    let mut config = load_config()?;
+
+    // Determine output format; options tied to another format are a usage error
+    let format = args.format.clone().unwrap_or_else(|| configured_format(&config));
+    if let Err(error) = args.check_format(&format) {
+        error.exit();
+    }
+
+    let CheckArgs {
+        path,
+        format: _,
+        output,
+        include_unknown,
+        quiet,
+        verbose,
+        exit_zero,
+        sbom_sort,
+        deterministic,
+        include_environment,
+        junit_warnings,
+        stream,
+        events,
+        only,
+        display_name,
+        baseline,
+        update_baseline,
+        baseline_format,
+        comment,
+        diff_base,
+        count_only,
+        license_dir,
+        scan_site_packages,
+        wheels_dir,
+        conda_prefix,
+        zip,
+        sbom,
+        fail_on,
+        max_review,
+        max_packages,
+        parallelism,
+        ignore_file,
+        no_builtin_allowlist,
+        webhook,
+        webhook_header,
+        webhook_required,
+        tui,
+        append,
+        python_version,
+        include_self,
+        resolve_license_urls,
+        compact,
+        locale,
+        no_color,
+        list_licenses,
+        group_by_license,
+        compress,
+        explain,
+        enforce_version,
+        csv: csv_args,
+        json: json_args,
+        html: html_args,
+    } = args;
+
+    // --count-only prints a bare number, nothing else
+    let quiet = quiet || count_only;
+
+    if let Some(policy) = config.policy.as_mut().filter(|policy| no_builtin_allowlist && policy.backport_allowlist.is_none()) {
+        policy.backport_allowlist = Some(vec![]);
+    }
+
+    // Pinned tool version: CI image updates must not silently change behavior
+    if let Some(mismatch) = tool_version_mismatch(&config, env!("CARGO_PKG_VERSION"))? {
+        if enforce_version {
+            anyhow::bail!("{} (--enforce-version)", mismatch);
+        }
+        if !quiet {
+            eprintln!("⚠️  {}", mismatch);
+        }
+    }
+
     // CLI arguments override config values
     let include_unknown = include_unknown || config.include_unknown.unwrap_or(false);
 
-    // Auto-detect uv.lock or fallback to site-packages
-    let packages = extract_licenses_auto(path, include_unknown)?;
-    
-    let mut report = create_report(packages);
-    
+    let JsonArgs { include_raw_metadata, flatten_violations, indent, sort_keys, include_hash, include_license_index } = json_args;
+    let only_violations = csv_args.only_violations;
+    if only_violations && (config.policy.is_none() || !config.check_violations.unwrap_or(false)) {
+        anyhow::bail!("--only-violations needs a policy with check_violations enabled");
+    }
+    let csv_options = csv_options(csv_args.columns.as_deref(), csv_args.delimiter.as_deref())?;
+    // Validate the logo before scanning
+    let html_options = HtmlOptions { title: html_args.report_title, logo: html_args.report_logo.as_deref().map(load_logo).transpose()? };
+
+    let fail_on = resolve_fail_on(fail_on, config.fail_on_violations.unwrap_or(false));
+    let locale = locale.unwrap_or_default();
+    let exit_policy = ExitPolicy { fail_on, max_review, quiet, exit_zero, locale };
+
+    let gzip = compress.is_some() || output.as_deref().is_some_and(is_gzip_path);
+    if gzip && (append || stream) {
+        anyhow::bail!("Compressed output (--compress or a .gz --output) is not supported with --append or --stream");
+    }
+
+    if tui && !cfg!(feature = "tui") {
+        anyhow::bail!("--tui is not available in this build; reinstall with `cargo install py-license-auditor --features tui`");
+    }
+
+    let filter = only.map(|only| match only {
+        OnlyArg::Osi => OsiFilter::Osi,
+        OnlyArg::NonOsi => OsiFilter::NonOsi,
+    });
+
+    // --python-version picks the lib/pythonX.Y directory up front
+    let path = match &python_version {
+        Some(version) if scan_site_packages.is_none() => Some(find_site_packages_path_for(path, Some(version))?),
+        _ => path,
+    };
+
+    if let Some(package_name) = &explain {
+        let site_packages = site_packages_to_scan(scan_site_packages.as_deref(), path, python_version.as_deref())?;
+        let policy = config.policy.as_ref().filter(|_| config.check_violations.unwrap_or(false));
+        let explanation = explain_package(&site_packages, package_name, policy, chrono::Utc::now().date_naive())?;
+        println!("{}", format_explanation(&explanation));
+        return Ok(());
+    }
+
+    let ignore = load_audit_ignore(ignore_file.as_deref(), quiet)?;
+    let webhook_headers = webhook_header.iter().map(|header| parse_header(header)).collect::<Result<Vec<_>>>()?;
+    if let Some(url) = &webhook {
+        check_url(url)?;
+    }
+
+    // Without uv.lock, an activated conda environment is scanned instead
+    let conda_prefix = conda_prefix.or_else(|| {
+        let default_scan = path.is_none() && license_dir.is_none() && scan_site_packages.is_none() && wheels_dir.is_none() && zip.is_none() && sbom.is_none();
+        if default_scan && UvLockParser::find_uv_lock().is_none() { detect_conda_prefix() } else { None }
+    });
+
+    if license_dir.is_none() && scan_site_packages.is_none() && wheels_dir.is_none() && zip.is_none() && sbom.is_none() && conda_prefix.is_none() {
+        check_requires_python(path.as_deref(), &config, quiet)?;
+        if let Some(lock_path) = UvLockParser::find_uv_lock() {
+            let lock_file = UvLockParser::parse_uv_lock(lock_path)?;
+            if let Some(max_packages) = max_packages {
+                UvLockParser::check_package_count(&lock_file, max_packages)?;
+            }
+            if lock_file.packages.len() > PACKAGE_COUNT_WARNING && !quiet {
+                eprintln!(
+                    "Warning: uv.lock lists {} packages (more than {}); it may be corrupted. Use --max-packages to fail instead",
+                    lock_file.packages.len(), PACKAGE_COUNT_WARNING
+                );
+            }
+        }
+    }
+
+    if stream {
+        // An activated conda environment is only found at runtime
+        if conda_prefix.is_some() {
+            anyhow::bail!("--stream is not supported for conda environments");
+        }
+        let options = StreamOptions { include_unknown, quiet, filter, append, include_raw_metadata, include_self, display_name: display_name.into(), ignore, locale };
+        let violations = stream_report(&format, path, output, &options, &csv_options, &config)?;
+        if let Some(violations) = &violations {
+            handle_violations(violations, &exit_policy);
+        }
+        return Ok(());
+    }
+
+    // A global -q is only merged in after parsing, so clap cannot check this one
+    if events && output.is_none() && !quiet {
+        anyhow::bail!("--events writes the event log to stdout; write the report with --output (or drop it with --quiet)");
+    }
+    let mut event_log = events.then(|| EventLog::new(io::stdout()));
+    let mut streamed = false;
+
+    let mut packages = if let Some(dir) = &license_dir {
+        extract_licenses_from_dir(dir, include_unknown)?
+    } else if scan_site_packages.is_some() {
+        let site_packages = site_packages_to_scan(scan_site_packages.as_deref(), None, python_version.as_deref())?;
+        if !quiet {
+            eprintln!("Scanning {} without uv.lock (installed packages, not pinned versions)", site_packages.display());
+        }
+        extract_all_licenses(&site_packages, include_unknown)?
+    } else if let Some(wheels) = &wheels_dir {
+        extract_licenses_from_wheels(wheels, include_unknown)?
+    } else if let Some(zip) = &zip {
+        extract_licenses_from_zip(zip, include_unknown)?
+    } else if let Some(sbom) = &sbom {
+        read_sbom(sbom, include_unknown)?
+    } else if let Some(prefix) = &conda_prefix {
+        if !quiet {
+            eprintln!("Scanning conda environment {}", prefix.display());
+        }
+        extract_licenses_from_conda(prefix, include_unknown)?
+    } else {
+        // uv.lock is required for the default scan
+        match event_log.as_mut() {
+            Some(event_log) => {
+                // Packages arrive in completion order; restore a stable order for the report
+                let mut packages = Vec::new();
+                stream_licenses_auto(path.clone(), include_unknown, include_self, |package| {
+                    event_log.package(&package)?;
+                    packages.push(package);
+                    Ok(())
+                })?;
+                packages.sort_by(|a, b| a.name.cmp(&b.name));
+                streamed = true;
+                packages
+            }
+            None => with_parallelism(parallelism, || extract_licenses_auto(path.clone(), include_unknown, include_self))?,
+        }
+    };
+    // Other sources are read in one pass: report their packages once extracted
+    if let Some(event_log) = event_log.as_mut().filter(|_| !streamed) {
+        packages.iter().try_for_each(|package| event_log.package(package))?;
+    }
+
+    // An empty scan of a site-packages directory: say whether the env is empty or the path is wrong
+    let scanned_environment = license_dir.is_none() && wheels_dir.is_none() && zip.is_none() && sbom.is_none() && conda_prefix.is_none();
+    if packages.is_empty() && scanned_environment && !quiet {
+        let site_packages = site_packages_to_scan(scan_site_packages.as_deref(), path.clone(), python_version.as_deref());
+        if let Some(hint) = site_packages.ok().and_then(|site_packages| empty_environment_hint(&site_packages)) {
+            eprintln!("ℹ️  {}", hint);
+        }
+    }
+
+    if let Some(ignore) = &ignore {
+        let ignored = ignore.apply(&mut packages);
+        if ignored > 0 && !quiet {
+            eprintln!("Ignored {} package(s) matching the ignore file", ignored);
+        }
+    }
+    if resolve_license_urls {
+        let resolved = py_license_auditor::license::resolve_license_urls(&mut packages);
+        if resolved > 0 && !quiet {
+            eprintln!("🔗 Resolved {} license(s) from Project-URL links", resolved);
+        }
+    }
+    if !include_raw_metadata {
+        packages.iter_mut().for_each(|package| package.raw_license = None);
+    }
+    DisplayName::from(display_name).apply(&mut packages);
+    if let Some(annotations) = &config.annotations {
+        apply_annotations(&mut packages, annotations);
+    }
+    let mut report = create_report_with(packages, config.osi_overrides.clone().unwrap_or_default());
+
+    let base_lock = match &diff_base {
+        Some(git_ref) => {
+            let lock_path = UvLockParser::find_uv_lock().ok_or_else(missing_uv_lock_error)?;
+            match read_lock_at_ref(git_ref, &lock_path)? {
+                Some(lock) => Some(lock),
+                None => anyhow::bail!(
+                    "uv.lock ({}) does not exist at '{}'; nothing to compare against (run without --diff-base for a full check)",
+                    lock_path.display(), git_ref
+                ),
+            }
+        }
+        None => None,
+    };
+
+    report.footer = config.report.clone().filter(|footer| !footer.is_empty());
+    if !deterministic {
+        report.generated_at = Some(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+    }
+
+    if include_environment {
+        // Deterministic reports must not embed machine-specific absolute paths
+        report.environment = Some(collect_environment(path, deterministic));
+    }
+
+    // Over the full inventory, before --only narrows the listing
+    if include_hash {
+        report.content_hash = Some(report.content_hash());
+    }
+    if include_license_index {
+        report.packages_by_license = Some(report.license_index());
+    }
+
     // Policy checking (if configured)
     if let Some(policy) = &config.policy {
         if config.check_violations.unwrap_or(false) {
-            let violations = policy.detect_violations(&report.packages);
-            
-            // Handle violations
-            if violations.total > 0 {
+            let today = chrono::Utc::now().date_naive();
+            let mut violations = policy.detect_violations_at(&report.packages, today, locale);
+            report.exception_coverage = Some(policy.exception_coverage(&report.packages, today));
+            if let (Some(git_ref), Some(base_lock)) = (&diff_base, &base_lock) {
+                let diff = diff_against_base(git_ref, base_lock, &report.packages, &violations.details);
                 if !quiet {
-                    eprintln!("License violations found: {} total ({} errors, {} warnings)", 
-                             violations.total, violations.errors, violations.warnings);
+                    print_diff_summary(&diff);
+                }
+                violations = ViolationSummary::from_details(diff.new_violations.clone());
+                report.diff = Some(diff);
+            }
+            if let Some(baseline_path) = &baseline {
+                let format = match baseline_format {
+                    Some(BaselineFormatArg::Json) => BaselineFormat::Json,
+                    Some(BaselineFormatArg::Toml) => BaselineFormat::Toml,
+                    None => BaselineFormat::from_path(baseline_path),
+                };
+                let mut known = load_baseline(baseline_path, format)?;
+                if update_baseline {
+                    let update = known.update(&violations);
+                    save_baseline(baseline_path, &known, format)?;
+                    if !quiet {
+                        eprintln!("📌 Baseline updated: {} entries ({} added, {} pruned)",
+                                 known.entries.len(), update.added, update.pruned);
+                    }
                 }
-                
-                if !exit_zero && config.fail_on_violations.unwrap_or(false) && violations.errors > 0 {
-                    eprintln!("Exiting with error due to forbidden licenses");
-                    std::process::exit(1);
+                let (remaining, suppressed) = known.suppress(violations);
+                if suppressed > 0 && !quiet && !comment {
+                    eprintln!("📌 {} baselined violation(s) ignored", suppressed);
                 }
+                violations = remaining;
             }
-            
             report.violations = Some(violations);
         }
     }
+    if let Some(event_log) = event_log.as_mut() {
+        event_log.summary(&report)?;
+    }
+
+    // Delivered before the exit code is decided, so failing audits reach the dashboard too
+    if let Some(url) = &webhook {
+        send_webhook(url, &webhook_headers, webhook_required, &report, quiet)?;
+    }
+
+    // --tui shows violations instead of failing on them; --list-licenses only summarizes.
+    // A PR comment, violations CSV or license grouping is written first: failing runs are exactly when it is needed.
+    let defer_exit = matches!(format, OutputFormat::PrComment) || only_violations || group_by_license || comment;
+    if let Some(violations) = report.violations.as_ref().filter(|_| !count_only && !tui && !list_licenses && !defer_exit) {
+        handle_violations(violations, &exit_policy);
+    }
 
-    // Determine output format
-    let format = format.unwrap_or_else(|| {
-        match config.format.as_deref() {
-            Some("json") => OutputFormat::Json,
-            Some("csv") => OutputFormat::Csv,
-            Some("table") => OutputFormat::Table,
-            _ => OutputFormat::Table,  // Default to table instead of JSON
+    if count_only {
+        let (count, failing) = count_only_result(report.violations.as_ref(), &exit_policy);
+        println!("{}", count);
+        if !failing.is_empty() {
+            eprintln!("{}", exit_policy.locale.format("violations.exit", &[&failing.join(", ")]));
+            std::process::exit(1);
         }
-    });
-    
+        return Ok(());
+    }
+
+    // Filter the listing only after the full environment was summarized and checked
+    if let Some(filter) = filter {
+        report.apply_filter(filter, &config.osi_overrides.clone().unwrap_or_default());
+    }
+
+    #[cfg(feature = "tui")]
+    if tui {
+        return browse_report(&report);
+    }
+
+    let sbom_options = SbomOptions {
+        sort: match sbom_sort {
+            SbomSortArg::Name => SbomSort::Name,
+            SbomSortArg::None => SbomSort::None,
+        },
+        deterministic,
+    };
+
+    if sort_keys {
+        report.summary.license_types.sort_keys();
+    }
+
     // Generate output
     let output_content = match format {
-        OutputFormat::Json => serde_json::to_string_pretty(&report)?,
-        OutputFormat::Table => format_table_output(&report, verbose),
-        OutputFormat::Csv => "CSV not implemented yet".to_string(),
+        _ if list_licenses => format_license_list(&report.summary, matches!(format, OutputFormat::Json))?,
+        _ if comment => {
+            let failing = report.violations.as_ref().map(|violations| failing_reasons(violations, &exit_policy)).unwrap_or_default();
+            format_new_violations_comment(&report, &failing)
+        }
+        _ if group_by_license => {
+            let details = report.violations.as_ref().map(|violations| violations.details.as_slice()).unwrap_or_default();
+            format_license_groups(details, matches!(format, OutputFormat::Json))?
+        }
+        _ if flatten_violations => {
+            let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+            json_lines::format_flat_violations(&report, &timestamp, matches!(format, OutputFormat::JsonLines))?
+        }
+        OutputFormat::Json => to_json_string(&report, indent.unwrap_or_default())?,
+        OutputFormat::JsonLines => format_json_lines_output(&report)?,
+        OutputFormat::Table => {
+            let table = if compact {
+                format_compact_output(&report, verbose, locale)
+            } else {
+                let collapse = config.summary.as_ref().map(|summary| summary.collapse.as_slice()).unwrap_or_default();
+                format_table_output(&report, verbose, collapse, locale)
+            };
+            if no_color { strip_ansi(&table) } else { table }
+        }
+        OutputFormat::Csv if only_violations => csv::format_violations_csv(&report, csv_options.delimiter),
+        OutputFormat::Csv => format_csv_output(&report, &csv_options),
+        OutputFormat::CycloneDx => format_cyclonedx(&report, &sbom_options)?,
+        OutputFormat::Spdx => format_spdx(&report, &sbom_options)?,
+        OutputFormat::Junit => {
+            let warnings = match junit_warnings {
+                JunitWarningsArg::Skipped => JunitWarnings::Skipped,
+                JunitWarningsArg::Error => JunitWarnings::Error,
+            };
+            format_junit_output(&report, warnings)
+        }
+        OutputFormat::Kdl => format_kdl_output(&report),
+        OutputFormat::Html => format_html_output(&report, &html_options),
+        OutputFormat::Text => format_plain_output(&report, locale),
+        OutputFormat::PrComment => {
+            let failing = report.violations.as_ref().map(|violations| failing_reasons(violations, &exit_policy)).unwrap_or_default();
+            let exit_code = if failing.is_empty() || exit_policy.exit_zero { 0 } else { 1 };
+            format_pr_comment(&report, &failing, exit_code)
+        }
     };
 
     match output {
-        Some(path) => fs::write(path, output_content)?,
+        Some(path) if append => {
+            let header = matches!(format, OutputFormat::Csv).then(|| csv::csv_header(&csv_options));
+            append_output(&path, &output_content, header.as_deref())?;
+        }
+        Some(path) => write_output_file(&path, &output_content, gzip)?,
         None => {
             if !quiet {
                 println!("{}", output_content);
             }
         }
     }
+    if let Some(path) = &csv_args.summary_csv {
+        write_output_file(path, &csv::format_summary_csv(&report.summary, csv_options.delimiter), is_gzip_path(path))?;
+    }
+
+    if let Some(violations) = report.violations.as_ref().filter(|_| defer_exit) {
+        handle_violations(violations, &exit_policy);
+    }
+    Ok(())
+}
+
+/// Run the report browser and save the exceptions added in it
+#[cfg(feature = "tui")]
+fn browse_report(report: &py_license_auditor::LicenseReport) -> Result<()> {
+    use py_license_auditor::exceptions::{get_exceptions_file_path, load_exceptions, save_exceptions};
+
+    let added = py_license_auditor::tui::run_tui(report)?;
+    if !added.is_empty() {
+        let mut exceptions = load_exceptions()?;
+        let count = added.len();
+        for exception in added {
+            exceptions.add_exception(exception);
+        }
+        save_exceptions(&exceptions)?;
+        eprintln!("✅ Added {} exception(s) to {}", count, get_exceptions_file_path().display());
+    }
+    Ok(())
+}
+
+/// `format` from the config file when `--format` is not given (default: table)
+fn configured_format(config: &Config) -> OutputFormat {
+    match config.format.as_deref() {
+        Some("json") => OutputFormat::Json,
+        Some("json-lines") => OutputFormat::JsonLines,
+        Some("csv") => OutputFormat::Csv,
+        Some("cyclonedx") => OutputFormat::CycloneDx,
+        Some("spdx") => OutputFormat::Spdx,
+        Some("junit") => OutputFormat::Junit,
+        Some("kdl") => OutputFormat::Kdl,
+        Some("html") => OutputFormat::Html,
+        Some("text") => OutputFormat::Text,
+        Some("pr-comment") => OutputFormat::PrComment,
+        Some("table") => OutputFormat::Table,
+        _ => OutputFormat::Table,  // Default to table instead of JSON
+    }
+}
+
+/// Resolve `--columns` and `--delimiter`; `size` and `direct` read their values from uv.lock
+fn csv_options(columns: Option<&str>, delimiter: Option<&str>) -> Result<csv::CsvOptions> {
+    let delimiter = delimiter.map(csv::parse_delimiter).transpose()?.unwrap_or(',');
+    let Some(columns) = columns else {
+        return Ok(csv::CsvOptions { delimiter, ..Default::default() });
+    };
+    let columns = csv::parse_columns(columns)?;
+    if columns.is_empty() {
+        anyhow::bail!("--columns needs at least one column");
+    }
+
+    let locked = if columns.iter().any(|column| column.needs_lock()) {
+        let lock_path = UvLockParser::find_uv_lock().ok_or_else(missing_uv_lock_error)?;
+        UvLockParser::locked_package_info(&UvLockParser::parse_uv_lock(lock_path)?)
+    } else {
+        Default::default()
+    };
+    Ok(csv::CsvOptions { columns, locked, delimiter })
+}
+
+/// Note (or fail, per `python_mismatch`) when the audited interpreter is outside requires-python
+fn check_requires_python(path: Option<&Path>, config: &Config, quiet: bool) -> Result<()> {
+    let mode = config.python_mismatch.unwrap_or_default();
+    if mode == PythonMismatch::Ignore {
+        return Ok(());
+    }
+
+    let requires_python = UvLockParser::find_uv_lock()
+        .and_then(|lock| UvLockParser::parse_uv_lock(lock).ok())
+        .and_then(|lock| lock.requires_python);
+    let version = find_site_packages_path(path.map(Path::to_path_buf))
+        .ok()
+        .and_then(|site_packages| detect_python_version(&site_packages));
+
+    if let (Some(requires_python), Some(version)) = (requires_python, version) {
+        if let Some(note) = requires_python_note(&requires_python, &version) {
+            if mode == PythonMismatch::Error {
+                anyhow::bail!(note);
+            }
+            if !quiet {
+                eprintln!("ℹ️  {}", note);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_diff_summary(diff: &LicenseDiff) {
+    eprintln!("🔀 Compared to {}: {} new or changed packages, {} new violations",
+             diff.base_ref, diff.new_packages.len(), diff.new_violations.len());
+    if !diff.new_licenses.is_empty() {
+        eprintln!("   New licenses: {}", diff.new_licenses.join(", "));
+    }
+}
+
+/// Which violation categories fail the run
+struct ExitPolicy {
+    fail_on: Vec<ViolationLevel>,
+    /// `--max-review`: ceiling on review-required packages, independent of `fail_on`
+    max_review: Option<usize>,
+    quiet: bool,
+    exit_zero: bool,
+    locale: Locale,
+}
+
+/// Levels that fail the run: `--fail-on` overrides `fail_on_violations`, which fails on
+/// forbidden licenses only
+fn resolve_fail_on(fail_on: Option<Vec<FailOnArg>>, fail_on_violations: bool) -> Vec<ViolationLevel> {
+    match fail_on {
+        Some(levels) => levels.into_iter().map(ViolationLevel::from).collect(),
+        None if fail_on_violations => vec![ViolationLevel::Forbidden],
+        None => vec![],
+    }
+}
+
+/// Report violations on stderr and exit non-zero on the `--fail-on` categories
+/// or when review-required packages exceed `--max-review`
+fn handle_violations(violations: &ViolationSummary, policy: &ExitPolicy) {
+    if violations.total == 0 {
+        return;
+    }
+    if !policy.quiet {
+        eprintln!("{}", policy.locale.format("violations.found", &[&violations.total, &violations.errors, &violations.review_required, &violations.unknown]));
+    }
+
+    let failing = failing_reasons(violations, policy);
+    if !policy.exit_zero && !failing.is_empty() {
+        eprintln!("{}", policy.locale.format("violations.exit", &[&failing.join(", ")]));
+        std::process::exit(1);
+    }
+}
+
+/// Why the violations fail the run under `policy` (empty when they do not)
+fn failing_reasons(violations: &ViolationSummary, policy: &ExitPolicy) -> Vec<String> {
+    // Entries marked `severity = "note"` are reported but never fail the run
+    let blocking = |level: &ViolationLevel| {
+        violations.count(level) - violations.details.iter().filter(|v| &v.violation_level == level && v.is_note()).count()
+    };
+    let mut failing: Vec<String> = policy.fail_on
+        .iter()
+        .filter(|level| blocking(level) > 0)
+        .map(|level| policy.locale.text(match level {
+            ViolationLevel::Forbidden => "reason.forbidden",
+            ViolationLevel::ReviewRequired => "reason.review_required",
+            _ => "reason.unknown",
+        }).to_string())
+        .collect();
+    if let Some(max_review) = policy.max_review {
+        let review_required = blocking(&ViolationLevel::ReviewRequired);
+        if review_required > max_review {
+            failing.push(policy.locale.format("reason.max_review", &[&review_required, &max_review, &(review_required - max_review)]));
+        }
+    }
+    failing
+}
+
+/// `--scan-site-packages` (a venv or a site-packages directory) when given, otherwise the
+/// environment of the project at `path`
+fn site_packages_to_scan(scan_site_packages: Option<&Path>, path: Option<PathBuf>, python_version: Option<&str>) -> Result<PathBuf> {
+    match scan_site_packages {
+        Some(site_packages) => find_site_packages_path_for(Some(site_packages.to_path_buf()), python_version),
+        None => find_site_packages_path(path),
+    }
+}
+
+/// `--count-only`: the total violation count, and why it fails the run (empty when it does not)
+fn count_only_result(violations: Option<&ViolationSummary>, policy: &ExitPolicy) -> (usize, Vec<String>) {
+    match violations {
+        Some(violations) if !policy.exit_zero => (violations.total, failing_reasons(violations, policy)),
+        Some(violations) => (violations.total, vec![]),
+        None => (0, vec![]),
+    }
+}
+
+struct StreamOptions {
+    include_unknown: bool,
+    quiet: bool,
+    filter: Option<OsiFilter>,
+    append: bool,
+    include_raw_metadata: bool,
+    include_self: bool,
+    display_name: DisplayName,
+    ignore: Option<AuditIgnore>,
+    locale: Locale,
+}
+
+/// `--ignore-file`, or `.auditignore` in the project root when present
+fn load_audit_ignore(path: Option<&Path>, quiet: bool) -> Result<Option<AuditIgnore>> {
+    // The project root is where uv.lock was found; without one, the current directory
+    let project_root = UvLockParser::find_uv_lock()
+        .and_then(|lock| lock.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."));
+    let Some((path, ignore)) = AuditIgnore::resolve(path, &project_root)? else {
+        return Ok(None);
+    };
+    if !quiet {
+        eprintln!("Using ignore file {}", path.display());
+    }
+    Ok(Some(ignore))
+}
+
+/// `--webhook`: deliver the JSON report; a failed delivery only warns unless required
+fn send_webhook(url: &str, headers: &[(String, String)], required: bool, report: &py_license_auditor::LicenseReport, quiet: bool) -> Result<()> {
+    match post_report(url, headers, &serde_json::to_string(report)?) {
+        Ok(()) => {
+            if !quiet {
+                eprintln!("📤 Report sent to {}", url);
+            }
+            Ok(())
+        }
+        Err(error) if required => Err(error),
+        Err(error) => {
+            eprintln!("⚠️  {:#}", error);
+            Ok(())
+        }
+    }
+}
 
+/// For `--append`: whether the CSV file still needs a header. An existing
+/// header must match, so one file never mixes column layouts.
+fn needs_csv_header(path: &Path, header: &str) -> Result<bool> {
+    let existing = match fs::read_to_string(path) {
+        Ok(existing) => existing,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(true),
+        Err(error) => return Err(error.into()),
+    };
+    match existing.lines().next() {
+        None => Ok(true),
+        Some(line) if line == header => Ok(false),
+        Some(line) => anyhow::bail!(
+            "Cannot append to {}: its CSV header '{}' differs from '{}'",
+            path.display(), line, header
+        ),
+    }
+}
+
+/// `--append`: add `content` to the end of `path`. For CSV (`csv_header` given) the
+/// header line of `content` is dropped when the file already starts with it.
+fn append_output(path: &Path, content: &str, csv_header: Option<&str>) -> Result<()> {
+    let mut content = content;
+    if let Some(header) = csv_header {
+        if !needs_csv_header(path, header)? {
+            content = content.split_once('\n').map_or("", |(_, rows)| rows);
+        }
+    }
+    fs::OpenOptions::new().create(true).append(true).open(path)?.write_all(content.as_bytes())?;
     Ok(())
 }
+
+/// Write packages as they are extracted instead of collecting the full report first.
+/// Rows appear in completion order; the summary is accumulated along the way.
+fn stream_report(
+    format: &OutputFormat,
+    path: Option<PathBuf>,
+    output: Option<PathBuf>,
+    options: &StreamOptions,
+    csv_options: &csv::CsvOptions,
+    config: &Config,
+) -> Result<Option<ViolationSummary>> {
+    let mut write_header = true;
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) if options.append => {
+            if matches!(format, OutputFormat::Csv) {
+                write_header = needs_csv_header(&path, &csv::csv_header(csv_options))?;
+            }
+            Box::new(BufWriter::new(fs::OpenOptions::new().create(true).append(true).open(path)?))
+        }
+        Some(path) => Box::new(BufWriter::new(fs::File::create(path)?)),
+        None if options.quiet => Box::new(io::sink()),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    let policy = config.policy.as_ref().filter(|_| config.check_violations.unwrap_or(false));
+    let osi_overrides = config.osi_overrides.clone().unwrap_or_default();
+    let mut summary = SummaryAccumulator::with_osi_overrides(osi_overrides.clone());
+    let mut violation_details = Vec::new();
+    let today = chrono::Utc::now().date_naive();
+
+    if matches!(format, OutputFormat::Csv) && write_header {
+        writeln!(writer, "{}", csv::csv_header(csv_options))?;
+    }
+
+    stream_licenses_auto(path, options.include_unknown, options.include_self, |package| {
+        if options.ignore.as_ref().is_some_and(|ignore| ignore.is_ignored(&package.name)) {
+            return Ok(());
+        }
+        let mut package = finalize_package(package);
+        if !options.include_raw_metadata {
+            package.raw_license = None;
+        }
+        options.display_name.apply(std::slice::from_mut(&mut package));
+        if let Some(annotations) = &config.annotations {
+            apply_annotations(std::slice::from_mut(&mut package), annotations);
+        }
+        summary.add(&package);
+        let mut level = None;
+        if let Some(policy) = policy {
+            let details = policy.detect_violations_at(std::slice::from_ref(&package), today, options.locale).details;
+            level = details.first().map(|v| v.violation_level.clone());
+            violation_details.extend(details);
+        }
+        if options.filter.is_some_and(|filter| !filter.matches(&package, &osi_overrides)) {
+            return Ok(());
+        }
+
+        match format {
+            OutputFormat::Csv => writeln!(writer, "{}", csv::csv_row(&package, level.as_ref(), csv_options))?,
+            _ => writeln!(writer, "{}", json_lines::package_line(&package)?)?,
+        }
+        Ok(())
+    })?;
+
+    let violations = policy.map(|_| ViolationSummary::from_details(violation_details));
+    if matches!(format, OutputFormat::JsonLines) {
+        writeln!(writer, "{}", json_lines::summary_line(&summary.finish(), violations.as_ref())?)?;
+    }
+    writer.flush()?;
+
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use py_license_auditor::policy::{RuleSeverity, Violation};
+
+    fn summary(levels: &[ViolationLevel]) -> ViolationSummary {
+        ViolationSummary::from_details(levels.iter().map(|level| Violation { violation_level: level.clone(), ..Default::default() }).collect())
+    }
+
+    fn exit_policy(fail_on: Vec<ViolationLevel>, max_review: Option<usize>) -> ExitPolicy {
+        ExitPolicy { fail_on, max_review, quiet: true, exit_zero: false, locale: Locale::En }
+    }
+
+    #[test]
+    fn test_fail_on_levels() {
+        assert_eq!(resolve_fail_on(None, false), vec![]);
+        assert_eq!(resolve_fail_on(None, true), vec![ViolationLevel::Forbidden]);
+        assert_eq!(resolve_fail_on(Some(vec![FailOnArg::Unknown]), true), vec![ViolationLevel::Unknown]);
+
+        // One review-required and one unknown package: only the listed levels fail
+        let violations = summary(&[ViolationLevel::ReviewRequired, ViolationLevel::Unknown]);
+        assert_eq!(failing_reasons(&violations, &exit_policy(vec![ViolationLevel::Unknown], None)), vec!["unknown licenses"]);
+        assert!(failing_reasons(&violations, &exit_policy(vec![ViolationLevel::Forbidden], None)).is_empty());
+        assert_eq!(failing_reasons(&violations, &exit_policy(vec![ViolationLevel::ReviewRequired], None)), vec!["licenses requiring review"]);
+
+        let violations = summary(&[ViolationLevel::Unknown]);
+        assert!(failing_reasons(&violations, &exit_policy(vec![ViolationLevel::ReviewRequired], None)).is_empty());
+        let violations = summary(&[ViolationLevel::Forbidden]);
+        assert_eq!(failing_reasons(&violations, &exit_policy(vec![ViolationLevel::Forbidden], None)), vec!["forbidden licenses"]);
+    }
+
+    #[test]
+    fn test_append_csv_header_handling() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.csv");
+
+        // Missing file: the header is written
+        assert!(needs_csv_header(&path, "name,version").unwrap());
+        append_output(&path, "name,version\nrequests,2.31.0\n", Some("name,version")).unwrap();
+        // Matching header: only the rows are appended
+        assert!(!needs_csv_header(&path, "name,version").unwrap());
+        append_output(&path, "name,version\nclick,8.1.7\n", Some("name,version")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "name,version\nrequests,2.31.0\nclick,8.1.7\n");
+
+        // Different columns: refused, and the file is left alone
+        let error = append_output(&path, "name,license\nattrs,MIT\n", Some("name,license")).unwrap_err();
+        assert!(error.to_string().contains("differs from 'name,license'"), "{}", error);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+        // An empty file still needs one
+        let empty = dir.path().join("empty.csv");
+        fs::write(&empty, "").unwrap();
+        assert!(needs_csv_header(&empty, "name,version").unwrap());
+    }
+
+    #[test]
+    fn test_max_review_threshold() {
+        let review = |count: usize| summary(&vec![ViolationLevel::ReviewRequired; count]);
+        let policy = exit_policy(vec![], Some(2));
+
+        assert!(failing_reasons(&review(1), &policy).is_empty());
+        assert!(failing_reasons(&review(2), &policy).is_empty());
+        assert_eq!(
+            failing_reasons(&review(3), &policy),
+            vec!["3 review-required packages exceeding --max-review 2 by 1"]
+        );
+
+        // Notes never count towards the ceiling
+        let mut violations = review(3);
+        violations.details[0].severity = Some(RuleSeverity::Note);
+        assert!(failing_reasons(&violations, &policy).is_empty());
+    }
+
+    #[test]
+    fn test_count_only_result() {
+        let policy = exit_policy(vec![ViolationLevel::Forbidden], None);
+
+        // No policy check: nothing to count
+        assert_eq!(count_only_result(None, &policy), (0, vec![]));
+
+        // The count covers every violation; only the --fail-on levels fail the run
+        let violations = summary(&[ViolationLevel::ReviewRequired, ViolationLevel::Unknown]);
+        assert_eq!(count_only_result(Some(&violations), &policy), (2, vec![]));
+        let violations = summary(&[ViolationLevel::Forbidden, ViolationLevel::Unknown]);
+        assert_eq!(count_only_result(Some(&violations), &policy), (2, vec!["forbidden licenses".to_string()]));
+
+        // --exit-zero still prints the count
+        let policy = ExitPolicy { exit_zero: true, ..policy };
+        assert_eq!(count_only_result(Some(&violations), &policy), (2, vec![]));
+    }
+
+    #[test]
+    fn test_site_packages_to_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let venv = dir.path().join("image-venv");
+        fs::create_dir_all(venv.join("lib/python3.12/site-packages")).unwrap();
+        let site_packages = fs::canonicalize(venv.join("lib/python3.12/site-packages")).unwrap();
+
+        // A venv or the site-packages directory itself, no uv.lock needed
+        assert_eq!(site_packages_to_scan(Some(&venv), None, None).unwrap(), site_packages);
+        assert_eq!(site_packages_to_scan(Some(&site_packages), None, None).unwrap(), site_packages);
+        assert_eq!(site_packages_to_scan(Some(&venv), None, Some("3.12")).unwrap(), site_packages);
+        assert!(site_packages_to_scan(Some(&venv), None, Some("3.11")).is_err());
+
+        // --scan-site-packages wins over the project path
+        let other = dir.path().join("other");
+        fs::create_dir_all(&other).unwrap();
+        assert_eq!(site_packages_to_scan(Some(&venv), Some(other), None).unwrap(), site_packages);
+        assert_eq!(site_packages_to_scan(None, Some(venv.clone()), None).unwrap(), site_packages);
+
+        let error = site_packages_to_scan(Some(&dir.path().join("missing")), None, None).unwrap_err();
+        assert!(error.to_string().contains("does not exist"), "{}", error);
+    }
+}
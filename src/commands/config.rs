@@ -1,13 +1,87 @@
 use anyhow::Result;
+use std::path::PathBuf;
+use crate::cli::{MigrateExceptionsArg, PolicyFormatArg};
+use py_license_auditor::config::load_policy_file;
+use py_license_auditor::license::{create_report, extract_licenses_auto};
+use py_license_auditor::policy::{diff_policies, format_exception_coverage, IdleException, IdleExceptionKind, format_policy_diff, format_policy_test_results, load_policy_tests};
+use py_license_auditor::exceptions::{
+    find_exception_conflicts, load_exceptions, save_exceptions, get_exceptions_file_path, to_file_exceptions, to_policy_exceptions,
+    ConflictKind, ExceptionConflict,
+};
+use py_license_auditor::output::format_policy_markdown;
 
-pub fn handle_config(show: bool, validate: bool, quiet: bool) -> Result<()> {
-    if !show && !validate {
+/// What `config` should do (one field per flag)
+pub struct ConfigActions {
+    pub show: bool,
+    pub validate: bool,
+    pub show_policy: bool,
+    pub diff_policy: Option<Vec<PathBuf>>,
+    pub exception_coverage: bool,
+    pub migrate_exceptions: Option<MigrateExceptionsArg>,
+    pub test_policy: Option<PathBuf>,
+}
+
+pub fn handle_config(actions: ConfigActions, format: PolicyFormatArg, quiet: bool) -> Result<()> {
+    let ConfigActions { show, validate, show_policy, diff_policy, exception_coverage, migrate_exceptions, test_policy } = actions;
+    if !show && !validate && !show_policy && diff_policy.is_none() && !exception_coverage && migrate_exceptions.is_none() && test_policy.is_none() {
         if !quiet {
-            eprintln!("Use --show or --validate");
+            eprintln!("Use --show, --validate, --show-policy, --diff-policy, --exception-coverage, --migrate-exceptions or --test-policy");
         }
         std::process::exit(1);
     }
+
+    if let Some(direction) = migrate_exceptions {
+        migrate_exception_store(direction, quiet)?;
+    }
+
+    if let Some([old, new]) = diff_policy.as_deref() {
+        let diff = diff_policies(&load_policy_file(old)?, &load_policy_file(new)?);
+        match format {
+            PolicyFormatArg::Markdown => println!("{}", format_policy_diff(&diff)),
+            PolicyFormatArg::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+        }
+    }
+
+    if show_policy {
+        let config = py_license_auditor::config::load_config()?;
+        let Some(policy) = &config.policy else {
+            anyhow::bail!("No license policy configured (run 'py-license-auditor init <preset>' first)");
+        };
+        let rendered = match format {
+            PolicyFormatArg::Markdown => format_policy_markdown(policy, &load_exceptions()?.exceptions),
+            PolicyFormatArg::Json => serde_json::to_string_pretty(policy)?,
+        };
+        println!("{}", rendered.trim_end());
+    }
     
+    if exception_coverage {
+        let config = py_license_auditor::config::load_config()?;
+        let Some(policy) = &config.policy else {
+            anyhow::bail!("No license policy configured (run 'py-license-auditor init <preset>' first)");
+        };
+        let report = create_report(extract_licenses_auto(None, true, false)?);
+        let coverage = policy.exception_coverage(&report.packages, chrono::Utc::now().date_naive());
+        match format {
+            PolicyFormatArg::Markdown => println!("{}", format_exception_coverage(&coverage)),
+            PolicyFormatArg::Json => println!("{}", serde_json::to_string_pretty(&coverage)?),
+        }
+    }
+
+    if let Some(cases_path) = &test_policy {
+        let config = py_license_auditor::config::load_config()?;
+        let Some(policy) = &config.policy else {
+            anyhow::bail!("No license policy configured (run 'py-license-auditor init <preset>' first)");
+        };
+        let results = policy.run_tests(&load_policy_tests(cases_path)?);
+        match format {
+            PolicyFormatArg::Markdown => println!("{}", format_policy_test_results(&results)),
+            PolicyFormatArg::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+        }
+        if results.iter().any(|result| !result.passed) {
+            std::process::exit(1);
+        }
+    }
+
     if show {
         match py_license_auditor::config::load_config() {
             Ok(config) => {
@@ -26,10 +100,47 @@ pub fn handle_config(show: bool, validate: bool, quiet: bool) -> Result<()> {
     
     if validate {
         match py_license_auditor::config::load_config() {
-            Ok(_) => {
+            Ok(config) => {
                 if !quiet {
                     println!("✅ Configuration is valid");
                 }
+
+                // Duplicate/overlapping exceptions are legal but ambiguous: first match wins
+                if let Some(policy) = &config.policy {
+                    let conflicts = find_exception_conflicts(
+                        policy.exceptions.iter().map(|e| (e.name.as_str(), e.version.as_deref())),
+                    );
+                    let versions: Vec<_> = policy.exceptions.iter().map(|e| e.version.clone()).collect();
+                    print_exception_conflicts("pyproject.toml", &conflicts, &versions, quiet);
+                }
+
+                let exceptions = load_exceptions()?;
+                let conflicts = exceptions.find_conflicts();
+                let versions: Vec<_> = exceptions.exceptions.iter().map(|e| e.version.clone()).collect();
+                print_exception_conflicts(".exceptions.toml", &conflicts, &versions, quiet);
+
+                // Exceptions that no longer suppress anything mislead audits
+                let has_exceptions = config.policy.as_ref().is_some_and(|policy| !policy.exceptions.is_empty())
+                    || !exceptions.exceptions.is_empty();
+                if let Some(policy) = config.policy.as_ref().filter(|_| has_exceptions && !quiet) {
+                    match extract_licenses_auto(None, true, false) {
+                        Ok(packages) => {
+                            let packages = create_report(packages).packages;
+                            let today = chrono::Utc::now().date_naive();
+                            let idle = policy.idle_exceptions(
+                                policy.exceptions.iter().map(|e| (e.name.as_str(), e.version.as_deref(), e.expires)),
+                                &packages, today,
+                            );
+                            print_idle_exceptions("pyproject.toml", &idle);
+                            let idle = policy.idle_exceptions(
+                                exceptions.exceptions.iter().map(|e| (e.name.as_str(), e.version.as_deref(), e.expires)),
+                                &packages, today,
+                            );
+                            print_idle_exceptions(".exceptions.toml", &idle);
+                        }
+                        Err(error) => eprintln!("ℹ️  Skipped checking exceptions against installed packages: {}", error),
+                    }
+                }
             }
             Err(e) => {
                 if !quiet {
@@ -42,3 +153,84 @@ pub fn handle_config(show: bool, validate: bool, quiet: bool) -> Result<()> {
     
     Ok(())
 }
+
+/// Copy exceptions into the other store; the source is left untouched for review
+fn migrate_exception_store(direction: MigrateExceptionsArg, quiet: bool) -> Result<()> {
+    let config = py_license_auditor::config::load_config()?;
+    let policy_exceptions = config.policy.map(|policy| policy.exceptions).unwrap_or_default();
+    let mut file = load_exceptions()?;
+
+    let (source, target, migrated, skipped, warnings) = match direction {
+        MigrateExceptionsArg::ToPyproject => {
+            let migration = to_policy_exceptions(&file.exceptions, &policy_exceptions);
+            if !migration.exceptions.is_empty() {
+                py_license_auditor::config::add_exceptions_to_config(migration.exceptions.clone())?;
+            }
+            (".exceptions.toml", "pyproject.toml", migration.exceptions.len(), migration.skipped, migration.warnings)
+        }
+        MigrateExceptionsArg::ToFile => {
+            let migration = to_file_exceptions(&policy_exceptions, &file.exceptions, chrono::Utc::now());
+            let migrated = migration.exceptions.len();
+            if migrated > 0 {
+                file.exceptions.extend(migration.exceptions);
+                save_exceptions(&file)?;
+            }
+            ("pyproject.toml", ".exceptions.toml", migrated, migration.skipped, migration.warnings)
+        }
+    };
+
+    if !quiet {
+        for warning in &warnings {
+            eprintln!("⚠️  {}", warning);
+        }
+        let target_path = match direction {
+            MigrateExceptionsArg::ToPyproject => "pyproject.toml".to_string(),
+            MigrateExceptionsArg::ToFile => get_exceptions_file_path().display().to_string(),
+        };
+        println!("✅ Copied {} exception(s) from {} to {} ({} already present)", migrated, source, target_path, skipped);
+        if migrated + skipped > 0 {
+            println!("   Remove them from {} once {} is your single source of truth", source, target);
+        }
+    }
+    Ok(())
+}
+
+fn print_idle_exceptions(source: &str, idle: &[IdleException]) {
+    if idle.is_empty() {
+        return;
+    }
+
+    println!("⚠️  {} exceptions in {} have no effect:", idle.len(), source);
+    for exception in idle {
+        let reason = match exception.kind {
+            IdleExceptionKind::Unnecessary => "unnecessary, the package's license is allowed".to_string(),
+            IdleExceptionKind::Stale => "stale, no such package is installed".to_string(),
+            IdleExceptionKind::Expired => match exception.expires {
+                Some(date) => format!("expired on {}", date),
+                None => "expired".to_string(),
+            },
+        };
+        println!("   - {} {}: {}", exception.name, exception.version.as_deref().unwrap_or("*"), reason);
+    }
+}
+
+fn print_exception_conflicts(source: &str, conflicts: &[ExceptionConflict], versions: &[Option<String>], quiet: bool) {
+    if conflicts.is_empty() || quiet {
+        return;
+    }
+
+    println!("⚠️  {} conflicting exceptions in {} (first match wins):", conflicts.len(), source);
+    for conflict in conflicts {
+        let (a, b) = conflict.indices;
+        let kind = match conflict.kind {
+            ConflictKind::Duplicate => "duplicates",
+            ConflictKind::Overlapping => "overlaps",
+        };
+        println!("   - {}: '{}' {} '{}'", conflict.name,
+                 versions[a].as_deref().unwrap_or("*"), kind,
+                 versions[b].as_deref().unwrap_or("*"));
+    }
+    if source == ".exceptions.toml" {
+        println!("   Run 'py-license-auditor fix --dedup-exceptions' to merge redundant entries");
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use py_license_auditor::exceptions::{get_exceptions_file_path, load_exceptions, parse_exceptions_csv, save_exceptions};
+
+/// Import approvals from a spreadsheet export into .exceptions.toml
+pub fn handle_exceptions(import: &Path, dry_run: bool, quiet: bool) -> Result<()> {
+    let content = fs::read_to_string(import)
+        .with_context(|| format!("Failed to read {}", import.display()))?;
+    let parsed = parse_exceptions_csv(&content);
+
+    for warning in &parsed.warnings {
+        eprintln!("⚠️  {}: {}", import.display(), warning);
+    }
+
+    if dry_run {
+        if !quiet {
+            println!("🔍 Would import {} exception(s) into {}:", parsed.exceptions.len(), get_exceptions_file_path().display());
+            for exception in &parsed.exceptions {
+                let expires = exception.expires.map_or("never".to_string(), |date| date.format("%Y-%m-%d").to_string());
+                println!("  + {} {} ({}; expires {})",
+                         exception.name, exception.version.as_deref().unwrap_or("*"), exception.reason, expires);
+            }
+        }
+        return Ok(());
+    }
+
+    let count = parsed.exceptions.len();
+    if count > 0 {
+        let mut exceptions = load_exceptions()?;
+        for exception in parsed.exceptions {
+            exceptions.add_exception(exception);
+        }
+        save_exceptions(&exceptions)?;
+    }
+    if !quiet {
+        println!("✅ Imported {} exception(s) into {} ({} row(s) skipped)",
+                 count, get_exceptions_file_path().display(), parsed.warnings.len());
+    }
+    Ok(())
+}
@@ -1,16 +1,65 @@
 use anyhow::Result;
 use std::path::PathBuf;
-use crate::cli::OutputFormat;
-use py_license_auditor::license::extract_licenses_auto;
+use crate::cli::{FailOnArg, OutputFormat, VersionScopeArg};
+use py_license_auditor::license::{extract_licenses_auto, LicenseReport};
 use py_license_auditor::config::load_config;
+use py_license_auditor::exceptions::{load_exceptions, save_exceptions};
+use py_license_auditor::policy::{Violation, ViolationLevel, ViolationSummary};
+use py_license_auditor::python_version::PythonVersion;
+
+pub struct FixOptions {
+    pub dry_run: bool,
+    pub interactive: bool,
+    pub dedup_exceptions: bool,
+    /// Saved `check --format json` report to take violations from
+    pub from_report: Option<PathBuf>,
+    /// `--levels`: violation levels to except (all when `None`)
+    pub levels: Option<Vec<FailOnArg>>,
+    /// `--version-scope`: versions covered by each new exception
+    pub version_scope: VersionScopeArg,
+    /// `--include-forbidden`: also auto-except forbidden licenses
+    pub include_forbidden: bool,
+}
+
+/// Split off forbidden violations, which the default `fix` never turns into exceptions.
+/// Returns the violations to except and the refused forbidden ones.
+fn withhold_forbidden(violations: ViolationSummary, include_forbidden: bool) -> (ViolationSummary, Vec<Violation>) {
+    if include_forbidden {
+        return (violations, Vec::new());
+    }
+    let (refused, details): (Vec<_>, Vec<_>) = violations.details
+        .into_iter()
+        .partition(|v| v.violation_level == ViolationLevel::Forbidden);
+    (ViolationSummary::from_details(details), refused)
+}
+
+/// Exception version spec for `version` under `scope` (exact when the version is not numeric)
+fn exception_version(version: &str, scope: VersionScopeArg) -> String {
+    let Some(parsed) = PythonVersion::parse(version) else {
+        return version.to_string();
+    };
+    let major = parsed.0[0];
+    match scope {
+        VersionScopeArg::Exact => version.to_string(),
+        VersionScopeArg::Minor => {
+            let minor = parsed.0.get(1).copied().unwrap_or(0);
+            format!(">={},<{}.{}", version, major, minor + 1)
+        }
+        VersionScopeArg::Major => format!(">={},<{}", version, major + 1),
+    }
+}
 
 pub fn handle_fix(
     path: Option<PathBuf>,
-    dry_run: bool,
-    interactive: bool,
     _format: Option<OutputFormat>,
+    options: FixOptions,
     quiet: bool,
 ) -> Result<()> {
+    let FixOptions { dry_run, interactive, dedup_exceptions, from_report, levels, version_scope, include_forbidden } = options;
+    if dedup_exceptions {
+        return dedup_exceptions_file(dry_run, quiet);
+    }
+
     // Load configuration
     let config = load_config()?;
     
@@ -23,12 +72,44 @@ pub fn handle_fix(
     
     let policy = config.policy.unwrap();
     
-    // Extract packages
-    let include_unknown = config.include_unknown.unwrap_or(false);
-    let packages = extract_licenses_auto(path, include_unknown)?;
-    
-    // Check for violations
-    let violations = policy.detect_violations(&packages);
+    let violations = match &from_report {
+        // Approve from a saved report: no environment needed
+        Some(report_path) => {
+            let report = LicenseReport::load(report_path)?;
+            let Some(violations) = report.violations else {
+                anyhow::bail!(
+                    "{} has no violations section (run 'check' with check_violations = true)",
+                    report_path.display()
+                );
+            };
+            // Skip packages excepted since the report was produced
+            let details = violations.details
+                .into_iter()
+                .filter(|v| policy.is_exception(&v.package_name, v.package_version.as_deref()).is_none())
+                .collect();
+            ViolationSummary::from_details(details)
+        }
+        None => {
+            let include_unknown = config.include_unknown.unwrap_or(false);
+            let packages = extract_licenses_auto(path, include_unknown, false)?;
+            policy.detect_violations(&packages)
+        }
+    };
+
+    let violations = match levels {
+        Some(levels) => {
+            let levels: Vec<ViolationLevel> = levels
+                .into_iter()
+                .map(ViolationLevel::from)
+                .collect();
+            let details = violations.details
+                .into_iter()
+                .filter(|v| levels.contains(&v.violation_level))
+                .collect();
+            ViolationSummary::from_details(details)
+        }
+        None => violations,
+    };
     
     if violations.total == 0 {
         if !quiet {
@@ -62,14 +143,32 @@ pub fn handle_fix(
         return Ok(());
     }
     
+    // Forbidden licenses are never approved by accident
+    let (violations, refused) = withhold_forbidden(violations, include_forbidden);
+    if !refused.is_empty() && !quiet {
+        eprintln!("⚠️  Refusing to auto-except {} package(s) with forbidden licenses (pass --include-forbidden to override):", refused.len());
+        for violation in &refused {
+            eprintln!("   ❌ {} {} ({})", violation.package_name,
+                     violation.package_version.as_deref().unwrap_or("*"),
+                     violation.license.as_deref().unwrap_or("unknown"));
+        }
+    }
+    if violations.total == 0 {
+        if !quiet {
+            println!("No exceptions to add");
+        }
+        return Ok(());
+    }
+
     // Default behavior: create exceptions from violations
     let mut exceptions = Vec::new();
     for detail in &violations.details {
         let exception = py_license_auditor::policy::PackageException {
             name: detail.package_name.clone(),
-            version: detail.package_version.clone(),
+            version: detail.package_version.as_deref().map(|version| exception_version(version, version_scope)),
             reason: format!("Auto-generated exception for {} license", 
                           detail.license.as_deref().unwrap_or("unknown")),
+            expires: None,
         };
         exceptions.push(exception);
     }
@@ -100,3 +199,61 @@ pub fn handle_fix(
     
     Ok(())
 }
+
+fn dedup_exceptions_file(dry_run: bool, quiet: bool) -> Result<()> {
+    let mut exceptions = load_exceptions()?;
+    let removed = exceptions.dedup();
+
+    if removed == 0 {
+        if !quiet {
+            println!("No redundant exceptions found in .exceptions.toml");
+        }
+        return Ok(());
+    }
+
+    if dry_run {
+        if !quiet {
+            println!("Would remove {} redundant exceptions from .exceptions.toml", removed);
+        }
+        return Ok(());
+    }
+
+    save_exceptions(&exceptions)?;
+    if !quiet {
+        println!("✅ Removed {} redundant exceptions from .exceptions.toml", removed);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(name: &str, level: ViolationLevel) -> Violation {
+        Violation {
+            package_name: name.to_string(),
+            package_version: Some("1.0".to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: level,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_fix_keeps_forbidden_violations() {
+        let violations = || ViolationSummary::from_details(vec![
+            violation("gpl-thing", ViolationLevel::Forbidden),
+            violation("lgpl-thing", ViolationLevel::ReviewRequired),
+        ]);
+
+        let (to_except, refused) = withhold_forbidden(violations(), false);
+        assert_eq!(to_except.details.iter().map(|v| v.package_name.as_str()).collect::<Vec<_>>(), vec!["lgpl-thing"]);
+        assert_eq!(refused.len(), 1);
+        assert_eq!(refused[0].package_name, "gpl-thing");
+
+        let (to_except, refused) = withhold_forbidden(violations(), true);
+        assert_eq!(to_except.total, 2);
+        assert!(refused.is_empty());
+    }
+}
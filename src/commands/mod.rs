@@ -2,8 +2,10 @@ pub mod check;
 pub mod init;
 pub mod fix;
 pub mod config;
+pub mod exceptions;
 
 pub use check::handle_check;
 pub use init::handle_init;
-pub use fix::handle_fix;
-pub use config::handle_config;
+pub use fix::{handle_fix, FixOptions};
+pub use config::{handle_config, ConfigActions};
+pub use exceptions::handle_exceptions;
@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
+use crate::atomic_file::write_atomic;
+use crate::license::{OsiOverrides, PackageLicense, ReportFooter};
 use crate::policy::{LicensePolicy, PackageException};
+use crate::uv_lock::normalize_package_name;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -18,10 +21,88 @@ pub struct Config {
     /// Fail on policy violations
     pub fail_on_violations: Option<bool>,
     
+    /// What to do when the audited Python is outside uv.lock's requires-python
+    pub python_mismatch: Option<PythonMismatch>,
+    
+    /// Oldest auditor version this project expects (e.g. "0.3.0")
+    pub min_version: Option<String>,
+    
+    /// Auditor version this project is pinned to: an exact version ("0.3.1")
+    /// or a semver requirement ("^0.3", ">=0.3, <0.5")
+    pub required_version: Option<String>,
+    
+    /// Summary display settings
+    pub summary: Option<SummaryConfig>,
+    
+    /// Owner / purpose notes attached to packages in reports (no effect on policy)
+    pub annotations: Option<Vec<PackageAnnotation>>,
+    
+    /// Licenses forced into (or out of) the OSI-approved bucket of the summary
+    pub osi_overrides: Option<OsiOverrides>,
+    
+    /// Footer text and policy link shown at the end of reports
+    pub report: Option<ReportFooter>,
+    
     /// Embedded policy configuration
     pub policy: Option<LicensePolicy>,
 }
 
+/// `python_mismatch`: handling of an audited Python outside uv.lock's requires-python
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PythonMismatch {
+    /// Print a note and continue
+    #[default]
+    Note,
+    /// Fail the run
+    Error,
+    Ignore,
+}
+
+/// `[[tool.py-license-auditor.annotations]]`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackageAnnotation {
+    pub name: String,
+    pub version: Option<String>,
+    pub note: String,
+    pub owner: Option<String>,
+}
+
+impl PackageAnnotation {
+    fn matches(&self, package: &PackageLicense) -> bool {
+        normalize_package_name(&self.name) == normalize_package_name(&package.name)
+            && (self.version.is_none() || self.version == package.version)
+    }
+
+    fn render(&self) -> String {
+        match &self.owner {
+            Some(owner) => format!("{} (owner: {})", self.note, owner),
+            None => self.note.clone(),
+        }
+    }
+}
+
+/// Attach annotations to matching packages; a version-specific annotation wins over a name-only one
+pub fn apply_annotations(packages: &mut [PackageLicense], annotations: &[PackageAnnotation]) {
+    for package in packages {
+        let annotation = annotations
+            .iter()
+            .filter(|annotation| annotation.matches(package))
+            .max_by_key(|annotation| annotation.version.is_some());
+        if let Some(annotation) = annotation {
+            package.annotation = Some(annotation.render());
+        }
+    }
+}
+
+/// `[tool.py-license-auditor.summary]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummaryConfig {
+    /// Licenses shown as a single "MIT ×312" line in table output instead of listed per package
+    #[serde(default)]
+    pub collapse: Vec<String>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -29,23 +110,149 @@ impl Default for Config {
             include_unknown: Some(false),
             check_violations: Some(false),
             fail_on_violations: Some(false),
+            python_mismatch: None,
+            min_version: None,
+            required_version: None,
+            summary: None,
+            annotations: None,
+            osi_overrides: None,
+            report: None,
             policy: None,
         }
     }
 }
 
 impl Config {
-    // Config struct now directly contains the policy, no resolution needed
+    /// Fill every unset field from `fallback`, which has lower precedence.
+    /// The policy is taken as a whole: a project policy fully replaces a user policy.
+    pub fn with_fallback(self, fallback: Config) -> Config {
+        Config {
+            format: self.format.or(fallback.format),
+            include_unknown: self.include_unknown.or(fallback.include_unknown),
+            check_violations: self.check_violations.or(fallback.check_violations),
+            fail_on_violations: self.fail_on_violations.or(fallback.fail_on_violations),
+            python_mismatch: self.python_mismatch.or(fallback.python_mismatch),
+            min_version: self.min_version.or(fallback.min_version),
+            required_version: self.required_version.or(fallback.required_version),
+            summary: self.summary.or(fallback.summary),
+            annotations: self.annotations.or(fallback.annotations),
+            osi_overrides: self.osi_overrides.or(fallback.osi_overrides),
+            report: self.report.or(fallback.report),
+            policy: self.policy.or(fallback.policy),
+        }
+    }
+}
+
+/// Compare the running auditor version with `min_version` / `required_version`.
+/// Returns a description of the mismatch, if any.
+pub fn tool_version_mismatch(config: &Config, running: &str) -> Result<Option<String>> {
+    let running = semver::Version::parse(running)
+        .with_context(|| format!("Invalid tool version: {}", running))?;
+
+    if let Some(min_version) = &config.min_version {
+        let min = semver::Version::parse(min_version.trim())
+            .with_context(|| format!("Invalid min_version '{}' (expected e.g. \"0.3.0\")", min_version))?;
+        if running < min {
+            return Ok(Some(format!(
+                "py-license-auditor {} is older than min_version {} from the project configuration", running, min
+            )));
+        }
+    }
+
+    if let Some(required) = &config.required_version {
+        // A bare version pins exactly; anything else is a semver requirement
+        let matches = match semver::Version::parse(required.trim()) {
+            Ok(pinned) => running == pinned,
+            Err(_) => semver::VersionReq::parse(required)
+                .with_context(|| format!("Invalid required_version '{}' (expected e.g. \"0.3.1\" or \"^0.3\")", required))?
+                .matches(&running),
+        };
+        if !matches {
+            return Ok(Some(format!(
+                "py-license-auditor {} does not match required_version {} from the project configuration", running, required
+            )));
+        }
+    }
+
+    Ok(None)
 }
 
-/// Load configuration from pyproject.toml
+/// Load configuration with precedence: project pyproject.toml > user config > built-in default.
+/// (CLI flags are applied on top by the command handlers.)
 pub fn load_config() -> Result<Config> {
+    let project = load_project_config()?;
+    let user = match user_config_path() {
+        Some(path) => load_user_config_from(&path)?,
+        None => None,
+    };
+
+    Ok(match (project, user) {
+        (Some(project), Some(user)) => project.with_fallback(user),
+        (Some(project), None) => project,
+        (None, Some(user)) => user.with_fallback(Config::default()),
+        (None, None) => Config::default(),
+    })
+}
+
+/// Location of the user-level config: `$XDG_CONFIG_HOME/py-license-auditor/config.toml`,
+/// falling back to `~/.config/py-license-auditor/config.toml`
+pub fn user_config_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_home.join("py-license-auditor").join("config.toml"))
+}
+
+/// Load a user-level config file. Keys are the same as `[tool.py-license-auditor]`, at top level.
+pub fn load_user_config_from(path: &Path) -> Result<Option<Config>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read user config: {}", path.display()))?;
+    let config: Config = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse user config: {}", path.display()))?;
+
+    Ok(Some(config))
+}
+
+/// Load a policy from a standalone file: a pyproject.toml / preset
+/// (`[tool.py-license-auditor.policy]`), a user config (`[policy]`), a bare
+/// policy TOML, or the JSON printed by `config --show-policy --format json`
+pub fn load_policy_file(path: &Path) -> Result<LicensePolicy> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read policy file: {}", path.display()))?;
+
+    if path.extension().is_some_and(|extension| extension == "json") {
+        return serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse policy: {}", path.display()));
+    }
+
+    let document: toml::Value = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse policy file: {}", path.display()))?;
+    let section = document
+        .get("tool")
+        .and_then(|tool| tool.get("py-license-auditor"))
+        .and_then(|tool| tool.get("policy"))
+        .or_else(|| document.get("policy"))
+        .unwrap_or(&document);
+    section
+        .clone()
+        .try_into()
+        .with_context(|| format!("No valid license policy in {}", path.display()))
+}
+
+/// Load the `[tool.py-license-auditor]` section of the project's pyproject.toml, if any
+fn load_project_config() -> Result<Option<Config>> {
     let pyproject_path = std::env::current_dir()
         .unwrap_or_else(|_| PathBuf::from("."))
         .join("pyproject.toml");
     
     if !pyproject_path.exists() {
-        return Ok(Config::default());
+        return Ok(None);
     }
     
     let content = fs::read_to_string(&pyproject_path)
@@ -59,11 +266,11 @@ pub fn load_config() -> Result<Config> {
         if let Some(py_license_auditor) = tool.get("py-license-auditor") {
             let config: Config = py_license_auditor.clone().try_into()
                 .context("Failed to parse [tool.py-license-auditor] section")?;
-            return Ok(config);
+            return Ok(Some(config));
         }
     }
     
-    Ok(Config::default())
+    Ok(None)
 }
 
 /// Add exceptions to pyproject.toml
@@ -108,7 +315,7 @@ pub fn add_exceptions_to_config(exceptions: Vec<PackageException>) -> Result<()>
     let updated_content = toml::to_string_pretty(&pyproject)
         .context("Failed to serialize updated pyproject.toml")?;
     
-    fs::write(&pyproject_path, updated_content)
+    write_atomic(&pyproject_path, |writer| Ok(writer.write_all(updated_content.as_bytes())?))
         .with_context(|| format!("Failed to write pyproject.toml: {}", pyproject_path.display()))?;
     
     Ok(())
@@ -238,4 +445,107 @@ reason = "Legacy dependency, approved by legal team"
         assert_eq!(policy.review_required.exact, vec!["MPL-2.0"]);
         assert!(policy.review_required.patterns.is_empty());
     }
+
+    #[test]
+    fn test_user_config_merged_under_project() {
+        let temp_dir = tempdir().unwrap();
+        let user_path = temp_dir.path().join("config.toml");
+        fs::write(&user_path, r#"
+format = "json"
+include_unknown = true
+
+[policy]
+name = "Personal Policy"
+
+[policy.allowed_licenses]
+exact = ["MIT"]
+"#).unwrap();
+
+        let user = load_user_config_from(&user_path).unwrap().unwrap();
+        assert_eq!(user.policy.as_ref().unwrap().name, "Personal Policy");
+
+        // Project sets format only: user supplies the rest
+        let project = Config {
+            format: Some("csv".to_string()),
+            include_unknown: None,
+            check_violations: None,
+            fail_on_violations: None,
+            python_mismatch: None,
+            min_version: None,
+            required_version: None,
+            summary: None,
+            annotations: None,
+            osi_overrides: None,
+            report: None,
+            policy: None,
+        };
+        let merged = project.with_fallback(user);
+        assert_eq!(merged.format, Some("csv".to_string()));
+        assert_eq!(merged.include_unknown, Some(true));
+        assert_eq!(merged.policy.unwrap().name, "Personal Policy");
+
+        assert!(load_user_config_from(&temp_dir.path().join("missing.toml")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_python_mismatch_is_validated_on_load() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "python_mismatch = \"error\"\n").unwrap();
+        let config = load_user_config_from(&path).unwrap().unwrap();
+        assert_eq!(config.python_mismatch, Some(PythonMismatch::Error));
+
+        fs::write(&path, "python_mismatch = \"fail\"\n").unwrap();
+        let error = format!("{:#}", load_user_config_from(&path).unwrap_err());
+        assert!(error.contains("unknown variant `fail`"), "{}", error);
+    }
+
+    #[test]
+    fn test_apply_annotations() {
+        let annotations: Vec<PackageAnnotation> = toml::from_str::<toml::Value>(r#"
+[[annotations]]
+name = "Requests"
+note = "HTTP client for the billing service"
+owner = "payments-team"
+
+[[annotations]]
+name = "requests"
+version = "2.31.0"
+note = "pinned for TLS fix"
+"#).unwrap()["annotations"].clone().try_into().unwrap();
+
+        let package = |version: &str| PackageLicense {
+            name: "requests".to_string(),
+            version: Some(version.to_string()),
+            effective_license: Some("Apache-2.0".to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let mut packages = vec![package("2.31.0"), package("2.32.0")];
+        apply_annotations(&mut packages, &annotations);
+
+        assert_eq!(packages[0].annotation.as_deref(), Some("pinned for TLS fix"));
+        assert_eq!(packages[1].annotation.as_deref(), Some("HTTP client for the billing service (owner: payments-team)"));
+    }
+
+    #[test]
+    fn test_tool_version_mismatch() {
+        let config = |min: Option<&str>, required: Option<&str>| Config {
+            min_version: min.map(str::to_string),
+            required_version: required.map(str::to_string),
+            ..Config::default()
+        };
+
+        assert_eq!(tool_version_mismatch(&Config::default(), "0.3.1").unwrap(), None);
+        assert_eq!(tool_version_mismatch(&config(Some("0.3.0"), None), "0.3.1").unwrap(), None);
+        assert!(tool_version_mismatch(&config(Some("0.4.0"), None), "0.3.1").unwrap().unwrap().contains("older than min_version 0.4.0"));
+
+        // A bare version is an exact pin, not a caret requirement
+        assert_eq!(tool_version_mismatch(&config(None, Some("0.3.1")), "0.3.1").unwrap(), None);
+        assert!(tool_version_mismatch(&config(None, Some("0.3.0")), "0.3.1").unwrap().is_some());
+        assert_eq!(tool_version_mismatch(&config(None, Some("^0.3")), "0.3.1").unwrap(), None);
+        assert!(tool_version_mismatch(&config(None, Some(">=0.4, <0.5")), "0.3.1").unwrap().is_some());
+
+        assert!(tool_version_mismatch(&config(None, Some("latest")), "0.3.1").is_err());
+    }
 }
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use std::process::Command;
+
+/// Schemes curl may use, for the request itself and for any redirect
+const PROTOCOLS: &str = "=https,http";
+
+/// A `curl` invocation with the flags every caller needs: no progress output,
+/// errors on stderr, and http(s) only so a URL can never reach file:// and friends
+pub(crate) fn command() -> Command {
+    let mut command = Command::new("curl");
+    command.args(["--silent", "--show-error", "--proto", PROTOCOLS, "--proto-redir", PROTOCOLS]);
+    command
+}
+
+/// Reject anything but an http(s) URL before it is handed to curl
+pub(crate) fn ensure_http_url(url: &str) -> Result<()> {
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        anyhow::bail!("not an http(s) URL: {}", url);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_http_urls_are_accepted() {
+        assert!(ensure_http_url("https://example.com/hook").is_ok());
+        assert!(ensure_http_url("http://localhost:8080/").is_ok());
+        assert!(ensure_http_url("file:///etc/passwd").is_err());
+        assert!(ensure_http_url("ftp://example.com/report").is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+use std::process::Command;
+use crate::license::PackageLicense;
+use crate::policy::Violation;
+use crate::uv_lock::{normalize_package_name, UvLockFile, UvLockParser};
+
+/// License changes relative to a git ref (`check --diff-base`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseDiff {
+    pub base_ref: String,
+    /// Packages that are new or changed version since the base
+    pub new_packages: Vec<String>,
+    /// Licenses that no unchanged package already used
+    pub new_licenses: Vec<String>,
+    /// Violations of new or changed packages only
+    pub new_violations: Vec<Violation>,
+}
+
+/// Read uv.lock as it was at `git_ref`. Returns None when the ref has no uv.lock.
+pub fn read_lock_at_ref(git_ref: &str, lock_path: &Path) -> Result<Option<UvLockFile>> {
+    let lock_dir = lock_path.parent().unwrap_or(Path::new("."));
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(lock_dir)
+            .args(args)
+            .output()
+            .context("Failed to run git (is it installed?)")
+    };
+
+    let verify = git(&["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", git_ref)])?;
+    if !verify.status.success() {
+        anyhow::bail!("Unknown git ref '{}' (run from inside the repository and fetch the branch first)", git_ref);
+    }
+
+    let show = git(&["show", &format!("{}:./uv.lock", git_ref)])?;
+    if !show.status.success() {
+        return Ok(None);
+    }
+
+    let content = String::from_utf8(show.stdout).context("uv.lock at base ref is not valid UTF-8")?;
+    let lock_file = UvLockParser::parse_uv_lock_str(&content)
+        .with_context(|| format!("Failed to parse uv.lock at '{}'", git_ref))?;
+    Ok(Some(lock_file))
+}
+
+/// Compare the current audit with the packages locked at the base ref.
+///
+/// Only the current environment is installed, so the base licenses are those of
+/// packages whose locked name and version did not change.
+pub fn diff_against_base(base_ref: &str, base_lock: &UvLockFile, packages: &[PackageLicense], violations: &[Violation]) -> LicenseDiff {
+    let base: HashSet<(String, String)> = base_lock
+        .packages
+        .iter()
+        .map(|package| (normalize_package_name(&package.name), package.version.clone()))
+        .collect();
+    let is_new = |name: &str, version: Option<&str>| {
+        !base.contains(&(normalize_package_name(name), version.unwrap_or_default().to_string()))
+    };
+
+    let (new, unchanged): (Vec<&PackageLicense>, Vec<&PackageLicense>) = packages
+        .iter()
+        .partition(|package| is_new(&package.name, package.version.as_deref()));
+
+    let base_licenses: HashSet<&str> = unchanged.iter().filter_map(|p| p.effective_license.as_deref()).collect();
+    let new_licenses: BTreeSet<String> = new
+        .iter()
+        .filter_map(|p| p.effective_license.as_deref())
+        .filter(|license| !base_licenses.contains(license))
+        .map(str::to_string)
+        .collect();
+
+    let mut new_packages: Vec<String> = new
+        .iter()
+        .map(|p| format!("{} {}", p.name, p.version.as_deref().unwrap_or("unknown")))
+        .collect();
+    new_packages.sort();
+
+    LicenseDiff {
+        base_ref: base_ref.to_string(),
+        new_packages,
+        new_licenses: new_licenses.into_iter().collect(),
+        new_violations: violations
+            .iter()
+            .filter(|v| is_new(&v.package_name, v.package_version.as_deref()))
+            .cloned()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::ViolationLevel;
+
+    fn package(name: &str, version: &str, license: &str) -> PackageLicense {
+        PackageLicense {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            effective_license: Some(license.to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn violation(name: &str, version: &str) -> Violation {
+        Violation {
+            package_name: name.to_string(),
+            package_version: Some(version.to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: ViolationLevel::Forbidden,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_only_new_risk() {
+        let base_lock = UvLockParser::parse_uv_lock_str(r#"
+version = 1
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+
+[[package]]
+name = "old-gpl"
+version = "1.0"
+"#).unwrap();
+
+        let packages = vec![
+            package("requests", "2.31.0", "Apache-2.0"),
+            package("old_gpl", "1.0", "GPL-3.0"),
+            package("new-gpl", "0.1", "GPL-3.0"),
+            package("attrs", "23.1.0", "MIT"),
+        ];
+        let violations = vec![violation("old_gpl", "1.0"), violation("new-gpl", "0.1")];
+
+        let diff = diff_against_base("main", &base_lock, &packages, &violations);
+        assert_eq!(diff.new_packages, vec!["attrs 23.1.0", "new-gpl 0.1"]);
+        // GPL-3.0 was already used by an unchanged package
+        assert_eq!(diff.new_licenses, vec!["MIT"]);
+        assert_eq!(diff.new_violations.len(), 1);
+        assert_eq!(diff.new_violations[0].package_name, "new-gpl");
+    }
+}
@@ -1,5 +1,7 @@
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use super::models::ExceptionsFile;
+use crate::python_version::{satisfies, PythonVersion};
+use crate::uv_lock::{normalize_package_name, normalize_version};
 
 impl ExceptionsFile {
     pub fn is_excepted(&self, package_name: &str, package_version: Option<&str>) -> bool {
@@ -16,14 +18,7 @@ impl ExceptionsFile {
                 return false;
             }
 
-            // Check version match (if specified)
-            match (&exc.version, package_version) {
-                (Some(exc_version), Some(pkg_version)) => {
-                    exc_version == "*" || exc_version == pkg_version
-                }
-                (Some(_), None) => false,
-                (None, _) => true,
-            }
+            version_spec_matches(exc.version.as_deref(), package_version)
         })
     }
 
@@ -41,3 +36,143 @@ impl ExceptionsFile {
         original_count - self.exceptions.len()
     }
 }
+
+/// How two exceptions for the same package relate to each other
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictKind {
+    /// Same package and identical version spec
+    Duplicate,
+    /// Version specs overlap (e.g. `*` and `2.31.0`)
+    Overlapping,
+}
+
+/// A pair of exceptions that both match the same package
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExceptionConflict {
+    pub name: String,
+    pub kind: ConflictKind,
+    /// Positions of the two entries in the exception list
+    pub indices: (usize, usize),
+}
+
+fn covers_all_versions(version: Option<&str>) -> bool {
+    matches!(version, None | Some("*"))
+}
+
+/// A PEP 440 range such as `>=1.0,<2.0` rather than an exact version
+fn is_version_range(spec: &str) -> bool {
+    spec.trim_start().starts_with(['<', '>', '=', '!', '~'])
+}
+
+/// Whether an exception's version spec matches an installed version: no spec or `*`
+/// matches everything, a range (`>=1.0,<2.0`) is evaluated, anything else must be equal.
+/// Shared by `.exceptions.toml` and the pyproject policy exceptions.
+pub fn version_spec_matches(spec: Option<&str>, version: Option<&str>) -> bool {
+    match (spec, version) {
+        (spec, _) if covers_all_versions(spec) => true,
+        // Ranges compare the public version: `>=2.0` matches `2.1.0+cpu` (PEP 440)
+        (Some(spec), Some(version)) if is_version_range(spec) => {
+            let public = version.split_once('+').map_or(version, |(public, _)| public);
+            PythonVersion::parse(public)
+                .and_then(|version| satisfies(spec, &version))
+                .unwrap_or(false)
+        }
+        (Some(spec), Some(version)) => normalize_version(spec) == normalize_version(version),
+        _ => false,
+    }
+}
+
+/// Whether every version matched by `inner` is also matched by `outer`
+fn version_covers(outer: Option<&str>, inner: Option<&str>) -> bool {
+    covers_all_versions(outer)
+        || outer == inner
+        || inner.is_some_and(|inner| !is_version_range(inner) && !covers_all_versions(Some(inner)) && version_spec_matches(outer, Some(inner)))
+}
+
+/// Find duplicate or overlapping entries in a list of `(name, version)` exceptions.
+/// Shared by `.exceptions.toml` and the pyproject policy exceptions.
+pub fn find_exception_conflicts<'a, I>(entries: I) -> Vec<ExceptionConflict>
+where
+    I: IntoIterator<Item = (&'a str, Option<&'a str>)>,
+{
+    let entries: Vec<_> = entries.into_iter().collect();
+    let mut conflicts = Vec::new();
+
+    for (i, (name_a, version_a)) in entries.iter().enumerate() {
+        for (j, (name_b, version_b)) in entries.iter().enumerate().skip(i + 1) {
+            if normalize_package_name(name_a) != normalize_package_name(name_b) {
+                continue;
+            }
+
+            let kind = if version_a == version_b
+                || (covers_all_versions(*version_a) && covers_all_versions(*version_b))
+            {
+                ConflictKind::Duplicate
+            } else if version_covers(*version_a, *version_b) || version_covers(*version_b, *version_a) {
+                ConflictKind::Overlapping
+            } else {
+                continue;
+            };
+
+            conflicts.push(ExceptionConflict {
+                name: name_a.to_string(),
+                kind,
+                indices: (i, j),
+            });
+        }
+    }
+
+    conflicts
+}
+
+impl ExceptionsFile {
+    pub fn find_conflicts(&self) -> Vec<ExceptionConflict> {
+        find_exception_conflicts(
+            self.exceptions.iter().map(|exc| (exc.name.as_str(), exc.version.as_deref())),
+        )
+    }
+
+    /// Drop exceptions made redundant by another entry that matches at least the same
+    /// versions for at least as long. Merging never changes which packages are excepted.
+    pub fn dedup(&mut self) -> usize {
+        let expires_no_earlier = |a: Option<NaiveDate>, b: Option<NaiveDate>| match (a, b) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(a), Some(b)) => a >= b,
+        };
+
+        let count = self.exceptions.len();
+        let mut keep = vec![true; count];
+
+        for i in 0..count {
+            for j in 0..count {
+                if i == j || !keep[j] {
+                    continue;
+                }
+                let (candidate, other) = (&self.exceptions[i], &self.exceptions[j]);
+                if normalize_package_name(&candidate.name) != normalize_package_name(&other.name) {
+                    continue;
+                }
+
+                let dominated = version_covers(other.version.as_deref(), candidate.version.as_deref())
+                    && expires_no_earlier(other.expires, candidate.expires);
+                let equivalent = version_covers(candidate.version.as_deref(), other.version.as_deref())
+                    && expires_no_earlier(candidate.expires, other.expires);
+
+                // For equivalent entries keep the first one
+                if dominated && (!equivalent || j < i) {
+                    keep[i] = false;
+                    break;
+                }
+            }
+        }
+
+        let mut index = 0;
+        self.exceptions.retain(|_| {
+            index += 1;
+            keep[index - 1]
+        });
+
+        count - self.exceptions.len()
+    }
+}
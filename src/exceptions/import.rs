@@ -0,0 +1,153 @@
+use chrono::{NaiveDate, Utc};
+use super::models::Exception;
+
+/// Column order when the file has no header row
+const DEFAULT_COLUMNS: [&str; 5] = ["name", "version", "reason", "added_by", "expires"];
+
+/// Result of reading an approvals spreadsheet exported as CSV
+#[derive(Debug, Default)]
+pub struct CsvImport {
+    pub exceptions: Vec<Exception>,
+    /// One message per skipped row, with its 1-based line number
+    pub warnings: Vec<String>,
+}
+
+/// Split CSV content into records (RFC 4180: quoted fields may contain commas,
+/// doubled quotes and line breaks). Each record carries its starting line number.
+fn parse_records(content: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut fields)));
+                line += 1;
+                record_line = line;
+            }
+            _ => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+
+    records.retain(|(_, fields)| fields.iter().any(|field| !field.trim().is_empty()));
+    records
+}
+
+/// Read exceptions from CSV with columns name, version, reason, added_by, expires.
+/// A header row (starting with `name`) may reorder the columns; malformed rows are skipped.
+pub fn parse_exceptions_csv(content: &str) -> CsvImport {
+    let mut records = parse_records(content).into_iter().peekable();
+    let has_header = records.peek()
+        .and_then(|(_, fields)| fields.first())
+        .is_some_and(|first| first.trim().eq_ignore_ascii_case("name"));
+    let columns: Vec<String> = match records.next_if(|_| has_header) {
+        Some((_, header)) => header.iter().map(|column| column.trim().to_lowercase().replace([' ', '-'], "_")).collect(),
+        None => DEFAULT_COLUMNS.iter().map(|column| column.to_string()).collect(),
+    };
+
+    let mut import = CsvImport::default();
+    for (line, fields) in records {
+        let get = |column: &str| {
+            columns.iter().position(|c| c == column)
+                .and_then(|index| fields.get(index))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+
+        let Some(name) = get("name") else {
+            import.warnings.push(format!("line {}: missing package name, skipped", line));
+            continue;
+        };
+        let Some(reason) = get("reason") else {
+            import.warnings.push(format!("line {}: missing reason for '{}', skipped", line, name));
+            continue;
+        };
+        let expires = match get("expires") {
+            None => None,
+            Some(value) if value.eq_ignore_ascii_case("never") || value.eq_ignore_ascii_case("permanent") => None,
+            Some(value) => match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    import.warnings.push(format!("line {}: invalid expiry date '{}' for '{}' (expected YYYY-MM-DD), skipped", line, value, name));
+                    continue;
+                }
+            },
+        };
+
+        import.exceptions.push(Exception {
+            name: name.to_string(),
+            version: get("version").map(str::to_string),
+            reason: reason.to_string(),
+            added_by: get("added_by").map(str::to_string),
+            added_date: Utc::now(),
+            expires,
+            permanent: expires.is_none(),
+            added_interactively: false,
+        });
+    }
+
+    import
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exceptions_csv() {
+        let content = concat!(
+            "Name,Reason,Expires,Version,Added By\r\n",
+            "requests,\"Approved, see ticket \"\"LEGAL-1\"\"\",2030-06-30,2.31.0,alice\r\n",
+            "legacy,\"multi\nline\",never,,\r\n",
+            ",no name,,,\r\n",
+            "click,ok,30/06/2030,,\r\n",
+            "six,,,,\r\n",
+            "\r\n",
+        );
+        let import = parse_exceptions_csv(content);
+
+        assert_eq!(import.exceptions.len(), 2);
+        let requests = &import.exceptions[0];
+        assert_eq!(requests.reason, "Approved, see ticket \"LEGAL-1\"");
+        assert_eq!(requests.version.as_deref(), Some("2.31.0"));
+        assert_eq!(requests.added_by.as_deref(), Some("alice"));
+        assert_eq!(requests.expires, NaiveDate::from_ymd_opt(2030, 6, 30));
+        assert!(import.exceptions[1].permanent);
+        assert_eq!(import.exceptions[1].reason, "multi\nline");
+
+        assert_eq!(import.warnings.len(), 3);
+        assert!(import.warnings[0].starts_with("line 5: missing package name"));
+        assert!(import.warnings[1].contains("invalid expiry date '30/06/2030'"));
+        assert!(import.warnings[2].starts_with("line 7: missing reason for 'six'"));
+    }
+
+    #[test]
+    fn test_parse_exceptions_csv_without_header() {
+        let import = parse_exceptions_csv("requests,2.31.0,approved,bob,2030-01-01\n");
+        assert_eq!(import.exceptions.len(), 1);
+        assert_eq!(import.exceptions[0].added_by.as_deref(), Some("bob"));
+        assert!(import.warnings.is_empty());
+    }
+}
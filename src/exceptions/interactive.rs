@@ -1,47 +1,55 @@
 use std::io::{self, Write};
-use std::collections::HashMap;
+use std::collections::BTreeSet;
 use anyhow::Result;
 use chrono::{Utc, NaiveDate};
 use super::models::Exception;
 use super::storage::{load_exceptions, save_exceptions};
-use crate::policy::{ViolationSummary, Violation};
-use crate::license::normalize_license_name;
+use crate::policy::{group_violations_by_license, LicenseGroup, ViolationSummary};
 
-#[derive(Debug)]
-struct LicenseGroup {
-    license: String,
-    normalized_license: String,
-    packages: Vec<Violation>,
-    violation_type: String,
+/// Group approval: reason, expiry and packages deselected with `e(dit)` (0-based indices)
+struct GroupDecision {
+    reason: String,
+    expires: Option<NaiveDate>,
+    excluded: BTreeSet<usize>,
 }
 
-fn group_violations_by_license(violations: Vec<Violation>) -> Vec<LicenseGroup> {
-    let mut groups: HashMap<String, LicenseGroup> = HashMap::new();
-    
-    for violation in violations {
-        let license = violation.license.as_ref().unwrap_or(&"Unknown".to_string()).clone();
-        let normalized = normalize_license_name(&license);
-        
-        let violation_type = match violation.violation_level {
-            crate::policy::ViolationLevel::Forbidden => "Forbidden license",
-            crate::policy::ViolationLevel::ReviewRequired => "Review required", 
-            crate::policy::ViolationLevel::Unknown => "Unknown license",
-            _ => "Violation",
+/// Parse a 1-based selection like "1 3, 5-7" into 0-based indices
+fn parse_selection(input: &str, len: usize) -> Result<BTreeSet<usize>> {
+    let mut selected = BTreeSet::new();
+    for part in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim().parse::<usize>()?, end.trim().parse::<usize>()?),
+            None => {
+                let n = part.parse::<usize>()?;
+                (n, n)
+            }
         };
-        
-        groups.entry(normalized.clone())
-            .or_insert_with(|| LicenseGroup {
-                license: license.clone(),
-                normalized_license: normalized,
-                packages: Vec::new(),
-                violation_type: violation_type.to_string(),
-            })
-            .packages.push(violation);
+        if start == 0 || end > len || start > end {
+            anyhow::bail!("'{}' is out of range 1-{}", part, len);
+        }
+        selected.extend(start - 1..end);
+    }
+    Ok(selected)
+}
+
+/// List every package of the group and let the user deselect some of them
+fn prompt_for_deselection(group: &LicenseGroup) -> Result<BTreeSet<usize>> {
+    for (i, violation) in group.packages.iter().enumerate() {
+        let version = violation.package_version.as_deref().unwrap_or("unknown");
+        println!("     {:>3}. {} ({})", i + 1, violation.package_name, version);
+    }
+
+    loop {
+        print!("   Deselect packages (e.g. 1 3 5-7, empty for none): ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        match parse_selection(input.trim(), group.packages.len()) {
+            Ok(excluded) => return Ok(excluded),
+            Err(e) => println!("   Invalid selection: {}", e),
+        }
     }
-    
-    let mut result: Vec<_> = groups.into_values().collect();
-    result.sort_by(|a, b| a.normalized_license.cmp(&b.normalized_license));
-    result
 }
 
 fn display_license_group(group: &LicenseGroup, group_num: usize, total_groups: usize) {
@@ -61,21 +69,27 @@ fn display_license_group(group: &LicenseGroup, group_num: usize, total_groups: u
     }
 }
 
-fn prompt_for_group_exception(group: &LicenseGroup, group_num: usize, total_groups: usize) -> Result<Option<(String, Option<NaiveDate>)>> {
+fn prompt_for_group_exception(group: &LicenseGroup, group_num: usize, total_groups: usize) -> Result<Option<GroupDecision>> {
     display_license_group(group, group_num, total_groups);
     
-    print!("   Add exception for ALL packages with this license? [y/N/s(kip)/q(uit)]: ");
+    print!("   Add exception for ALL packages with this license? [y/N/e(dit)/s(kip)/q(uit)]: ");
     io::stdout().flush()?;
     
     let mut input = String::new();
     io::stdin().read_line(&mut input)?;
     let input = input.trim().to_lowercase();
     
-    match input.as_str() {
+    let excluded = match input.as_str() {
         "q" | "quit" => return Err(anyhow::anyhow!("User quit")),
         "s" | "skip" => return Ok(None),
-        input if input.starts_with('y') => {},
+        "e" | "edit" => prompt_for_deselection(group)?,
+        input if input.starts_with('y') => BTreeSet::new(),
         _ => return Ok(None),
+    };
+
+    if excluded.len() == group.packages.len() {
+        println!("   All packages deselected, skipping group");
+        return Ok(None);
     }
     
     // Get reason
@@ -97,8 +111,7 @@ fn prompt_for_group_exception(group: &LicenseGroup, group_num: usize, total_grou
     };
     
     // Get expiration date
-    let today = Utc::now().date_naive();
-    let default_expiry = today + chrono::Duration::days(90);
+    let default_expiry = default_expiry();
     
     print!("   Expiry date (YYYY-MM-DD) [{}]: ", default_expiry.format("%Y-%m-%d"));
     io::stdout().flush()?;
@@ -120,7 +133,7 @@ fn prompt_for_group_exception(group: &LicenseGroup, group_num: usize, total_grou
         }
     };
     
-    Ok(Some((reason, expires)))
+    Ok(Some(GroupDecision { reason, expires, excluded }))
 }
 
 pub fn prompt_for_exception(package_name: &str, package_version: Option<&str>, license: &str, violation_type: &str) -> Result<Option<Exception>> {
@@ -145,20 +158,10 @@ pub fn prompt_for_exception(package_name: &str, package_version: Option<&str>, l
     io::stdin().read_line(&mut reason)?;
     let reason = reason.trim();
     
-    let reason = if reason.is_empty() {
-        "temporary exception".to_string()
-    } else {
-        match reason {
-            "research" => "research prototype".to_string(),
-            "migration" => "migration in progress".to_string(),
-            "legacy" => "legacy compatibility".to_string(),
-            _ => reason.to_string(),
-        }
-    };
+    let reason = expand_reason(reason);
     
     // Get expiration date
-    let today = Utc::now().date_naive();
-    let default_expiry = today + chrono::Duration::days(90); // 3 months default
+    let default_expiry = default_expiry();
     
     print!("   Expiry date (YYYY-MM-DD) [{}]: ", default_expiry.format("%Y-%m-%d"));
     io::stdout().flush()?;
@@ -180,7 +183,28 @@ pub fn prompt_for_exception(package_name: &str, package_version: Option<&str>, l
         }
     };
     
-    let exception = Exception {
+    Ok(Some(new_exception(package_name, package_version, reason, expires)))
+}
+
+/// Expand the reason shortcuts (research/migration/legacy); empty means temporary
+pub fn expand_reason(reason: &str) -> String {
+    match reason.trim() {
+        "" => "temporary exception".to_string(),
+        "research" => "research prototype".to_string(),
+        "migration" => "migration in progress".to_string(),
+        "legacy" => "legacy compatibility".to_string(),
+        reason => reason.to_string(),
+    }
+}
+
+/// Expiry offered by default for new exceptions (3 months)
+pub fn default_expiry() -> NaiveDate {
+    Utc::now().date_naive() + chrono::Duration::days(90)
+}
+
+/// An interactively added exception for one package
+pub fn new_exception(package_name: &str, package_version: Option<&str>, reason: String, expires: Option<NaiveDate>) -> Exception {
+    Exception {
         name: package_name.to_string(),
         version: package_version.map(|v| v.to_string()),
         reason,
@@ -189,9 +213,7 @@ pub fn prompt_for_exception(package_name: &str, package_version: Option<&str>, l
         expires,
         permanent: expires.is_none(),
         added_interactively: true,
-    };
-    
-    Ok(Some(exception))
+    }
 }
 
 /// Handle interactive exception processing for violations using license grouping
@@ -210,12 +232,7 @@ pub fn handle_interactive_exceptions(violations: ViolationSummary) -> Result<Vio
         .collect();
     
     if unexcepted_violations.is_empty() {
-        return Ok(ViolationSummary {
-            total: 0,
-            errors: 0,
-            warnings: 0,
-            details: Vec::new(),
-        });
+        return Ok(ViolationSummary::from_details(Vec::new()));
     }
     
     // Group violations by license
@@ -225,19 +242,19 @@ pub fn handle_interactive_exceptions(violations: ViolationSummary) -> Result<Vio
     
     for (i, group) in groups.iter().enumerate() {
         match prompt_for_group_exception(group, i + 1, groups.len()) {
-            Ok(Some((reason, expires))) => {
-                // Add exceptions for all packages in this group
-                for violation in &group.packages {
-                    let exception = Exception {
-                        name: violation.package_name.clone(),
-                        version: violation.package_version.clone(),
-                        reason: reason.clone(),
-                        added_by: std::env::var("USER").ok().or_else(|| std::env::var("USERNAME").ok()),
-                        added_date: Utc::now(),
+            Ok(Some(GroupDecision { reason, expires, excluded })) => {
+                // Add exceptions for the selected packages; deselected ones remain violations
+                for (index, violation) in group.packages.iter().enumerate() {
+                    if excluded.contains(&index) {
+                        remaining_violations.push(violation.clone());
+                        continue;
+                    }
+                    let exception = new_exception(
+                        &violation.package_name,
+                        violation.package_version.as_deref(),
+                        reason.clone(),
                         expires,
-                        permanent: expires.is_none(),
-                        added_interactively: true,
-                    };
+                    );
                     exceptions.add_exception(exception);
                     exceptions_added += 1;
                 }
@@ -263,16 +280,20 @@ pub fn handle_interactive_exceptions(violations: ViolationSummary) -> Result<Vio
     }
     
     // Update violation summary with remaining violations
-    let errors = remaining_violations.iter().filter(|v| v.violation_level == crate::policy::ViolationLevel::Forbidden).count();
-    let warnings = remaining_violations.iter().filter(|v| 
-        v.violation_level == crate::policy::ViolationLevel::ReviewRequired || 
-        v.violation_level == crate::policy::ViolationLevel::Unknown
-    ).count();
-    
-    Ok(ViolationSummary {
-        total: remaining_violations.len(),
-        errors,
-        warnings,
-        details: remaining_violations,
-    })
+    Ok(ViolationSummary::from_details(remaining_violations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("", 5).unwrap(), BTreeSet::new());
+        assert_eq!(parse_selection("1 3, 4-5", 5).unwrap(), BTreeSet::from([0, 2, 3, 4]));
+        assert!(parse_selection("0", 5).is_err());
+        assert!(parse_selection("6", 5).is_err());
+        assert!(parse_selection("3-2", 5).is_err());
+        assert!(parse_selection("abc", 5).is_err());
+    }
 }
@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use super::models::Exception;
+use crate::policy::PackageException;
+use crate::uv_lock::normalize_package_name;
+
+/// Exceptions converted for the other store, plus notes about dropped or invented fields
+#[derive(Debug)]
+pub struct Migration<T> {
+    pub exceptions: Vec<T>,
+    /// Entries already present in the target store (same package and version)
+    pub skipped: usize,
+    pub warnings: Vec<String>,
+}
+
+fn same_entry(name: &str, version: Option<&str>, other_name: &str, other_version: Option<&str>) -> bool {
+    normalize_package_name(name) == normalize_package_name(other_name) && version == other_version
+}
+
+/// `.exceptions.toml` → pyproject.toml. The policy has no fields for who approved an
+/// entry or when, so `added_by` / `added_date` are lost.
+pub fn to_policy_exceptions(exceptions: &[Exception], existing: &[PackageException]) -> Migration<PackageException> {
+    let (present, new): (Vec<&Exception>, Vec<&Exception>) = exceptions.iter().partition(|exception| {
+        existing.iter().any(|e| same_entry(&e.name, e.version.as_deref(), &exception.name, exception.version.as_deref()))
+    });
+
+    let mut warnings = vec![];
+    if !new.is_empty() {
+        warnings.push(format!("added_date is not stored in pyproject.toml; dropped for {} exception(s)", new.len()));
+    }
+    let with_author = new.iter().filter(|exception| exception.added_by.is_some()).count();
+    if with_author > 0 {
+        warnings.push(format!("added_by is not stored in pyproject.toml; dropped for {} exception(s)", with_author));
+    }
+
+    Migration {
+        exceptions: new.into_iter().map(|exception| PackageException {
+            name: exception.name.clone(),
+            version: exception.version.clone(),
+            reason: exception.reason.clone(),
+            expires: exception.expires,
+        }).collect(),
+        skipped: present.len(),
+        warnings,
+    }
+}
+
+/// pyproject.toml → `.exceptions.toml`. Entries get `now` as their `added_date`.
+pub fn to_file_exceptions(exceptions: &[PackageException], existing: &[Exception], now: DateTime<Utc>) -> Migration<Exception> {
+    let (present, new): (Vec<&PackageException>, Vec<&PackageException>) = exceptions.iter().partition(|exception| {
+        existing.iter().any(|e| same_entry(&e.name, e.version.as_deref(), &exception.name, exception.version.as_deref()))
+    });
+
+    let mut warnings = vec![];
+    if !new.is_empty() {
+        warnings.push(format!(
+            "pyproject.toml has no added_date; using {} for {} exception(s)",
+            now.format("%Y-%m-%d"), new.len()
+        ));
+    }
+
+    Migration {
+        exceptions: new.into_iter().map(|exception| Exception {
+            name: exception.name.clone(),
+            version: exception.version.clone(),
+            reason: exception.reason.clone(),
+            added_by: None,
+            added_date: now,
+            expires: exception.expires,
+            permanent: exception.expires.is_none(),
+            added_interactively: false,
+        }).collect(),
+        skipped: present.len(),
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_round_trip_between_stores() {
+        let now = Utc::now();
+        let expires = NaiveDate::from_ymd_opt(2030, 6, 30);
+        let exceptions = vec![
+            Exception {
+                name: "legacy_lib".to_string(),
+                version: Some("1.0".to_string()),
+                reason: "approved by legal".to_string(),
+                added_by: Some("alice".to_string()),
+                added_date: now,
+                expires,
+                permanent: false,
+                added_interactively: true,
+            },
+            Exception {
+                name: "internal-tool".to_string(),
+                version: None,
+                reason: "first-party".to_string(),
+                added_by: None,
+                added_date: now,
+                expires: None,
+                permanent: true,
+                added_interactively: false,
+            },
+        ];
+
+        let to_policy = to_policy_exceptions(&exceptions, &[]);
+        assert_eq!(to_policy.exceptions.len(), 2);
+        assert_eq!(to_policy.exceptions[0].expires, expires);
+        assert_eq!(to_policy.warnings.len(), 2);
+        assert!(to_policy.warnings[1].contains("added_by"));
+
+        let back = to_file_exceptions(&to_policy.exceptions, &[], now);
+        for (original, migrated) in exceptions.iter().zip(&back.exceptions) {
+            assert_eq!(migrated.name, original.name);
+            assert_eq!(migrated.version, original.version);
+            assert_eq!(migrated.reason, original.reason);
+            assert_eq!(migrated.expires, original.expires);
+            assert_eq!(migrated.permanent, original.permanent);
+        }
+
+        // Entries already in the target store are not duplicated
+        let again = to_file_exceptions(&to_policy.exceptions, &exceptions, now);
+        assert!(again.exceptions.is_empty());
+        assert_eq!(again.skipped, 2);
+        assert!(again.warnings.is_empty());
+    }
+}
@@ -2,11 +2,16 @@ pub mod models;
 pub mod storage;
 pub mod checker;
 pub mod interactive;
+pub mod import;
+pub mod migrate;
 
 // Re-export commonly used items
 pub use models::{Exception, ExceptionsFile};
 pub use storage::{load_exceptions, save_exceptions, get_exceptions_file_path};
-pub use interactive::{prompt_for_exception, handle_interactive_exceptions};
+pub use interactive::{prompt_for_exception, handle_interactive_exceptions, new_exception, expand_reason, default_expiry};
+pub use checker::{find_exception_conflicts, version_spec_matches, ConflictKind, ExceptionConflict};
+pub use import::{parse_exceptions_csv, CsvImport};
+pub use migrate::{to_file_exceptions, to_policy_exceptions, Migration};
 
 #[cfg(test)]
 mod tests {
@@ -188,4 +193,121 @@ mod tests {
         assert_eq!(loaded_exceptions.exceptions[0].name, "test-package");
         assert_eq!(loaded_exceptions.exceptions[0].version, Some("1.0.0".to_string()));
     }
+
+    #[test]
+    fn test_write_to_stable_layout() {
+        let added_date = chrono::DateTime::parse_from_rfc3339("2024-03-01T09:30:00Z").unwrap().with_timezone(&Utc);
+        let mut exceptions_file = ExceptionsFile::new();
+        exceptions_file.add_exception(Exception {
+            name: "internal-lib".to_string(),
+            version: None,
+            reason: "Vendored \"as is\"".to_string(),
+            added_by: Some("legal".to_string()),
+            added_date,
+            expires: NaiveDate::from_ymd_opt(2025, 1, 31),
+            permanent: false,
+            added_interactively: true,
+        });
+        exceptions_file.add_exception(Exception {
+            name: "gpl-tool".to_string(),
+            version: Some("2.0".to_string()),
+            reason: "Build only".to_string(),
+            added_by: None,
+            added_date,
+            expires: None,
+            permanent: true,
+            added_interactively: false,
+        });
+
+        let mut written = Vec::new();
+        exceptions_file.write_to(&mut written).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert_eq!(written, concat!(
+            "[[exceptions]]\n",
+            "name = \"internal-lib\"\n",
+            "reason = 'Vendored \"as is\"'\n",
+            "added_by = \"legal\"\n",
+            "added_date = \"2024-03-01T09:30:00Z\"\n",
+            "expires = \"2025-01-31\"\n",
+            "permanent = false\n",
+            "added_interactively = true\n",
+            "\n",
+            "[[exceptions]]\n",
+            "name = \"gpl-tool\"\n",
+            "version = \"2.0\"\n",
+            "reason = \"Build only\"\n",
+            "added_date = \"2024-03-01T09:30:00Z\"\n",
+            "permanent = true\n",
+            "added_interactively = false\n",
+        ));
+
+        let parsed: ExceptionsFile = toml::from_str(&written).unwrap();
+        assert_eq!(parsed.exceptions[0].reason, "Vendored \"as is\"");
+        assert_eq!(parsed.exceptions[0].added_date, added_date);
+        assert_eq!(parsed.exceptions[1].version.as_deref(), Some("2.0"));
+    }
+
+    fn exception_for(name: &str, version: Option<&str>, expires: Option<NaiveDate>) -> Exception {
+        Exception {
+            name: name.to_string(),
+            version: version.map(|v| v.to_string()),
+            reason: "testing".to_string(),
+            added_by: None,
+            added_date: Utc::now(),
+            expires,
+            permanent: expires.is_none(),
+            added_interactively: false,
+        }
+    }
+
+    #[test]
+    fn test_find_conflicts_overlapping_versions() {
+        let mut exceptions_file = ExceptionsFile::new();
+        exceptions_file.add_exception(exception_for("requests", Some("*"), None));
+        exceptions_file.add_exception(exception_for("requests", Some("2.31.0"), None));
+        exceptions_file.add_exception(exception_for("click", Some("8.1.7"), None));
+        exceptions_file.add_exception(exception_for("click", Some("8.1.6"), None));
+
+        let conflicts = exceptions_file.find_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].name, "requests");
+        assert_eq!(conflicts[0].kind, ConflictKind::Overlapping);
+        assert_eq!(conflicts[0].indices, (0, 1));
+    }
+
+    #[test]
+    fn test_find_conflicts_duplicate() {
+        let conflicts = find_exception_conflicts(vec![
+            ("requests", None),
+            ("requests", Some("*")),
+            ("Typing_Extensions", None),
+            ("typing-extensions", None),
+        ]);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].kind, ConflictKind::Duplicate);
+        assert_eq!(conflicts[1].indices, (2, 3));
+    }
+
+    #[test]
+    fn test_dedup_keeps_broadest_longest_lived() {
+        let mut exceptions_file = ExceptionsFile::new();
+        let soon = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let later = NaiveDate::from_ymd_opt(2031, 1, 1).unwrap();
+
+        exceptions_file.add_exception(exception_for("requests", Some("2.31.0"), Some(soon)));
+        exceptions_file.add_exception(exception_for("requests", None, Some(later)));
+        exceptions_file.add_exception(exception_for("requests", None, Some(later)));
+        // Specific version outlives the wildcard, so merging it would lose coverage
+        exceptions_file.add_exception(exception_for("click", None, Some(soon)));
+        exceptions_file.add_exception(exception_for("click", Some("8.1.7"), None));
+        // Spellings of the same package are one package
+        exceptions_file.add_exception(exception_for("Requests", Some("2.32.0"), Some(soon)));
+
+        let removed = exceptions_file.dedup();
+        assert_eq!(removed, 3);
+        assert_eq!(exceptions_file.exceptions.len(), 3);
+        assert_eq!(exceptions_file.exceptions[0].name, "requests");
+        assert_eq!(exceptions_file.exceptions[0].version, None);
+        assert!(exceptions_file.find_conflicts().iter().all(|c| c.name == "click"));
+    }
 }
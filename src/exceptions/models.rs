@@ -13,7 +13,7 @@ pub struct Exception {
     pub added_interactively: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ExceptionsFile {
     pub exceptions: Vec<Exception>,
 }
@@ -1,7 +1,34 @@
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
-use super::models::ExceptionsFile;
+use chrono::SecondsFormat;
+use crate::atomic_file::write_atomic;
+use super::models::{Exception, ExceptionsFile};
+
+/// A TOML basic string (quoted and escaped)
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// One `[[exceptions]]` table, keys in a fixed order so the file diffs cleanly
+fn write_exception<W: Write>(writer: &mut W, exception: &Exception) -> std::io::Result<()> {
+    writeln!(writer, "[[exceptions]]")?;
+    writeln!(writer, "name = {}", toml_string(&exception.name))?;
+    if let Some(version) = &exception.version {
+        writeln!(writer, "version = {}", toml_string(version))?;
+    }
+    writeln!(writer, "reason = {}", toml_string(&exception.reason))?;
+    if let Some(added_by) = &exception.added_by {
+        writeln!(writer, "added_by = {}", toml_string(added_by))?;
+    }
+    writeln!(writer, "added_date = {}", toml_string(&exception.added_date.to_rfc3339_opts(SecondsFormat::AutoSi, true)))?;
+    if let Some(expires) = &exception.expires {
+        writeln!(writer, "expires = {}", toml_string(&expires.to_string()))?;
+    }
+    writeln!(writer, "permanent = {}", exception.permanent)?;
+    writeln!(writer, "added_interactively = {}", exception.added_interactively)
+}
 
 impl ExceptionsFile {
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -15,12 +42,20 @@ impl ExceptionsFile {
     }
 
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let content = toml::to_string_pretty(self)
-            .context("Failed to serialize exceptions")?;
-        
-        fs::write(&path, content)
-            .with_context(|| format!("Failed to write exceptions file: {}", path.as_ref().display()))?;
-        
+        write_atomic(path.as_ref(), |writer| self.write_to(writer))
+            .with_context(|| format!("Failed to write exceptions file: {}", path.as_ref().display()))
+    }
+
+    /// Write the file entry by entry, without building it in memory. Keys keep a fixed
+    /// order: name, version, reason, added_by, added_date, expires, permanent.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<()> {
+        for (index, exception) in self.exceptions.iter().enumerate() {
+            if index > 0 {
+                writeln!(writer)?;
+            }
+            write_exception(&mut writer, exception)?;
+        }
+        writer.flush()?;
         Ok(())
     }
 }
@@ -0,0 +1,257 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::license::extractor::{locate_package, metadata_file};
+use crate::license::{finalize_package, normalize_license_name, PackageLicense};
+use crate::policy::{LicensePolicy, PackageException, ViolationLevel};
+use crate::exceptions::version_spec_matches;
+use crate::i18n::Locale;
+use crate::uv_lock::normalize_package_name;
+
+/// Metadata fields that decide a package's license
+const LICENSE_FIELDS: [&str; 5] = ["Name", "Version", "License", "License-Expression", "License-File"];
+
+/// How the policy judges one package (`check --explain`)
+#[derive(Debug, Clone)]
+pub struct PolicyVerdict {
+    /// Level before exceptions are applied
+    pub level: ViolationLevel,
+    pub matched_rule: Option<String>,
+    /// The exception for this package, even when it has expired
+    pub exception: Option<PackageException>,
+    pub exception_expired: bool,
+    /// On the stdlib backport allowlist (built-in or `backport_allowlist`)
+    pub backport: bool,
+}
+
+impl PolicyVerdict {
+    /// Whether an active exception overrides the level (the backport allowlist never overrides Forbidden)
+    pub fn excepted(&self) -> bool {
+        let backport = self.backport && self.level != ViolationLevel::Forbidden;
+        (backport || (self.exception.is_some() && !self.exception_expired)) && self.level != ViolationLevel::Allowed
+    }
+}
+
+/// Everything that went into one package's license decision
+#[derive(Debug, Clone)]
+pub struct PackageExplanation {
+    pub metadata_dir: PathBuf,
+    pub metadata_file: Option<PathBuf>,
+    /// License-related header fields as written in METADATA / PKG-INFO
+    pub raw_fields: Vec<(String, String)>,
+    pub package: PackageLicense,
+    pub normalized_license: Option<String>,
+    pub verdict: Option<PolicyVerdict>,
+}
+
+/// License-related header fields of a METADATA / PKG-INFO file, in file order
+fn license_fields(content: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    let mut continuing = false;
+
+    for line in content.lines() {
+        // The body (long description) starts after the first blank line
+        if line.trim().is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            if continuing {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push('\n');
+                    value.push_str(line.trim());
+                }
+            }
+            continue;
+        }
+
+        continuing = false;
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        let wanted = LICENSE_FIELDS.contains(&key) || (key == "Classifier" && value.starts_with("License ::"));
+        if wanted {
+            fields.push((key.to_string(), value.to_string()));
+            continuing = true;
+        }
+    }
+
+    fields
+}
+
+fn judge(policy: &LicensePolicy, package: &PackageLicense, today: NaiveDate) -> PolicyVerdict {
+    let without_exceptions = LicensePolicy { exceptions: vec![], backport_allowlist: Some(vec![]), ..policy.clone() };
+    let (level, matched_rule) = match without_exceptions.detect_violations_at(std::slice::from_ref(package), today, Locale::En).details.pop() {
+        Some(violation) => (violation.violation_level, violation.matched_rule),
+        None => {
+            let matched = package.effective_license.as_deref()
+                .and_then(|license| policy.allowed_licenses.find_match(&normalize_license_name(license)));
+            (ViolationLevel::Allowed, matched)
+        }
+    };
+
+    let exception = policy.exceptions.iter().find(|exception| {
+        normalize_package_name(&exception.name) == normalize_package_name(&package.name)
+            && version_spec_matches(exception.version.as_deref(), package.version.as_deref())
+    });
+    PolicyVerdict {
+        level,
+        matched_rule,
+        exception_expired: exception.and_then(|e| e.expires).is_some_and(|expires| expires < today),
+        exception: exception.cloned(),
+        backport: policy.is_backport_package(&package.name),
+    }
+}
+
+/// Explain the license decision for one installed package
+pub fn explain_package(site_packages: &Path, package_name: &str, policy: Option<&LicensePolicy>, today: NaiveDate) -> Result<PackageExplanation> {
+    let (metadata_dir, package) = locate_package(site_packages, package_name)?;
+    let metadata_file = metadata_file(&metadata_dir);
+    let raw_fields = match &metadata_file {
+        Some(path) => license_fields(
+            &fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?,
+        ),
+        None => vec![],
+    };
+
+    let package = finalize_package(package);
+    let normalized_license = package.effective_license.as_deref().map(normalize_license_name);
+    let verdict = policy.map(|policy| judge(policy, &package, today));
+
+    Ok(PackageExplanation { metadata_dir, metadata_file, raw_fields, package, normalized_license, verdict })
+}
+
+fn level_label(level: &ViolationLevel) -> &'static str {
+    match level {
+        ViolationLevel::Allowed => "allowed",
+        ViolationLevel::ReviewRequired => "review required",
+        ViolationLevel::Forbidden => "forbidden",
+        ViolationLevel::Unknown => "unknown",
+    }
+}
+
+/// Human-readable explanation, one fact per line
+pub fn format_explanation(explanation: &PackageExplanation) -> String {
+    let package = &explanation.package;
+    let mut lines = vec![format!("📦 {} {}", package.name, package.version.as_deref().unwrap_or("(no version)"))];
+
+    lines.push(format!("   Location: {}", explanation.metadata_dir.display()));
+    match &explanation.metadata_file {
+        Some(path) => lines.push(format!("   Metadata: {}", path.display())),
+        None => lines.push("   Metadata: missing".to_string()),
+    }
+    if !explanation.raw_fields.is_empty() {
+        lines.push("   Raw fields:".to_string());
+        for (key, value) in &explanation.raw_fields {
+            lines.push(format!("     {}: {}", key, value.replace('\n', "\n       ")));
+        }
+    }
+
+    lines.push(format!(
+        "   Effective license: {} (from {})",
+        package.effective_license.as_deref().unwrap_or("none"),
+        package.metadata_source
+    ));
+    if let Some(normalized) = &explanation.normalized_license {
+        lines.push(format!("   Normalized: {}", normalized));
+    }
+    for warning in &package.warnings {
+        lines.push(format!("   ⚠️  {}", warning));
+    }
+
+    let Some(verdict) = &explanation.verdict else {
+        lines.push("   Policy: none configured".to_string());
+        return lines.join("\n");
+    };
+    lines.push(format!(
+        "   Policy: {}{}",
+        level_label(&verdict.level),
+        verdict.matched_rule.as_deref().map(|rule| format!(" ({})", rule)).unwrap_or_default()
+    ));
+    match &verdict.exception {
+        Some(exception) => {
+            let expires = match exception.expires {
+                Some(date) if verdict.exception_expired => format!("expired {}", date.format("%Y-%m-%d")),
+                Some(date) => format!("expires {}", date.format("%Y-%m-%d")),
+                None => "never expires".to_string(),
+            };
+            lines.push(format!("   Exception: {} ({})", exception.reason, expires));
+        }
+        None if verdict.backport && verdict.level == ViolationLevel::Forbidden => {
+            lines.push("   Exception: stdlib backport allowlist (does not cover forbidden licenses)".to_string())
+        }
+        None if verdict.backport => lines.push("   Exception: stdlib backport allowlist".to_string()),
+        None => lines.push("   Exception: none".to_string()),
+    }
+    let result = if verdict.excepted() { "excepted" } else { level_label(&verdict.level) };
+    lines.push(format!("   Result: {}", result));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_package() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dist_info = temp_dir.path().join("legacy_lib-1.0.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(dist_info.join("METADATA"), concat!(
+            "Metadata-Version: 2.1\n",
+            "Name: legacy-lib\n",
+            "Version: 1.0\n",
+            "License: GNU GPL v3\n",
+            "        with extra terms\n",
+            "Classifier: Programming Language :: Python\n",
+            "Classifier: License :: OSI Approved :: GNU General Public License v3 (GPLv3)\n",
+            "\n",
+            "License: not a header\n",
+        )).unwrap();
+
+        let policy: LicensePolicy = toml::from_str(r#"
+name = "strict"
+exceptions = [{ name = "legacy_lib", version = "1.0", reason = "approved by legal", expires = "2030-06-30" }]
+[forbidden_licenses]
+patterns = ["GPL-*"]
+"#).unwrap();
+        let today = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let explanation = explain_package(temp_dir.path(), "legacy-lib", Some(&policy), today).unwrap();
+
+        assert_eq!(explanation.raw_fields.len(), 4);
+        assert_eq!(explanation.raw_fields[2], ("License".to_string(), "GNU GPL v3\nwith extra terms".to_string()));
+        let verdict = explanation.verdict.as_ref().unwrap();
+        assert_eq!(verdict.level, ViolationLevel::Forbidden);
+        assert!(verdict.excepted());
+
+        let text = format_explanation(&explanation);
+        assert!(text.contains("Exception: approved by legal (expires 2030-06-30)"));
+        assert!(text.contains("Result: excepted"));
+
+        let later = NaiveDate::from_ymd_opt(2031, 1, 1).unwrap();
+        let expired = explain_package(temp_dir.path(), "legacy-lib", Some(&policy), later).unwrap();
+        assert!(format_explanation(&expired).contains("Result: forbidden"));
+        assert!(explain_package(temp_dir.path(), "missing", None, today).is_err());
+    }
+
+    #[test]
+    fn test_explain_range_exception() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dist_info = temp_dir.path().join("gpl_lib-1.4.2.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(dist_info.join("METADATA"), "Metadata-Version: 2.1\nName: gpl-lib\nVersion: 1.4.2\nLicense: GPL-3.0\n").unwrap();
+
+        let policy: LicensePolicy = toml::from_str(r#"
+name = "strict"
+exceptions = [{ name = "gpl-lib", version = ">=1.0,<2", reason = "approved for 1.x" }]
+[forbidden_licenses]
+patterns = ["GPL-*"]
+"#).unwrap();
+        let today = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let explanation = explain_package(temp_dir.path(), "gpl-lib", Some(&policy), today).unwrap();
+
+        // Same decision as `check`: the range covers 1.4.2
+        assert!(explanation.verdict.as_ref().unwrap().excepted());
+        assert!(format_explanation(&explanation).contains("Exception: approved for 1.x"));
+    }
+}
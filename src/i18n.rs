@@ -0,0 +1,172 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Language of human-readable output (`check --locale`)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+}
+
+/// English catalog: message id → text, `{}` marks an argument
+const EN: &[(&str, &str)] = &[
+    ("summary.header", "📦 License Summary ({} packages)"),
+    ("summary.counts", "✅ {} with licenses  ⚠️ {} unknown  🚫 {} violations"),
+    ("summary.line", "{} packages, {} with license, {} unknown"),
+    ("summary.line_violations", ", {} violations"),
+    ("filter.showing", "🔎 Showing only {} packages ({} of {}); summary covers all packages"),
+    ("filter.osi", "OSI-approved"),
+    ("filter.non_osi", "non-OSI"),
+    ("table.collapsed", "📚 Collapsed: {}"),
+    ("table.all_packages", "📦 All Packages:"),
+    ("table.annotations", "📝 Annotations:"),
+    ("table.issues_found", "🔍 Issues Found:"),
+    ("table.no_issues", "✅ No issues found!"),
+    ("table.no_packages", "No packages found."),
+    ("table.not_installed_hint", "💡 {} locked packages are not installed; run `uv sync` and re-check"),
+    ("table.verbose_hint", "💡 Run with --verbose to see all {} packages"),
+    ("table.metadata_warnings", "⚠️  Metadata warnings ({} packages):"),
+    ("footer.policy", "📖 License policy: {}"),
+    ("column.package", "Package"),
+    ("column.version", "Version"),
+    ("column.license", "License"),
+    ("column.status", "Status"),
+    ("column.problem", "Problem"),
+    ("status.ok", "OK"),
+    ("status.unknown", "Unknown"),
+    ("status.problem", "Problem"),
+    ("status.forbidden", "FORBIDDEN"),
+    ("status.review", "REVIEW"),
+    ("status.unknown_upper", "UNKNOWN"),
+    ("issue.no_license", "No license info"),
+    ("issue.not_installed", "Not installed"),
+    ("issue.no_metadata", "No METADATA"),
+    ("issue.forbidden", "Forbidden"),
+    ("issue.review_required", "Review required"),
+    ("issue.not_allowed", "Not allowed"),
+    ("issue.requires_review", "Requires review"),
+    ("violations.found", "License violations found: {} total ({} errors, {} review required, {} unknown)"),
+    ("violations.exit", "Exiting with error due to {}"),
+    ("reason.forbidden", "forbidden licenses"),
+    ("reason.review_required", "licenses requiring review"),
+    ("reason.unknown", "unknown licenses"),
+    ("reason.max_review", "{} review-required packages exceeding --max-review {} by {}"),
+    ("violation.forbidden", "License '{}' is forbidden by policy"),
+    ("violation.review_required", "License '{}' requires review"),
+    ("violation.not_allowed", "License '{}' is not in allowed list"),
+    ("violation.no_license", "No license information found"),
+    ("violation.note", "Note: {}"),
+    ("violation.expression", "{} (license expression '{}', deciding branch '{}')"),
+];
+
+/// Japanese catalog; ids missing here fall back to English
+const JA: &[(&str, &str)] = &[
+    ("summary.header", "📦 ライセンス概要（{} パッケージ）"),
+    ("summary.counts", "✅ ライセンスあり {}  ⚠️ 不明 {}  🚫 違反 {}"),
+    ("summary.line", "{} パッケージ、ライセンスあり {}、不明 {}"),
+    ("summary.line_violations", "、違反 {}"),
+    ("filter.showing", "🔎 {}のパッケージのみ表示（{} / {}）。概要は全パッケージが対象"),
+    ("filter.osi", "OSI 承認済み"),
+    ("filter.non_osi", "OSI 非承認"),
+    ("table.collapsed", "📚 集約: {}"),
+    ("table.all_packages", "📦 全パッケージ:"),
+    ("table.annotations", "📝 注記:"),
+    ("table.issues_found", "🔍 検出された問題:"),
+    ("table.no_issues", "✅ 問題は見つかりませんでした"),
+    ("table.no_packages", "パッケージが見つかりません。"),
+    ("table.not_installed_hint", "💡 ロックされた {} 個のパッケージが未インストールです。`uv sync` 後に再チェックしてください"),
+    ("table.verbose_hint", "💡 全 {} パッケージを表示するには --verbose を付けてください"),
+    ("table.metadata_warnings", "⚠️  メタデータの警告（{} パッケージ）:"),
+    ("footer.policy", "📖 ライセンスポリシー: {}"),
+    ("column.package", "パッケージ"),
+    ("column.version", "版"),
+    ("column.license", "ライセンス"),
+    ("column.status", "状態"),
+    ("column.problem", "問題"),
+    ("status.unknown", "不明"),
+    ("status.problem", "問題あり"),
+    ("status.forbidden", "禁止"),
+    ("status.review", "要レビュー"),
+    ("status.unknown_upper", "不明"),
+    ("issue.no_license", "ライセンス情報なし"),
+    ("issue.not_installed", "未インストール"),
+    ("issue.no_metadata", "METADATAなし"),
+    ("issue.forbidden", "禁止"),
+    ("issue.review_required", "要レビュー"),
+    ("issue.not_allowed", "許可リスト外"),
+    ("issue.requires_review", "要レビュー"),
+    ("violations.found", "ライセンス違反: 合計 {}（エラー {}、要レビュー {}、不明 {}）"),
+    ("violations.exit", "次の理由でエラー終了します: {}"),
+    ("reason.forbidden", "禁止ライセンス"),
+    ("reason.review_required", "要レビューのライセンス"),
+    ("reason.unknown", "不明なライセンス"),
+    ("reason.max_review", "要レビューのパッケージ {} 個が --max-review {} を {} 超過"),
+    ("violation.forbidden", "ライセンス '{}' はポリシーで禁止されています"),
+    ("violation.review_required", "ライセンス '{}' はレビューが必要です"),
+    ("violation.not_allowed", "ライセンス '{}' は許可リストにありません"),
+    ("violation.no_license", "ライセンス情報が見つかりません"),
+    ("violation.note", "注記: {}"),
+    ("violation.expression", "{}（ライセンス式 '{}'、判定を決めた分岐 '{}'）"),
+];
+
+/// `--locale` values: `en`, `ja`, or a tag such as `ja_JP.UTF-8`
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().split(['_', '-', '.']).next() {
+            Some("en") => Ok(Locale::En),
+            Some("ja") => Ok(Locale::Ja),
+            _ => Err(format!("unsupported locale '{}' (expected en or ja)", value)),
+        }
+    }
+}
+
+impl Locale {
+    /// The text of message `id`. Unknown ids are a programming error and return the id itself.
+    pub fn text(self, id: &'static str) -> &'static str {
+        let lookup = |catalog: &[(&'static str, &'static str)]| catalog.iter().find(|(key, _)| *key == id).map(|(_, text)| *text);
+        let localized = match self {
+            Locale::En => None,
+            Locale::Ja => lookup(JA),
+        };
+        localized.or_else(|| lookup(EN)).unwrap_or(id)
+    }
+
+    /// `text` with each `{}` replaced by the next argument
+    pub fn format(self, id: &'static str, args: &[&dyn Display]) -> String {
+        let mut parts = self.text(id).split("{}");
+        let mut output = parts.next().unwrap_or_default().to_string();
+        for (index, part) in parts.enumerate() {
+            if let Some(arg) = args.get(index) {
+                output.push_str(&arg.to_string());
+            }
+            output.push_str(part);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_lookup() {
+        assert_eq!("ja_JP.UTF-8".parse(), Ok(Locale::Ja));
+        assert_eq!("EN".parse(), Ok(Locale::En));
+        assert!("fr".parse::<Locale>().is_err());
+
+        assert_eq!(Locale::En.format("summary.header", &[&3]), "📦 License Summary (3 packages)");
+        assert_eq!(Locale::Ja.format("summary.header", &[&3]), "📦 ライセンス概要（3 パッケージ）");
+        // Missing in the Japanese catalog: English fallback
+        assert_eq!(Locale::Ja.text("status.ok"), "OK");
+
+        // Every localized id exists in the English catalog with the same number of arguments
+        for (id, text) in JA {
+            let english = EN.iter().find(|(key, _)| key == id).unwrap_or_else(|| panic!("{} missing in EN", id)).1;
+            assert_eq!(english.matches("{}").count(), text.matches("{}").count(), "{}", id);
+        }
+    }
+}
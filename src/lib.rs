@@ -5,6 +5,18 @@ pub mod exceptions;
 pub mod config;
 pub mod uv_lock;
 pub mod init;
+pub mod sbom;
+pub mod baseline;
+pub mod diff_base;
+pub mod explain;
+pub mod audit_ignore;
+pub mod webhook;
+pub mod i18n;
+pub mod python_version;
+pub mod atomic_file;
+mod curl;
+#[cfg(feature = "tui")]
+pub mod tui;
 
 // Re-export main types for easy access
 pub use license::{PackageLicense, LicenseReport, LicenseSummary, LicenseTypes};
@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::{normalize_license_name, DetectionStatus, PackageLicense};
+
+/// The fields we need from a `conda-meta/<name>-<version>-<build>.json` record
+#[derive(Debug, Deserialize)]
+struct CondaRecord {
+    name: String,
+    version: Option<String>,
+    license: Option<String>,
+}
+
+/// `$CONDA_PREFIX`, when it points at an environment with a `conda-meta` directory
+pub fn detect_conda_prefix() -> Option<PathBuf> {
+    let prefix = PathBuf::from(std::env::var_os("CONDA_PREFIX")?);
+    prefix.join("conda-meta").is_dir().then_some(prefix)
+}
+
+/// Extract licenses of a conda/mamba environment from `<prefix>/conda-meta/*.json`
+pub fn extract_licenses_from_conda(prefix: &Path, include_unknown: bool) -> Result<Vec<PackageLicense>> {
+    let conda_meta = prefix.join("conda-meta");
+    let entries = fs::read_dir(&conda_meta)
+        .with_context(|| format!("Not a conda environment (no {})", conda_meta.display()))?;
+
+    let mut packages = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let record: CondaRecord = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid conda metadata: {}", path.display()))?;
+
+        let effective_license = record.license
+            .as_deref()
+            .map(str::trim)
+            .filter(|license| !license.is_empty() && !license.eq_ignore_ascii_case("unknown"))
+            .map(normalize_license_name);
+        let detection_status = if effective_license.is_some() {
+            DetectionStatus::Found
+        } else {
+            DetectionStatus::NoLicenseField
+        };
+
+        if include_unknown || effective_license.is_some() {
+            packages.push(PackageLicense {
+                name: record.name,
+                version: record.version,
+                effective_license,
+                license_classifiers: vec![],
+                metadata_source: "conda-meta".to_string(),
+                license_expression: None,
+                warnings: vec![],
+                annotation: None,
+                detection_status,
+                raw_license: record.license,
+                metadata_name: None,
+                license_confidence: None,
+                license_url: None,
+            });
+        }
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_licenses_from_conda() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let conda_meta = temp_dir.path().join("conda-meta");
+        fs::create_dir_all(&conda_meta).unwrap();
+        fs::write(
+            conda_meta.join("numpy-1.26.4-py312h8753938_0.json"),
+            r#"{"name": "numpy", "version": "1.26.4", "license": "BSD-3-Clause", "files": []}"#,
+        ).unwrap();
+        fs::write(
+            conda_meta.join("openssl-3.3.1-h4bc722e_2.json"),
+            r#"{"name": "openssl", "version": "3.3.1", "license": "Apache 2.0"}"#,
+        ).unwrap();
+        fs::write(conda_meta.join("mystery-1.0-0.json"), r#"{"name": "mystery", "version": "1.0"}"#).unwrap();
+        fs::write(conda_meta.join("history"), "==> 2024-01-01 <==").unwrap();
+
+        let packages = extract_licenses_from_conda(temp_dir.path(), true).unwrap();
+        assert_eq!(packages.len(), 3);
+        assert_eq!(packages[0].name, "mystery");
+        assert_eq!(packages[0].detection_status, DetectionStatus::NoLicenseField);
+        assert_eq!(packages[1].effective_license.as_deref(), Some("BSD-3-Clause"));
+        assert_eq!(packages[1].metadata_source, "conda-meta");
+        assert_eq!(packages[2].effective_license.as_deref(), Some("Apache-2.0"));
+
+        assert_eq!(extract_licenses_from_conda(temp_dir.path(), false).unwrap().len(), 2);
+    }
+}
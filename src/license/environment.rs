@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::uv_lock::UvLockParser;
+use super::find_site_packages_path;
+
+/// What was scanned, recorded so archived reports are self-describing
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnvironmentInfo {
+    pub uv_lock: Option<String>,
+    pub requires_python: Option<String>,
+    pub site_packages: Option<String>,
+    pub tool_version: String,
+}
+
+/// Collect the environment fingerprint for a scan of `path` (or the auto-detected venv).
+/// With `relative_paths`, paths under the current directory are made relative so the
+/// fingerprint is reproducible across checkouts.
+pub fn collect_environment(path: Option<PathBuf>, relative_paths: bool) -> EnvironmentInfo {
+    let uv_lock = UvLockParser::find_uv_lock();
+    let requires_python = uv_lock
+        .as_ref()
+        .and_then(|lock| UvLockParser::parse_uv_lock(lock).ok())
+        .and_then(|lock| lock.requires_python);
+    let site_packages = match path {
+        Some(path) => Some(path),
+        None => find_site_packages_path(None).ok(),
+    };
+
+    let base = if relative_paths { std::env::current_dir().ok() } else { None };
+    let display = |path: &Path| display_path(path, base.as_deref());
+
+    EnvironmentInfo {
+        uv_lock: uv_lock.as_deref().map(display),
+        requires_python,
+        site_packages: site_packages.as_deref().map(display),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+/// Render `path` relative to `base` when it lies underneath it
+fn display_path(path: &Path, base: Option<&Path>) -> String {
+    match base {
+        Some(base) => path.strip_prefix(base).unwrap_or(path).display().to_string(),
+        None => path.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_path_relative_to_base() {
+        let base = Path::new("/work/project");
+        let site_packages = base.join(".venv").join("lib").join("site-packages");
+
+        assert_eq!(display_path(&site_packages, None), site_packages.display().to_string());
+        assert_eq!(
+            display_path(&site_packages, Some(base)),
+            Path::new(".venv").join("lib").join("site-packages").display().to_string()
+        );
+        // Paths outside the base stay absolute
+        assert_eq!(display_path(Path::new("/opt/venv"), Some(base)), "/opt/venv");
+    }
+
+    #[test]
+    fn test_collect_environment_records_tool_version() {
+        let environment = collect_environment(Some(PathBuf::from("/opt/site-packages")), false);
+        assert_eq!(environment.site_packages, Some("/opt/site-packages".to_string()));
+        assert_eq!(environment.tool_version, env!("CARGO_PKG_VERSION"));
+    }
+}
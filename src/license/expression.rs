@@ -0,0 +1,163 @@
+/// A parsed SPDX license expression (`MIT OR Apache-2.0`, `GPL-2.0-only WITH Classpath-exception-2.0`, ...)
+#[derive(Debug, Clone, PartialEq)]
+pub enum LicenseExpression {
+    License(String),
+    With(String, String),
+    And(Box<LicenseExpression>, Box<LicenseExpression>),
+    Or(Box<LicenseExpression>, Box<LicenseExpression>),
+}
+
+impl std::fmt::Display for LicenseExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LicenseExpression::License(id) => write!(f, "{}", id),
+            LicenseExpression::With(id, exception) => write!(f, "{} WITH {}", id, exception),
+            LicenseExpression::And(left, right) => write!(f, "{} AND {}", operand(left), operand(right)),
+            LicenseExpression::Or(left, right) => write!(f, "{} OR {}", left, right),
+        }
+    }
+}
+
+/// Parenthesize OR operands nested under AND
+fn operand(expression: &LicenseExpression) -> String {
+    match expression {
+        LicenseExpression::Or(..) => format!("({})", expression),
+        _ => expression.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String),
+    And,
+    Or,
+    With,
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let spaced = input.replace('(', " ( ").replace(')', " ) ");
+    spaced
+        .split_whitespace()
+        .map(|word| match word {
+            "AND" | "and" => Token::And,
+            "OR" | "or" => Token::Or,
+            "WITH" | "with" => Token::With,
+            "(" => Token::Open,
+            ")" => Token::Close,
+            id => Token::Id(id.to_string()),
+        })
+        .collect()
+}
+
+fn is_license_id(id: &str) -> bool {
+    id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '+' || c == ':')
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    // or := and ("OR" and)*
+    fn parse_or(&mut self) -> Option<LicenseExpression> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let right = self.parse_and()?;
+            left = LicenseExpression::Or(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    // and := with ("AND" with)*
+    fn parse_and(&mut self) -> Option<LicenseExpression> {
+        let mut left = self.parse_with()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            let right = self.parse_with()?;
+            left = LicenseExpression::And(Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    // with := id ("WITH" id)? | "(" or ")"
+    fn parse_with(&mut self) -> Option<LicenseExpression> {
+        match self.next()? {
+            Token::Open => {
+                let inner = self.parse_or()?;
+                (self.next()? == Token::Close).then_some(inner)
+            }
+            Token::Id(id) if is_license_id(&id) => {
+                if self.peek() == Some(&Token::With) {
+                    self.next();
+                    match self.next()? {
+                        Token::Id(exception) if is_license_id(&exception) => Some(LicenseExpression::With(id, exception)),
+                        _ => None,
+                    }
+                } else {
+                    Some(LicenseExpression::License(id))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse a compound SPDX expression. Returns None for single identifiers and for
+/// free-form text that is not a valid expression.
+pub fn parse_expression(input: &str) -> Option<LicenseExpression> {
+    let tokens = tokenize(input);
+    if !tokens.iter().any(|token| matches!(token, Token::And | Token::Or | Token::With)) {
+        return None;
+    }
+
+    let mut parser = Parser { tokens, pos: 0 };
+    let expression = parser.parse_or()?;
+    (parser.pos == parser.tokens.len()).then_some(expression)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(name: &str) -> Box<LicenseExpression> {
+        Box::new(LicenseExpression::License(name.to_string()))
+    }
+
+    #[test]
+    fn test_parse_expression_precedence() {
+        assert_eq!(
+            parse_expression("GPL-2.0-only OR MIT AND BSD-3-Clause"),
+            Some(LicenseExpression::Or(
+                id("GPL-2.0-only"),
+                Box::new(LicenseExpression::And(id("MIT"), id("BSD-3-Clause")))
+            ))
+        );
+        assert_eq!(
+            parse_expression("(MIT OR Apache-2.0) AND GPL-2.0-only WITH Classpath-exception-2.0").unwrap().to_string(),
+            "(MIT OR Apache-2.0) AND GPL-2.0-only WITH Classpath-exception-2.0"
+        );
+    }
+
+    #[test]
+    fn test_parse_expression_rejects_free_text() {
+        assert_eq!(parse_expression("MIT"), None);
+        assert_eq!(parse_expression("BSD License"), None);
+        assert_eq!(parse_expression("Dual licensed under GPL or MIT"), None);
+        assert_eq!(parse_expression("MIT OR"), None);
+        assert_eq!(parse_expression("(MIT OR Apache-2.0"), None);
+    }
+}
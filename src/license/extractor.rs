@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::Path;
-use super::{PackageLicense, normalize_license_name};
+use std::path::{Path, PathBuf};
+use super::text::{find_license_file, identify_license_text};
+use super::{DetectionStatus, PackageLicense, normalize_license_name};
+use crate::uv_lock::{normalize_package_name, normalize_version};
 
 fn compute_effective_license(raw_license: &Option<String>, classifiers: &[String]) -> Option<String> {
     // Prioritize classifiers (more standardized)
@@ -12,7 +14,8 @@ fn compute_effective_license(raw_license: &Option<String>, classifiers: &[String
     }
 
     // Only use raw license field if no classifiers found AND it's not a copyright statement
-    if let Some(license) = raw_license {
+    // or a pointer to a license file
+    if let Some(license) = raw_license.as_deref().filter(|license| license_file_pointer(license).is_none()) {
         if !license.starts_with("Copyright") && !license.starts_with("=") && license.len() >= 3 {
             return Some(normalize_license_name(license));
         }
@@ -21,12 +24,134 @@ fn compute_effective_license(raw_license: &Option<String>, classifiers: &[String
     None
 }
 
+/// Warn when license classifiers name none of the licenses in License-Expression
+fn expression_conflict(expression: &str, classifiers: &[String]) -> Option<String> {
+    let classifier_licenses: Vec<String> = classifiers
+        .iter()
+        .filter_map(|classifier| extract_license_from_classifier(classifier))
+        .map(|license| normalize_license_name(&license))
+        .collect();
+    if classifier_licenses.is_empty() {
+        return None;
+    }
+
+    let mut expression_licenses = Vec::new();
+    let mut after_with = false;
+    for token in expression.split(|c: char| c.is_whitespace() || c == '(' || c == ')').filter(|t| !t.is_empty()) {
+        match token {
+            "AND" | "OR" | "and" | "or" => {}
+            "WITH" | "with" => after_with = true,
+            _ if after_with => after_with = false,
+            id => expression_licenses.push(normalize_license_name(id)),
+        }
+    }
+
+    if classifier_licenses.iter().any(|license| expression_licenses.contains(license)) {
+        return None;
+    }
+    Some(format!(
+        "License-Expression '{}' disagrees with license classifiers ({}); using License-Expression",
+        expression,
+        classifier_licenses.join(", ")
+    ))
+}
+
+/// `License:` values that point at a file instead of naming a license ("See LICENSE",
+/// "file:LICENSE.txt", "LICENSE.txt"); returns the file name
+fn license_file_pointer(license: &str) -> Option<&str> {
+    let mut value = license.trim().trim_end_matches('.');
+    for prefix in ["file:", "see ", "the "] {
+        if value.get(..prefix.len()).is_some_and(|head| head.eq_ignore_ascii_case(prefix)) {
+            value = value[prefix.len()..].trim_start();
+        }
+    }
+    let suffix_start = value.len().saturating_sub(" file".len());
+    if value.get(suffix_start..).is_some_and(|tail| tail.eq_ignore_ascii_case(" file")) {
+        value = value[..suffix_start].trim_end();
+    }
+
+    let upper = value.to_uppercase();
+    // LicenseRef-* is an SPDX identifier, not a file
+    let names_license_file = ["LICENSE", "LICENCE", "COPYING"].iter().any(|name| upper.starts_with(name)) && !upper.starts_with("LICENSEREF-");
+    let plain_file_name = !value.contains(|c: char| c.is_whitespace() || c == '/' || c == '\\');
+    (names_license_file && plain_file_name).then_some(value)
+}
+
+/// Identify the license file a `License:` pointer refers to: the named file in the metadata
+/// directory or its PEP 639 `licenses/` folder, else any LICENSE*/COPYING* file there
+fn read_license_pointer(metadata_dir: &Path, file_name: &str) -> Option<(String, Option<f64>)> {
+    let licenses_dir = metadata_dir.join("licenses");
+    let path = [metadata_dir.join(file_name), licenses_dir.join(file_name)]
+        .into_iter()
+        .find(|path| path.is_file())
+        .or_else(|| find_license_file(metadata_dir).ok().flatten())
+        .or_else(|| find_license_file(&licenses_dir).ok().flatten())?;
+    identify_license_text(&String::from_utf8_lossy(&fs::read(path).ok()?))
+}
+
+/// Build a package from METADATA / PKG-INFO content
+pub(super) fn package_from_metadata(name: String, version: Option<String>, content: &str, metadata_source: &str) -> PackageLicense {
+    package_from_metadata_in(name, version, content, metadata_source, None)
+}
+
+/// `package_from_metadata` for an installed package: `License: See LICENSE` style
+/// values are resolved from the license files in `metadata_dir`
+fn package_from_metadata_in(name: String, version: Option<String>, content: &str, metadata_source: &str, metadata_dir: Option<&Path>) -> PackageLicense {
+    let metadata = parse_metadata_content(content);
+
+    let (mut effective_license, mut warnings) = match &metadata.expression {
+        // License-Expression is authoritative in Metadata 2.4
+        Some(expression) => (
+            Some(normalize_license_name(expression)),
+            expression_conflict(expression, &metadata.classifiers).into_iter().collect(),
+        ),
+        None => (compute_effective_license(&metadata.license, &metadata.classifiers), vec![]),
+    };
+    let mut license_confidence = None;
+    let pointer = metadata.license.as_deref().and_then(|license| license_file_pointer(license).map(|file| (license, file)));
+    if let (None, Some((license, file_name))) = (&effective_license, pointer) {
+        match metadata_dir.and_then(|dir| read_license_pointer(dir, file_name)) {
+            Some((identified, confidence)) => {
+                effective_license = Some(identified);
+                license_confidence = confidence;
+            }
+            None => warnings.push(format!("License field '{}' refers to a license file that was not found or not recognized", license)),
+        }
+    }
+    let detection_status = if effective_license.is_some() || !metadata.classifiers.is_empty() {
+        DetectionStatus::Found
+    } else {
+        DetectionStatus::NoLicenseField
+    };
+
+    PackageLicense {
+        name,
+        version,
+        effective_license,
+        license_classifiers: metadata.classifiers,
+        metadata_source: metadata_source.to_string(),
+        license_expression: metadata.expression,
+        warnings,
+        annotation: None,
+        detection_status,
+        raw_license: metadata.raw_license,
+        metadata_name: metadata.name,
+        license_confidence,
+        license_url: metadata.license_url,
+    }
+}
+
 fn extract_license_from_classifier(classifier: &str) -> Option<String> {
     // Extract license name from classifier like "License :: OSI Approved :: MIT License"
     if classifier.starts_with("License :: ") {
         let parts: Vec<&str> = classifier.split(" :: ").collect();
+        // Deeper classifiers nest further; the last segment is the most specific
         if parts.len() >= 3 {
-            return Some(parts[2].to_string());
+            return parts.last().map(|name| name.to_string());
+        }
+        // Two-level classifiers name the license directly ("License :: Other/Proprietary License")
+        if parts.len() == 2 && parts[1] != "OSI Approved" {
+            return Some(parts[1].to_string());
         }
     }
     None
@@ -62,28 +187,26 @@ pub fn extract_all_licenses(site_packages_path: &Path, include_unknown: bool) ->
 
 /// Extract license information for a specific package by name
 pub fn extract_license_for_package(site_packages_path: &Path, package_name: &str) -> Result<PackageLicense> {
-    // Try .dist-info first (modern format)
-    for entry in fs::read_dir(site_packages_path)? {
-        let entry = entry?;
-        let file_name = entry.file_name();
-        let name_str = file_name.to_string_lossy();
-        
-        if name_str.starts_with(&package_name.replace("-", "_")) && name_str.ends_with(".dist-info") {
-            if let Some(package) = extract_from_dist_info(&entry.path())? {
-                return Ok(package);
-            }
-        }
-    }
+    locate_package(site_packages_path, package_name).map(|(_, package)| package)
+}
 
-    // Try .egg-info (legacy format)
-    for entry in fs::read_dir(site_packages_path)? {
-        let entry = entry?;
-        let file_name = entry.file_name();
-        let name_str = file_name.to_string_lossy();
-        
-        if name_str.starts_with(&package_name.replace("-", "_")) && name_str.ends_with(".egg-info") {
-            if let Some(package) = extract_from_egg_info(&entry.path())? {
-                return Ok(package);
+/// Find a package's `.dist-info` (or legacy `.egg-info`) entry and extract its license
+pub fn locate_package(site_packages_path: &Path, package_name: &str) -> Result<(PathBuf, PackageLicense)> {
+    let prefix = package_name.replace("-", "_");
+
+    // Try .dist-info first (modern format), then .egg-info (legacy format)
+    for suffix in [".dist-info", ".egg-info"] {
+        for entry in fs::read_dir(site_packages_path)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let name_str = file_name.to_string_lossy();
+
+            if name_str.starts_with(&prefix) && name_str.ends_with(suffix) {
+                let path = entry.path();
+                let package = if suffix == ".dist-info" { extract_from_dist_info(&path)? } else { extract_from_egg_info(&path)? };
+                if let Some(package) = package {
+                    return Ok((path, package));
+                }
             }
         }
     }
@@ -91,55 +214,158 @@ pub fn extract_license_for_package(site_packages_path: &Path, package_name: &str
     anyhow::bail!("Package '{}' not found in site-packages", package_name)
 }
 
+/// The METADATA / PKG-INFO file of a `.dist-info` or `.egg-info` entry, if present
+pub fn metadata_file(metadata_dir: &Path) -> Option<PathBuf> {
+    if metadata_dir.extension().is_some_and(|ext| ext == "egg-info") {
+        return find_pkg_info(metadata_dir);
+    }
+    Some(metadata_dir.join("METADATA")).filter(|path| path.is_file())
+}
+
+/// Placeholder for an installed package whose metadata file is missing
+fn metadata_missing(name: String, version: Option<String>, metadata_source: &str) -> PackageLicense {
+    PackageLicense {
+        name,
+        version,
+        effective_license: None,
+        license_classifiers: vec![],
+        metadata_source: metadata_source.to_string(),
+        license_expression: None,
+        warnings: vec![],
+        annotation: None,
+        detection_status: DetectionStatus::MetadataMissing,
+        raw_license: None,
+        metadata_name: None,
+        license_confidence: None,
+        license_url: None,
+    }
+}
+
+/// Render the `license` and `classifiers` keys of a legacy (Metadata 2.0 era) `metadata.json`
+/// as METADATA headers, so it goes through the same parsing as METADATA
+fn metadata_json_headers(content: &str) -> Result<String> {
+    let document: serde_json::Value = serde_json::from_str(content)?;
+    let mut headers = String::new();
+    if let Some(name) = document.get("name").and_then(|name| name.as_str()) {
+        headers.push_str(&format!("Name: {}\n", name));
+    }
+    if let Some(license) = document.get("license").and_then(|license| license.as_str()) {
+        headers.push_str(&format!("License: {}\n", license.lines().next().unwrap_or_default()));
+    }
+    let classifiers = document.get("classifiers").and_then(|classifiers| classifiers.as_array());
+    for classifier in classifiers.into_iter().flatten().filter_map(|classifier| classifier.as_str()) {
+        headers.push_str(&format!("Classifier: {}\n", classifier));
+    }
+    Ok(headers)
+}
+
 fn extract_from_dist_info(dist_info_path: &Path) -> Result<Option<PackageLicense>> {
     let metadata_path = dist_info_path.join("METADATA");
     if !metadata_path.exists() {
-        return Ok(None);
+        if !dist_info_path.is_dir() {
+            return Ok(None);
+        }
+        let (name, version) = parse_name_version_from_dist_info(dist_info_path)?;
+        let json_path = dist_info_path.join("metadata.json");
+        if json_path.exists() {
+            let headers = fs::read_to_string(&json_path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| metadata_json_headers(&content))
+                .with_context(|| format!("Failed to read {}", json_path.display()))?;
+            return Ok(Some(package_from_metadata_in(name, version, &headers, "metadata.json", Some(dist_info_path))));
+        }
+        return Ok(Some(metadata_missing(name, version, "METADATA")));
     }
 
     let content = fs::read_to_string(&metadata_path)
         .with_context(|| format!("Failed to read {}", metadata_path.display()))?;
 
     let (name, version) = parse_name_version_from_dist_info(dist_info_path)?;
-    let (raw_license, classifiers) = parse_metadata_content(&content);
-    
-    // Compute effective license from raw data
-    let effective_license = compute_effective_license(&raw_license, &classifiers);
+    let mismatch = dist_info_mismatch(&name, version.as_deref(), &content);
+    let mut package = package_from_metadata_in(name, version, &content, "METADATA", Some(dist_info_path));
+    package.warnings.extend(mismatch);
+    Ok(Some(package))
+}
+
+/// Warn when the dist-info directory name disagrees with METADATA's `Name:` /
+/// `Version:` (a renamed directory, a corrupted or hand-edited install)
+fn dist_info_mismatch(name: &str, version: Option<&str>, content: &str) -> Option<String> {
+    let mut metadata_name = None;
+    let mut metadata_version = None;
+    // Headers end at the first blank line; the description body follows
+    for line in content.lines().take_while(|line| !line.trim().is_empty()) {
+        if let Some(value) = line.strip_prefix("Name: ") {
+            metadata_name = Some(value.trim());
+        } else if let Some(value) = line.strip_prefix("Version: ") {
+            metadata_version = Some(value.trim());
+        }
+    }
 
-    Ok(Some(PackageLicense {
+    let mut mismatches = Vec::new();
+    if let Some(metadata_name) = metadata_name.filter(|n| normalize_package_name(n) != normalize_package_name(name)) {
+        mismatches.push(format!("name '{}'", metadata_name));
+    }
+    if let (Some(metadata_version), Some(version)) = (metadata_version, version) {
+        if normalize_version(metadata_version) != normalize_version(version) {
+            mismatches.push(format!("version '{}'", metadata_version));
+        }
+    }
+    if mismatches.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "dist-info directory says {} {}, but METADATA has {}; the install may be renamed or tampered with",
         name,
-        version,
-        effective_license,
-        license_classifiers: classifiers,
-        metadata_source: "METADATA".to_string(),
-    }))
+        version.unwrap_or("(no version)"),
+        mismatches.join(" and ")
+    ))
 }
 
-fn extract_from_egg_info(egg_info_path: &Path) -> Result<Option<PackageLicense>> {
-    let pkg_info_path = egg_info_path.join("PKG-INFO");
-    if !pkg_info_path.exists() {
-        return Ok(None);
+/// Locate the PKG-INFO of an `.egg-info` entry: the entry itself when it is a
+/// legacy single file, `PKG-INFO` inside it, or a conventional nested location
+/// (`EGG-INFO/`, `src/*.egg-info/`, `*.egg-info/`) as found in unpacked sdists.
+fn find_pkg_info(egg_info_path: &Path) -> Option<PathBuf> {
+    if egg_info_path.is_file() {
+        return Some(egg_info_path.to_path_buf());
+    }
+
+    let direct = [egg_info_path.join("PKG-INFO"), egg_info_path.join("EGG-INFO").join("PKG-INFO")];
+    if let Some(path) = direct.into_iter().find(|path| path.is_file()) {
+        return Some(path);
+    }
+
+    for dir in [egg_info_path.join("src"), egg_info_path.to_path_buf()] {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        let mut nested: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "egg-info"))
+            .map(|path| path.join("PKG-INFO"))
+            .filter(|path| path.is_file())
+            .collect();
+        nested.sort();
+        if let Some(path) = nested.into_iter().next() {
+            return Some(path);
+        }
     }
 
+    None
+}
+
+fn extract_from_egg_info(egg_info_path: &Path) -> Result<Option<PackageLicense>> {
+    let Some(pkg_info_path) = find_pkg_info(egg_info_path) else {
+        let (name, version) = parse_name_version_from_egg_info(egg_info_path)?;
+        return Ok(Some(metadata_missing(name, version, "PKG-INFO")));
+    };
+
     let content = fs::read_to_string(&pkg_info_path)
         .with_context(|| format!("Failed to read {}", pkg_info_path.display()))?;
 
     let (name, version) = parse_name_version_from_egg_info(egg_info_path)?;
-    let (raw_license, classifiers) = parse_metadata_content(&content);
-    
-    // Compute effective license from raw data
-    let effective_license = compute_effective_license(&raw_license, &classifiers);
-
-    Ok(Some(PackageLicense {
-        name,
-        version,
-        effective_license,
-        license_classifiers: classifiers,
-        metadata_source: "PKG-INFO".to_string(),
-    }))
+    let egg_info_dir = Some(egg_info_path).filter(|path| path.is_dir());
+    Ok(Some(package_from_metadata_in(name, version, &content, "PKG-INFO", egg_info_dir)))
 }
 
-fn parse_name_version_from_dist_info(dist_info_path: &Path) -> Result<(String, Option<String>)> {
+pub(super) fn parse_name_version_from_dist_info(dist_info_path: &Path) -> Result<(String, Option<String>)> {
     let file_name = dist_info_path
         .file_name()
         .and_then(|n| n.to_str())
@@ -148,8 +374,10 @@ fn parse_name_version_from_dist_info(dist_info_path: &Path) -> Result<(String, O
     let name_version = file_name.strip_suffix(".dist-info")
         .ok_or_else(|| anyhow::anyhow!("Invalid dist-info directory name"))?;
 
-    // Split by the last occurrence of '-' to separate name and version
-    if let Some(last_dash) = name_version.rfind('-') {
+    // Split by the last occurrence of '-' to separate name and version; a PEP 440 local
+    // segment (`torch-2.1.0+cu-118`) belongs to the version even if it contains dashes
+    let public = name_version.split_once('+').map_or(name_version, |(public, _)| public);
+    if let Some(last_dash) = public.rfind('-') {
         let name = name_version[..last_dash].to_string();
         let version = name_version[last_dash + 1..].to_string();
         Ok((name, Some(version)))
@@ -177,19 +405,48 @@ fn parse_name_version_from_egg_info(egg_info_path: &Path) -> Result<(String, Opt
     }
 }
 
-fn parse_metadata_content(content: &str) -> (Option<String>, Vec<String>) {
+/// License-related fields of a METADATA / PKG-INFO file
+struct MetadataLicense {
+    license: Option<String>,
+    expression: Option<String>,
+    classifiers: Vec<String>,
+    /// The `License:` value as written, including placeholders such as "UNKNOWN"
+    raw_license: Option<String>,
+    /// The `Name:` field (canonical PyPI spelling)
+    name: Option<String>,
+    /// `Project-URL: License, <url>`
+    license_url: Option<String>,
+}
+
+fn parse_metadata_content(content: &str) -> MetadataLicense {
     let mut license = None;
+    let mut expression = None;
     let mut classifiers = Vec::new();
+    let mut raw_license = None;
+    let mut name = None;
+    let mut license_url = None;
+    let present = |value: &str| !value.trim().is_empty() && value.trim() != "UNKNOWN";
 
     for line in content.lines() {
-        if let Some(value) = line.strip_prefix("License: ") {
-            if !value.trim().is_empty() && value.trim() != "UNKNOWN" {
+        if let Some(value) = line.strip_prefix("Name: ") {
+            name.get_or_insert_with(|| value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("License: ") {
+            raw_license.get_or_insert_with(|| value.to_string());
+            if present(value) {
                 license = Some(value.trim().to_string());
             }
         } else if let Some(value) = line.strip_prefix("License-Expression: ") {
             // Modern PEP 639 License-Expression field (preferred over License field)
-            if !value.trim().is_empty() && value.trim() != "UNKNOWN" {
-                license = Some(value.trim().to_string());
+            if present(value) {
+                expression = Some(value.trim().to_string());
+            }
+        } else if let Some(value) = line.strip_prefix("Project-URL: ") {
+            // `Project-URL: <label>, <url>`; labels are free-form, so match "License"/"Licence" loosely
+            if let Some((label, url)) = value.split_once(',') {
+                let label = label.trim().to_lowercase();
+                if label == "license" || label == "licence" {
+                    license_url.get_or_insert_with(|| url.trim().to_string());
+                }
             }
         } else if let Some(value) = line.strip_prefix("Classifier: ") {
             if value.contains("License") {
@@ -198,5 +455,5 @@ fn parse_metadata_content(content: &str) -> (Option<String>, Vec<String>) {
         }
     }
 
-    (license, classifiers)
+    MetadataLicense { license, expression, classifiers, raw_license, name, license_url }
 }
@@ -0,0 +1,136 @@
+use flate2::read::GzDecoder;
+use std::collections::HashSet;
+use std::io::Read;
+use std::sync::OnceLock;
+
+/// Minimum similarity for a license text to be classified as a bundled license
+pub const MATCH_THRESHOLD: f64 = 0.8;
+
+macro_rules! reference_text {
+    ($id:literal) => {
+        ($id, include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/licenses/", $id, ".txt.gz")).as_slice())
+    };
+}
+
+/// Canonical texts of the most common licenses (gzip-compressed, in `assets/licenses`)
+const REFERENCE_TEXTS: &[(&str, &[u8])] = &[
+    reference_text!("MIT"),
+    reference_text!("Apache-2.0"),
+    reference_text!("BSD-2-Clause"),
+    reference_text!("BSD-3-Clause"),
+    reference_text!("ISC"),
+    reference_text!("0BSD"),
+    reference_text!("PSF-2.0"),
+    reference_text!("GPL-2.0"),
+    reference_text!("GPL-3.0"),
+    reference_text!("LGPL-2.0"),
+    reference_text!("LGPL-2.1"),
+    reference_text!("LGPL-3.0"),
+    reference_text!("MPL-1.1"),
+    reference_text!("MPL-2.0"),
+    reference_text!("CC0-1.0"),
+    reference_text!("Unlicense"),
+    reference_text!("BSL-1.0"),
+    reference_text!("Zlib"),
+    reference_text!("WTFPL"),
+];
+
+/// Longest line treated as a copyright notice (and left out of the comparison)
+const COPYRIGHT_LINE_WORDS: usize = 20;
+
+/// Closest bundled license and its similarity (0–1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LicenseMatch {
+    pub license: String,
+    pub confidence: f64,
+}
+
+/// Word bigrams of a text, ignoring case, punctuation, line wrapping and copyright lines
+fn fingerprint(text: &str) -> HashSet<String> {
+    let words: Vec<String> = text
+        .lines()
+        .filter(|line| {
+            // Copyright notices differ per project; long lines are license text that merely starts with one
+            let line = line.trim_start_matches(|c: char| !c.is_alphanumeric() && c != '(').to_lowercase();
+            let notice = line.starts_with("copyright") || line.starts_with("(c)");
+            !notice || line.split_whitespace().count() > COPYRIGHT_LINE_WORDS
+        })
+        .flat_map(|line| line.split(|c: char| !c.is_alphanumeric()).map(str::to_lowercase).collect::<Vec<_>>())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    words.windows(2).map(|pair| format!("{} {}", pair[0], pair[1])).collect()
+}
+
+/// Sørensen–Dice coefficient of two fingerprints
+fn dice(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    2.0 * a.intersection(b).count() as f64 / (a.len() + b.len()) as f64
+}
+
+/// Bundled texts, decompressed and fingerprinted on first use
+fn references() -> &'static [(&'static str, HashSet<String>)] {
+    static REFERENCES: OnceLock<Vec<(&'static str, HashSet<String>)>> = OnceLock::new();
+    REFERENCES.get_or_init(|| {
+        REFERENCE_TEXTS
+            .iter()
+            .map(|(id, compressed)| (*id, fingerprint(&decompress(compressed))))
+            .collect()
+    })
+}
+
+fn decompress(compressed: &[u8]) -> String {
+    let mut text = String::new();
+    GzDecoder::new(compressed)
+        .read_to_string(&mut text)
+        .expect("bundled license texts are valid gzip-compressed UTF-8");
+    text
+}
+
+/// The bundled license most similar to `text`, if it scores at least `MATCH_THRESHOLD`
+pub fn match_license_text(text: &str) -> Option<LicenseMatch> {
+    let fingerprint = fingerprint(text);
+    references()
+        .iter()
+        .map(|(id, reference)| (id, dice(&fingerprint, reference)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+        .map(|(id, score)| LicenseMatch {
+            license: id.to_string(),
+            confidence: (score * 1000.0).round() / 1000.0,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(id: &str) -> String {
+        let (_, compressed) = REFERENCE_TEXTS.iter().find(|(known, _)| *known == id).unwrap();
+        decompress(compressed)
+    }
+
+    #[test]
+    fn test_match_license_text() {
+        // Every bundled text matches itself
+        for (id, _) in REFERENCE_TEXTS {
+            assert_eq!(match_license_text(&reference(id)).map(|m| m.license).as_deref(), Some(*id));
+        }
+
+        // Re-wrapped, with a real copyright line and a project header
+        let body = reference("MIT").lines().skip(3).collect::<Vec<_>>().join(" ");
+        let mit = format!("The example project\nCopyright (c) 2024 Example Corp.\n\n{}", body);
+        let matched = match_license_text(&mit).unwrap();
+        assert_eq!(matched.license, "MIT");
+        assert!(matched.confidence > 0.95);
+
+        // Near neighbours stay apart
+        let bsd2 = reference("BSD-2-Clause").replace("<year>, <copyright holder>", "2021, Jane Doe");
+        assert_eq!(match_license_text(&bsd2).unwrap().license, "BSD-2-Clause");
+        assert_eq!(match_license_text(&reference("ISC")).unwrap().license, "ISC");
+
+        assert_eq!(match_license_text("Proprietary. All rights reserved."), None);
+    }
+}
@@ -1,13 +1,23 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use anyhow::{Context, Result};
 use std::fs;
-use std::path::PathBuf;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 use rayon::prelude::*;
 use indexmap::IndexMap;
-use crate::uv_lock::UvLockParser;
+use sha2::{Digest, Sha256};
+use crate::uv_lock::{normalize_version, UvLockParser};
 
+pub mod conda;
 pub mod extractor;
+pub mod environment;
+pub mod expression;
+pub mod fingerprint;
+pub mod project_url;
+pub mod text;
+pub mod wheel;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct LicenseInfo {
@@ -25,14 +35,59 @@ pub enum LicenseSource {
 
 // Re-export from extractor
 pub use extractor::extract_all_licenses;
+pub use environment::{collect_environment, EnvironmentInfo};
+pub use expression::{parse_expression, LicenseExpression};
+pub use text::{classify_license_text, identify_license_text, extract_licenses_from_dir};
+pub use conda::{detect_conda_prefix, extract_licenses_from_conda};
+pub use project_url::resolve_license_urls;
+pub use wheel::{extract_from_wheel, extract_licenses_from_wheels, extract_licenses_from_zip};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PackageLicense {
     pub name: String,
     pub version: Option<String>,
     pub effective_license: Option<String>,
     pub license_classifiers: Vec<String>,
     pub metadata_source: String,
+    /// PEP 639 `License-Expression`, authoritative over classifiers when present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_expression: Option<String>,
+    /// Metadata problems noticed during extraction (e.g. classifiers disagreeing with License-Expression)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Owner / purpose note from `[[tool.py-license-auditor.annotations]]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+    /// Why the license is (or is not) known, for triaging unknown packages
+    #[serde(default)]
+    pub detection_status: DetectionStatus,
+    /// Verbatim `License:` metadata value, kept only with `--include-raw-metadata`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_license: Option<String>,
+    /// `Name:` from METADATA (the canonical PyPI spelling), for `--display-name pypi`
+    #[serde(skip)]
+    pub metadata_name: Option<String>,
+    /// Similarity (0–1) to the bundled license text a license file was matched against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_confidence: Option<f64>,
+    /// `Project-URL: License, <url>` from METADATA, fetched by `--resolve-license-urls`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_url: Option<String>,
+}
+
+/// How a package's license information was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionStatus {
+    /// License metadata was found
+    #[default]
+    Found,
+    /// Locked in uv.lock but absent from site-packages (run `uv sync`)
+    NotInstalled,
+    /// Installed, but the dist-info / egg-info has no METADATA / PKG-INFO
+    MetadataMissing,
+    /// Metadata exists but declares no usable license (an upstream packaging issue)
+    NoLicenseField,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -41,6 +96,18 @@ pub struct LicenseTypes {
     pub non_osi: IndexMap<String, usize>,
 }
 
+impl LicenseTypes {
+    /// Reorder both maps alphabetically by license name instead of by count
+    /// (`--sort-keys`), so committed JSON reports diff only where values change
+    pub fn sort_keys(&mut self) {
+        let sorted = |map: &mut IndexMap<String, usize>| {
+            *map = std::mem::take(map).into_iter().collect::<BTreeMap<_, _>>().into_iter().collect();
+        };
+        sorted(&mut self.osi_approved);
+        sorted(&mut self.non_osi);
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct LicenseSummary {
     pub total_packages: usize,
@@ -51,55 +118,322 @@ pub struct LicenseSummary {
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct LicenseReport {
+    /// Shape of the JSON report; bumped whenever fields change incompatibly
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: String,
+    /// The tool that generated the report
+    #[serde(default)]
+    pub tool: ToolInfo,
+    /// RFC 3339 generation time (omitted under --deterministic)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<String>,
     pub packages: Vec<PackageLicense>,
     pub summary: LicenseSummary,
     /// 違反情報（ポリシーチェックが有効な場合のみ）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub violations: Option<crate::policy::ViolationSummary>,
+    /// 例外で抑制された違反の内訳（ポリシーチェックが有効な場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exception_coverage: Option<crate::policy::ExceptionCoverage>,
+    /// Scanned environment (only with --include-environment)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<EnvironmentInfo>,
+    /// Package list filter (with --only); the summary still covers every package
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<OsiFilter>,
+    /// Changes relative to a git ref (only with --diff-base)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<crate::diff_base::LicenseDiff>,
+    /// SHA-256 of the package inventory (only with --include-hash)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// Package names per license, in `license_types` order (only with --include-license-index)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packages_by_license: Option<IndexMap<String, Vec<String>>>,
+    /// Help text and policy link from `[tool.py-license-auditor.report]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<ReportFooter>,
+}
+
+/// `[tool.py-license-auditor.report]`: where report viewers can get help
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportFooter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_url: Option<String>,
+}
+
+impl ReportFooter {
+    pub fn is_empty(&self) -> bool {
+        let blank = |value: &Option<String>| value.as_deref().is_none_or(|value| value.trim().is_empty());
+        blank(&self.footer_text) && blank(&self.policy_url)
+    }
+}
+
+/// Current `schema_version` of JSON reports
+pub const REPORT_SCHEMA_VERSION: &str = "1";
+
+/// Reports written before `schema_version` existed have the version 1 shape
+fn legacy_schema_version() -> String {
+    "1".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub version: String,
+}
+
+impl ToolInfo {
+    pub fn current() -> Self {
+        ToolInfo { name: env!("CARGO_PKG_NAME").to_string(), version: env!("CARGO_PKG_VERSION").to_string() }
+    }
+}
+
+/// Restrict the package list to OSI-approved or non-OSI licenses
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OsiFilter {
+    Osi,
+    NonOsi,
+}
+
+impl OsiFilter {
+    /// Uses the same OSI determination as the summary's `license_types`, overrides included
+    pub fn matches(&self, package: &PackageLicense, osi_overrides: &OsiOverrides) -> bool {
+        let is_osi = get_license_info(package, osi_overrides).iter().any(|(_, is_osi)| *is_osi);
+        match self {
+            OsiFilter::Osi => is_osi,
+            OsiFilter::NonOsi => !is_osi,
+        }
+    }
+}
+
+/// Which spelling of a package name appears in reports (`--display-name`)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DisplayName {
+    /// As derived from the `.dist-info` directory (e.g. `typing_extensions`)
+    #[default]
+    DistInfo,
+    /// `Name:` from METADATA, the canonical PyPI spelling (e.g. `typing-extensions`)
+    Pypi,
+    /// PEP 503 normalized (lowercase, `-` separators)
+    Normalized,
+}
+
+impl DisplayName {
+    /// Rename packages for display; policy matching is normalization-based, so it is unaffected
+    pub fn apply(self, packages: &mut [PackageLicense]) {
+        for package in packages {
+            match self {
+                DisplayName::DistInfo => {}
+                DisplayName::Pypi => {
+                    if let Some(name) = package.metadata_name.clone() {
+                        package.name = name;
+                    }
+                }
+                DisplayName::Normalized => package.name = crate::uv_lock::normalize_package_name(&package.name),
+            }
+        }
+    }
+}
+
+impl LicenseReport {
+    /// Keep only packages matching `filter`, leaving the summary untouched
+    pub fn apply_filter(&mut self, filter: OsiFilter, osi_overrides: &OsiOverrides) {
+        self.packages.retain(|package| filter.matches(package, osi_overrides));
+        self.filter = Some(filter);
+    }
+
+    /// Package names grouped by the license they are counted under in the summary,
+    /// licenses in `license_types` order and names sorted within each license
+    pub fn license_index(&self) -> IndexMap<String, Vec<String>> {
+        let mut names: HashMap<String, Vec<String>> = HashMap::new();
+        for package in self.sorted_packages() {
+            for (license, _) in get_license_info(package, &OsiOverrides::default()) {
+                names.entry(license).or_default().push(package.name.clone());
+            }
+        }
+        let types = &self.summary.license_types;
+        types.osi_approved.keys()
+            .chain(types.non_osi.keys())
+            .filter_map(|license| names.remove_entry(license))
+            .collect()
+    }
+
+    /// `sha256:<hex>` over the packages' normalized name, version and license, sorted by
+    /// name and version, so equal inventories hash equally regardless of scan order
+    pub fn content_hash(&self) -> String {
+        let mut inventory: Vec<(String, &str, &str)> = self
+            .packages
+            .iter()
+            .map(|package| (
+                crate::uv_lock::normalize_package_name(&package.name),
+                package.version.as_deref().unwrap_or(""),
+                package.effective_license.as_deref().unwrap_or(""),
+            ))
+            .collect();
+        inventory.sort();
+        let canonical = serde_json::to_vec(&inventory).expect("inventory serializes");
+        let digest = Sha256::digest(&canonical);
+        format!("sha256:{}", digest.iter().map(|byte| format!("{:02x}", byte)).collect::<String>())
+    }
+
+    /// Packages by normalized name, then version: a row order independent of scan
+    /// (and parallel completion) order, for diff-stable CSV and JSON-lines output
+    pub fn sorted_packages(&self) -> Vec<&PackageLicense> {
+        let mut packages: Vec<&PackageLicense> = self.packages.iter().collect();
+        packages.sort_by_cached_key(|package| {
+            (crate::uv_lock::normalize_package_name(&package.name), package.version.clone(), package.name.clone())
+        });
+        packages
+    }
+
+    /// Read a report saved with `check --format json`, rejecting other JSON documents
+    pub fn load(path: &Path) -> Result<LicenseReport> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read report: {}", path.display()))?;
+        // Check the version before the shape, so newer reports fail with a clear message
+        let schema_version = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|document| document.get("schema_version")?.as_str().map(str::to_string));
+        if let Some(version) = schema_version.filter(|version| version != REPORT_SCHEMA_VERSION) {
+            anyhow::bail!(
+                "{} has report schema_version {}, but this version of {} reads schema_version {}",
+                path.display(), version, env!("CARGO_PKG_NAME"), REPORT_SCHEMA_VERSION
+            );
+        }
+        serde_json::from_str(&content).with_context(|| {
+            format!("{} is not a py-license-auditor JSON report (expected output of 'check --format json')", path.display())
+        })
+    }
 }
 
 pub fn find_site_packages_path(path: Option<PathBuf>) -> Result<PathBuf> {
+    find_site_packages_path_for(path, None)
+}
+
+/// Locate site-packages from the directory layout alone (`lib/pythonX.Y/site-packages`
+/// or `Lib/site-packages`), so venvs whose interpreter symlinks broke when copied
+/// between Docker stages still resolve. `python_version` ("3.12") picks among
+/// several `lib/pythonX.Y` directories.
+///
+/// The result is canonicalized, so later reads and report paths do not depend on the cwd.
+pub fn find_site_packages_path_for(path: Option<PathBuf>, python_version: Option<&str>) -> Result<PathBuf> {
+    let site_packages = resolve_site_packages(path, python_version)?;
+    fs::canonicalize(&site_packages).map_err(|error| match error.kind() {
+        std::io::ErrorKind::NotFound => anyhow::anyhow!("Path '{}' does not exist", site_packages.display()),
+        _ => anyhow::anyhow!("Cannot resolve path '{}': {}", site_packages.display(), error),
+    })
+}
+
+fn resolve_site_packages(path: Option<PathBuf>, python_version: Option<&str>) -> Result<PathBuf> {
     if let Some(path) = path {
         if path.join("site-packages").exists() {
             return Ok(path.join("site-packages"));
         }
-        if path.file_name().map_or(false, |name| name == "site-packages") {
+        if path.file_name().is_some_and(|name| name == "site-packages") {
             return Ok(path);
         }
+        // A virtual environment root
+        if path.join("lib").is_dir() || path.join("Lib").is_dir() {
+            if let Some(site_packages) = venv_site_packages(&path, python_version)? {
+                return Ok(site_packages);
+            }
+        }
         return Ok(path);
     }
 
     // Try to find .venv in current directory
-    let current_dir = std::env::current_dir()?;
-    let venv_path = current_dir.join(".venv");
-    
-    if venv_path.exists() {
-        // Unix-like systems
-        let lib_path = venv_path.join("lib");
-        if lib_path.exists() {
-            for entry in fs::read_dir(&lib_path)? {
-                let entry = entry?;
-                if entry.file_name().to_string_lossy().starts_with("python") {
-                    let site_packages = entry.path().join("site-packages");
-                    if site_packages.exists() {
-                        return Ok(site_packages);
-                    }
-                }
-            }
-        }
-        
-        // Windows
-        let lib_path = venv_path.join("Lib").join("site-packages");
-        if lib_path.exists() {
-            return Ok(lib_path);
-        }
+    let venv_path = std::env::current_dir()?.join(".venv");
+    if let Some(site_packages) = venv_site_packages(&venv_path, python_version)? {
+        return Ok(site_packages);
     }
 
     anyhow::bail!("Could not find site-packages directory. Please specify with --path")
 }
 
-/// Extract licenses from uv.lock file and corresponding site-packages
-pub fn extract_licenses_from_uv_lock(uv_lock_path: Option<PathBuf>, site_packages_path: Option<PathBuf>, include_unknown: bool) -> Result<Vec<PackageLicense>> {
+/// site-packages of a venv, or None when the layout has none
+fn venv_site_packages(venv_path: &Path, python_version: Option<&str>) -> Result<Option<PathBuf>> {
+    // Unix-like systems
+    let mut candidates: Vec<PathBuf> = match fs::read_dir(venv_path.join("lib")) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|dir| dir.file_name().is_some_and(|name| name.to_string_lossy().starts_with("python")))
+            .map(|dir| dir.join("site-packages"))
+            .filter(|site_packages| site_packages.is_dir())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    candidates.sort();
+
+    if let Some(version) = python_version {
+        let wanted = format!("python{}", version);
+        return match candidates.iter().find(|site_packages| {
+            site_packages.parent().and_then(|dir| dir.file_name()).is_some_and(|name| name.to_string_lossy() == wanted)
+        }) {
+            Some(site_packages) => Ok(Some(site_packages.clone())),
+            None => anyhow::bail!(
+                "No lib/{}/site-packages in {} (found: {})",
+                wanted, venv_path.display(), describe_candidates(&candidates)
+            ),
+        };
+    }
+
+    match candidates.len() {
+        0 => {}
+        1 => return Ok(candidates.pop()),
+        _ => anyhow::bail!(
+            "Several Python versions in {} ({}); choose one with --python-version",
+            venv_path.display(), describe_candidates(&candidates)
+        ),
+    }
+
+    // Windows
+    let lib_path = venv_path.join("Lib").join("site-packages");
+    Ok(lib_path.is_dir().then_some(lib_path))
+}
+
+/// Explain why a scan of `site_packages` found nothing: an empty environment
+/// or a directory that is not site-packages at all. `None` when the directory
+/// does hold package metadata (everything was filtered out, e.g. by --include-unknown).
+pub fn empty_environment_hint(site_packages: &Path) -> Option<String> {
+    let has_metadata = fs::read_dir(site_packages).ok()?.flatten().any(|entry| {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        name.ends_with(".dist-info") || name.ends_with(".egg-info")
+    });
+    if has_metadata {
+        return None;
+    }
+
+    if site_packages.file_name().is_some_and(|name| name == "site-packages") {
+        Some(format!(
+            "{} has no installed packages: the environment looks empty (run 'uv sync')",
+            site_packages.display()
+        ))
+    } else {
+        Some(format!(
+            "{} has no .dist-info or .egg-info entries: is this the right path? Pass a venv or its lib/pythonX.Y/site-packages",
+            site_packages.display()
+        ))
+    }
+}
+
+fn describe_candidates(candidates: &[PathBuf]) -> String {
+    if candidates.is_empty() {
+        return "none".to_string();
+    }
+    candidates
+        .iter()
+        .filter_map(|site_packages| site_packages.parent()?.file_name().map(|name| name.to_string_lossy().to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Resolve the uv.lock packages and site-packages directory to scan
+fn resolve_uv_lock_scan(uv_lock_path: Option<PathBuf>, site_packages_path: Option<PathBuf>, include_self: bool) -> Result<(Vec<(String, String)>, PathBuf)> {
     // Find uv.lock file
     let lock_path = match uv_lock_path {
         Some(path) => path,
@@ -109,123 +443,296 @@ pub fn extract_licenses_from_uv_lock(uv_lock_path: Option<PathBuf>, site_package
 
     // Parse uv.lock
     let lock_file = UvLockParser::parse_uv_lock(&lock_path)?;
-    let uv_packages = UvLockParser::extract_packages(&lock_file);
+    let uv_packages = UvLockParser::extract_packages(&lock_file, include_self);
 
-    // Find site-packages directory
-    let site_packages = match site_packages_path {
-        Some(path) => path,
-        None => find_site_packages_path(None)?
-    };
+    // Find site-packages directory (a venv root resolves to its site-packages)
+    let site_packages = find_site_packages_path(site_packages_path)?;
+
+    Ok((uv_packages, site_packages))
+}
+
+/// Extract the license for one package pinned in uv.lock
+fn extract_locked_package(site_packages: &Path, package_name: &str, package_version: &str, include_unknown: bool) -> Option<PackageLicense> {
+    if let Ok(mut license_info) = extractor::extract_license_for_package(site_packages, package_name) {
+        // Report the pinned version, but flag drift: the license was read from the installed copy
+        if let Some(installed) = license_info.version.as_deref().filter(|installed| normalize_version(installed) != normalize_version(package_version)) {
+            license_info.warnings.push(format!(
+                "installed {}@{} but lock pins {}; license read from installed copy",
+                package_name, installed, package_version
+            ));
+        }
+        license_info.version = Some(package_version.to_string());
+        Some(license_info)
+    } else if include_unknown {
+        // Package in uv.lock but not found in site-packages
+        Some(PackageLicense {
+            name: package_name.to_string(),
+            version: Some(package_version.to_string()),
+            effective_license: None,
+            license_classifiers: vec![],
+            metadata_source: "uv.lock (not installed)".to_string(),
+            license_expression: None,
+            warnings: vec![],
+            annotation: None,
+            detection_status: DetectionStatus::NotInstalled,
+            raw_license: None,
+            metadata_name: None,
+            license_confidence: None,
+            license_url: None,
+        })
+    } else {
+        None
+    }
+}
+
+/// Extract licenses from uv.lock file and corresponding site-packages
+pub fn extract_licenses_from_uv_lock(uv_lock_path: Option<PathBuf>, site_packages_path: Option<PathBuf>, include_unknown: bool, include_self: bool) -> Result<Vec<PackageLicense>> {
+    let (uv_packages, site_packages) = resolve_uv_lock_scan(uv_lock_path, site_packages_path, include_self)?;
 
     // Extract licenses for packages found in uv.lock (parallel processing)
     let licenses: Vec<PackageLicense> = uv_packages
         .par_iter()
         .filter_map(|(package_name, package_version)| {
-            if let Ok(mut license_info) = extractor::extract_license_for_package(&site_packages, package_name) {
-                // Verify version matches uv.lock
-                if license_info.version.as_ref() != Some(package_version) {
-                    license_info.version = Some(package_version.clone());
-                }
-                Some(license_info)
-            } else if include_unknown {
-                // Package in uv.lock but not found in site-packages
-                Some(PackageLicense {
-                    name: package_name.clone(),
-                    version: Some(package_version.clone()),
-                    effective_license: None,
-                    license_classifiers: vec![],
-                    metadata_source: "uv.lock (not installed)".to_string(),
-                })
-            } else {
-                None
-            }
+            extract_locked_package(&site_packages, package_name, package_version, include_unknown)
         })
         .collect();
 
     Ok(licenses)
 }
 
-/// Auto-detect and extract licenses (uv.lock required)
-pub fn extract_licenses_auto(path: Option<PathBuf>, include_unknown: bool) -> Result<Vec<PackageLicense>> {
-    // Require uv.lock file - no fallback to site-packages
-    if let Some(_) = UvLockParser::find_uv_lock() {
-        eprintln!("Found uv.lock, using uv-native extraction");
-        return extract_licenses_from_uv_lock(None, path, include_unknown);
+/// Run `extract` on a dedicated rayon pool of `threads` workers (`check --parallelism`);
+/// `None` uses the global pool with one worker per CPU. Only the parallel extraction
+/// inside `extract` is affected; `Some(1)` makes it sequential.
+pub fn with_parallelism<T: Send>(threads: Option<NonZeroUsize>, extract: impl FnOnce() -> Result<T> + Send) -> Result<T> {
+    match threads {
+        Some(threads) => {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads.get())
+                .build()
+                .context("Failed to create the extraction thread pool")?;
+            pool.install(extract)
+        }
+        None => extract(),
     }
+}
+
+/// Streaming variant of `extract_licenses_from_uv_lock`: packages are extracted in
+/// parallel and handed to `on_package` as they complete, in completion order.
+/// Only a bounded number of results is buffered at any time.
+pub fn stream_licenses_from_uv_lock<F>(uv_lock_path: Option<PathBuf>, site_packages_path: Option<PathBuf>, include_unknown: bool, include_self: bool, mut on_package: F) -> Result<()>
+where
+    F: FnMut(PackageLicense) -> Result<()>,
+{
+    let (uv_packages, site_packages) = resolve_uv_lock_scan(uv_lock_path, site_packages_path, include_self)?;
+    let (sender, receiver) = mpsc::sync_channel(STREAM_BUFFER);
+
+    std::thread::scope(|scope| {
+        let site_packages = &site_packages;
+        let uv_packages = &uv_packages;
+        scope.spawn(move || {
+            uv_packages.par_iter().for_each_with(sender, |sender, (package_name, package_version)| {
+                if let Some(package) = extract_locked_package(site_packages, package_name, package_version, include_unknown) {
+                    // The receiver only goes away if the consumer failed; stop quietly
+                    let _ = sender.send(package);
+                }
+            });
+        });
+
+        for package in receiver {
+            on_package(package)?;
+        }
+        Ok(())
+    })
+}
 
+/// Number of extracted packages buffered between the extraction workers and the writer
+const STREAM_BUFFER: usize = 64;
+
+pub fn missing_uv_lock_error() -> anyhow::Error {
     // No uv.lock found - this tool is uv-only
-    Err(anyhow::anyhow!(
+    anyhow::anyhow!(
         "No uv.lock found. This tool requires uv projects.\n\
          Please run 'uv sync' to generate uv.lock file.\n\
          \n\
          For non-uv projects, consider migrating to uv:\n\
          - uv init (new project)\n\
          - uv import (from requirements.txt/pyproject.toml)"
-    ))
+    )
 }
 
-pub fn create_report(packages: Vec<PackageLicense>) -> LicenseReport {
-    let total_packages = packages.len();
-    
-    // Set effective_license field for each package
-    let mut fixed_packages = packages;
-    for package in &mut fixed_packages {
-        package.effective_license = get_effective_license(package);
+/// Auto-detect and extract licenses (uv.lock required)
+pub fn extract_licenses_auto(path: Option<PathBuf>, include_unknown: bool, include_self: bool) -> Result<Vec<PackageLicense>> {
+    // Require uv.lock file - no fallback to site-packages
+    if UvLockParser::find_uv_lock().is_some() {
+        eprintln!("Found uv.lock, using uv-native extraction");
+        return extract_licenses_from_uv_lock(None, path, include_unknown, include_self);
+    }
+
+    Err(missing_uv_lock_error())
+}
+
+/// Auto-detect and stream licenses (uv.lock required), see `stream_licenses_from_uv_lock`
+pub fn stream_licenses_auto<F>(path: Option<PathBuf>, include_unknown: bool, include_self: bool, on_package: F) -> Result<()>
+where
+    F: FnMut(PackageLicense) -> Result<()>,
+{
+    if UvLockParser::find_uv_lock().is_some() {
+        eprintln!("Found uv.lock, using uv-native extraction");
+        return stream_licenses_from_uv_lock(None, path, include_unknown, include_self, on_package);
     }
-    
-    let with_license = fixed_packages.iter()
-        .filter(|p| p.effective_license.is_some())
-        .count();
-    let without_license = total_packages - with_license;
 
-    let mut osi_counts = HashMap::new();
-    let mut non_osi_counts = HashMap::new();
+    Err(missing_uv_lock_error())
+}
 
-    for package in &fixed_packages {
-        let license_info = get_license_info(package);
-        for (license_name, is_osi) in license_info {
+/// Set the computed effective license on a freshly extracted package
+pub fn finalize_package(mut package: PackageLicense) -> PackageLicense {
+    package.effective_license = get_effective_license(&package);
+    package
+}
+
+/// `osi_overrides` config: organizational OSI classification, consulted before the built-in list
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OsiOverrides {
+    #[serde(default)]
+    pub approved: Vec<String>,
+    #[serde(default)]
+    pub not_approved: Vec<String>,
+}
+
+impl OsiOverrides {
+    /// Forced classification for `license`, if any (`not_approved` wins when listed in both)
+    fn classify(&self, license: &str) -> Option<bool> {
+        if self.not_approved.iter().any(|name| name == license) {
+            Some(false)
+        } else if self.approved.iter().any(|name| name == license) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+/// Incrementally builds a `LicenseSummary`, one package at a time
+#[derive(Debug, Default)]
+pub struct SummaryAccumulator {
+    total_packages: usize,
+    with_license: usize,
+    osi_counts: HashMap<String, usize>,
+    non_osi_counts: HashMap<String, usize>,
+    osi_overrides: OsiOverrides,
+}
+
+impl SummaryAccumulator {
+    pub fn with_osi_overrides(osi_overrides: OsiOverrides) -> Self {
+        SummaryAccumulator { osi_overrides, ..Default::default() }
+    }
+
+    pub fn add(&mut self, package: &PackageLicense) {
+        self.total_packages += 1;
+        if package.effective_license.is_some() {
+            self.with_license += 1;
+        }
+
+        for (license_name, is_osi) in get_license_info(package, &self.osi_overrides) {
             if is_osi {
-                *osi_counts.entry(license_name).or_insert(0) += 1;
+                *self.osi_counts.entry(license_name).or_insert(0) += 1;
             } else {
-                *non_osi_counts.entry(license_name).or_insert(0) += 1;
+                *self.non_osi_counts.entry(license_name).or_insert(0) += 1;
             }
         }
     }
 
-    // Convert HashMap to Vec, sort by count (descending), then create IndexMap
-    let mut osi_vec: Vec<(String, usize)> = osi_counts.into_iter().collect();
-    osi_vec.sort_by(|a, b| b.1.cmp(&a.1));
-    let osi_approved: IndexMap<String, usize> = osi_vec.into_iter().collect();
+    pub fn finish(self) -> LicenseSummary {
+        // Convert HashMap to Vec, sort by count (descending), then create IndexMap
+        let mut osi_vec: Vec<(String, usize)> = self.osi_counts.into_iter().collect();
+        osi_vec.sort_by_key(|b| std::cmp::Reverse(b.1));
+        let osi_approved: IndexMap<String, usize> = osi_vec.into_iter().collect();
 
-    let mut non_osi_vec: Vec<(String, usize)> = non_osi_counts.into_iter().collect();
-    non_osi_vec.sort_by(|a, b| b.1.cmp(&a.1));
-    let non_osi: IndexMap<String, usize> = non_osi_vec.into_iter().collect();
+        let mut non_osi_vec: Vec<(String, usize)> = self.non_osi_counts.into_iter().collect();
+        non_osi_vec.sort_by_key(|b| std::cmp::Reverse(b.1));
+        let non_osi: IndexMap<String, usize> = non_osi_vec.into_iter().collect();
 
-    LicenseReport {
-        packages: fixed_packages,
-        summary: LicenseSummary {
-            total_packages,
-            with_license,
-            without_license,
+        LicenseSummary {
+            total_packages: self.total_packages,
+            with_license: self.with_license,
+            without_license: self.total_packages - self.with_license,
             license_types: LicenseTypes {
                 osi_approved,
                 non_osi,
             },
-        },
+        }
+    }
+}
+
+pub fn create_report(packages: Vec<PackageLicense>) -> LicenseReport {
+    create_report_with(packages, OsiOverrides::default())
+}
+
+/// `create_report`, splitting the summary by the configured OSI overrides
+pub fn create_report_with(packages: Vec<PackageLicense>, osi_overrides: OsiOverrides) -> LicenseReport {
+    let mut summary = SummaryAccumulator::with_osi_overrides(osi_overrides);
+
+    // Set effective_license field for each package
+    let packages: Vec<PackageLicense> = packages
+        .into_iter()
+        .map(|package| {
+            let package = finalize_package(package);
+            summary.add(&package);
+            package
+        })
+        .collect();
+
+    LicenseReport {
+        schema_version: REPORT_SCHEMA_VERSION.to_string(),
+        tool: ToolInfo::current(),
+        generated_at: None,
+        packages,
+        summary: summary.finish(),
         violations: None,
+        exception_coverage: None,
+        environment: None,
+        filter: None,
+        diff: None,
+        content_hash: None,
+        packages_by_license: None,
+        footer: None,
     }
 }
 
-fn get_license_info(package: &PackageLicense) -> Vec<(String, bool)> {
+fn get_license_info(package: &PackageLicense, osi_overrides: &OsiOverrides) -> Vec<(String, bool)> {
     let license_info = extract_license_info(package);
     
     if let Some(name) = license_info.name {
-        vec![(name, license_info.is_osi_approved)]
+        let is_osi = osi_overrides.classify(&name).unwrap_or(license_info.is_osi_approved);
+        vec![(name, is_osi)]
     } else {
         vec![("Unknown".to_string(), false)]
     }
 }
 
+/// Canonical name for `License :: Other/Proprietary License` and similar
+pub const PROPRIETARY: &str = "Proprietary";
+
+/// Normalized name for public-domain dedications without a specific SPDX id
+/// (e.g. `License :: Public Domain`)
+pub const PUBLIC_DOMAIN: &str = "Public-Domain";
+
+/// Public-domain dedications: no conditions at all, so neither copyleft nor attribution
+pub fn is_public_domain(license: &str) -> bool {
+    matches!(license, PUBLIC_DOMAIN | "CC0-1.0" | "Unlicense")
+}
+
+/// `MIT/Apache-2.0` → `MIT OR Apache-2.0` when every part looks like a license identifier
+fn slash_expression(license: &str) -> Option<String> {
+    let parts: Vec<&str> = license.split('/').map(str::trim).collect();
+    let is_id = |part: &str| {
+        part.len() >= 2
+            && part.starts_with(|c: char| c.is_ascii_alphabetic())
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+'))
+    };
+    (parts.len() >= 2 && parts.iter().all(|part| is_id(part))).then(|| parts.join(" OR "))
+}
+
 pub fn normalize_license_name(license: &str) -> String {
     let license = license.trim();
     let license_lower = license.to_lowercase();
@@ -234,6 +741,23 @@ pub fn normalize_license_name(license: &str) -> String {
     if license.starts_with("Copyright") || license.starts_with("=") || license.len() < 3 {
         return "Unknown".to_string();
     }
+
+    // Compound SPDX expressions are evaluated per branch by the policy, keep them intact
+    if parse_expression(license).is_some() {
+        return license.to_string();
+    }
+    // Cargo's legacy `MIT/Apache-2.0` (common in Rust extensions) means MIT OR Apache-2.0
+    if let Some(expression) = slash_expression(license) {
+        return expression;
+    }
+    // Other text with SPDX operators is a malformed expression: never collapse it to the
+    // first license the patterns below happen to find ("MIT AND (...)" is not "MIT")
+    if license
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .any(|token| matches!(token, "AND" | "OR" | "WITH"))
+    {
+        return license.to_string();
+    }
     
     // Exact matches first (most common cases)
     match license {
@@ -247,9 +771,30 @@ pub fn normalize_license_name(license: &str) -> String {
         "GPL-3.0" | "GPLv3" => return "GPL-3.0".to_string(),
         "LGPL-2.1" | "LGPLv2.1" => return "LGPL-2.1".to_string(),
         "LGPL-3.0" | "LGPLv3" => return "LGPL-3.0".to_string(),
+        "Other/Proprietary License" | "Proprietary" | "Proprietary License" | "LicenseRef-Proprietary" => {
+            return PROPRIETARY.to_string()
+        }
+        "Public Domain" | "Public domain" | "public domain" | "Public-Domain" => return PUBLIC_DOMAIN.to_string(),
+        "CC0" | "CC0-1.0" | "CC0 1.0" | "CC0 1.0 Universal" => return "CC0-1.0".to_string(),
         _ => {}
     }
     
+    // GNU classifiers carry an SPDX-ish suffix, e.g. "... v3 or later (GPLv3+)"
+    if let Some(spdx) = gnu_license_from_suffix(license) {
+        return spdx.to_string();
+    }
+
+    // Public-domain dedications before the generic patterns ("CC0 ... Dedication" etc.)
+    if license_lower.contains("unlicense") {
+        return "Unlicense".to_string();
+    }
+    if license_lower.contains("cc0") || license_lower.contains("creative commons zero") {
+        return "CC0-1.0".to_string();
+    }
+    if license_lower.contains("public domain") {
+        return PUBLIC_DOMAIN.to_string();
+    }
+
     // Pattern matching for variations
     if license_lower.contains("mit") {
         return "MIT".to_string();
@@ -266,32 +811,49 @@ pub fn normalize_license_name(license: &str) -> String {
     if license_lower.contains("bsd license") {
         return "BSD-3-Clause".to_string();
     }
-    if license_lower.contains("gpl") && license_lower.contains("3") {
-        return "GPL-3.0".to_string();
-    }
-    if license_lower.contains("gpl") && license_lower.contains("2") {
-        return "GPL-2.0".to_string();
-    }
+    // LGPL first: every LGPL name also contains "gpl"
     if license_lower.contains("lgpl") && license_lower.contains("3") {
         return "LGPL-3.0".to_string();
     }
     if license_lower.contains("lgpl") && license_lower.contains("2") {
         return "LGPL-2.1".to_string();
     }
+    if license_lower.contains("gpl") && license_lower.contains("3") {
+        return "GPL-3.0".to_string();
+    }
+    if license_lower.contains("gpl") && license_lower.contains("2") {
+        return "GPL-2.0".to_string();
+    }
     if license_lower.contains("mozilla public license") || license_lower == "mpl-2.0" {
         return "MPL-2.0".to_string();
     }
     if license_lower == "isc license" || license_lower == "isc" {
         return "ISC".to_string();
     }
-    if license_lower.contains("unlicense") {
-        return "Unlicense".to_string();
-    }
     
     // Return original if no normalization found
     license.to_string()
 }
 
+/// Map the parenthetical suffix of GNU license classifiers to an SPDX identifier
+fn gnu_license_from_suffix(license: &str) -> Option<&'static str> {
+    let suffix = license.strip_suffix(')')?.rsplit_once('(')?.1.trim();
+    let spdx = match suffix {
+        "GPLv2" => "GPL-2.0-only",
+        "GPLv2+" => "GPL-2.0-or-later",
+        "GPLv3" => "GPL-3.0-only",
+        "GPLv3+" => "GPL-3.0-or-later",
+        "LGPLv2" => "LGPL-2.0-only",
+        "LGPLv2+" => "LGPL-2.0-or-later",
+        "LGPLv3" => "LGPL-3.0-only",
+        "LGPLv3+" => "LGPL-3.0-or-later",
+        "AGPLv3" => "AGPL-3.0-only",
+        "AGPLv3+" => "AGPL-3.0-or-later",
+        _ => return None,
+    };
+    Some(spdx)
+}
+
 fn is_osi_approved_license(license: &str) -> bool {
     // Common OSI-approved licenses
     let osi_licenses = [
@@ -307,18 +869,39 @@ fn is_osi_approved_license(license: &str) -> bool {
 }
 
 pub fn extract_license_info(package: &PackageLicense) -> LicenseInfo {
-    // Prioritize classifiers (more standardized)
+    // License-Expression is authoritative in Metadata 2.4
+    if let Some(expression) = &package.license_expression {
+        let normalized_name = normalize_license_name(expression);
+        return LicenseInfo {
+            is_osi_approved: is_osi_approved_license(&normalized_name),
+            name: Some(normalized_name),
+            source: LicenseSource::LicenseField,
+        };
+    }
+
+    // Prioritize classifiers (more standardized); several distinct licenses mean dual licensing
+    let mut classifier_licenses: Vec<(String, &String)> = Vec::new();
     for classifier in &package.license_classifiers {
         if let Some(license_name) = extract_license_from_classifier(classifier) {
             let normalized_name = normalize_license_name(&license_name);
-            let is_osi = classifier.contains("OSI Approved");
-            return LicenseInfo {
-                name: Some(normalized_name),
-                is_osi_approved: is_osi,
-                source: LicenseSource::Classifier(classifier.clone()),
-            };
+            if !classifier_licenses.iter().any(|(name, _)| *name == normalized_name) {
+                classifier_licenses.push((normalized_name, classifier));
+            }
         }
     }
+    // The bare "License :: OSI Approved" classifier names no license of its own
+    if classifier_licenses.len() > 1 {
+        classifier_licenses.retain(|(name, _)| name != "OSI Approved");
+    }
+    if let Some((_, first_classifier)) = classifier_licenses.first() {
+        let names: Vec<&str> = classifier_licenses.iter().map(|(name, _)| name.as_str()).collect();
+        return LicenseInfo {
+            name: Some(names.join(" OR ")),
+            // Either license may be chosen, so one OSI-approved branch is enough
+            is_osi_approved: classifier_licenses.iter().any(|(_, classifier)| classifier.contains("OSI Approved")),
+            source: LicenseSource::Classifier((*first_classifier).clone()),
+        };
+    }
 
     // Use effective_license if available
     if let Some(license) = &package.effective_license {
@@ -349,8 +932,9 @@ fn extract_license_from_classifier(classifier: &str) -> Option<String> {
     // Extract license name from classifier like "License :: OSI Approved :: MIT License"
     if classifier.starts_with("License :: ") {
         let parts: Vec<&str> = classifier.split(" :: ").collect();
+        // Deeper classifiers nest further; the last segment is the most specific
         if parts.len() >= 3 {
-            return Some(parts[2].to_string());
+            return parts.last().map(|name| name.to_string());
         } else if parts.len() == 2 {
             return Some(parts[1].to_string());
         }
@@ -371,14 +955,15 @@ mod tests {
             effective_license: Some("Copyright (c) 2025, Example Corp.".to_string()),
             license_classifiers: vec!["License :: OSI Approved :: BSD License".to_string()],
             metadata_source: "METADATA".to_string(),
+            ..Default::default()
         };
 
-        let licenses = get_license_info(&package);
+        let licenses = get_license_info(&package, &OsiOverrides::default());
         
         // Should extract BSD-3-Clause from classifier, not Unknown from copyright
         assert_eq!(licenses.len(), 1);
         assert_eq!(licenses[0].0, "BSD-3-Clause");
-        assert_eq!(licenses[0].1, true); // OSI approved
+        assert!(licenses[0].1); // OSI approved
     }
 
     #[test]
@@ -388,16 +973,16 @@ mod tests {
             name: "legacy-package".to_string(),
             version: Some("0.9.0".to_string()),
             effective_license: Some("Copyright (c) 2025, Legacy Developer.".to_string()),
-            license_classifiers: vec![],
             metadata_source: "METADATA".to_string(),
+            ..Default::default()
         };
 
-        let licenses = get_license_info(&package);
+        let licenses = get_license_info(&package, &OsiOverrides::default());
         
         // Should be Unknown since copyright statements are not license names
         assert_eq!(licenses.len(), 1);
         assert_eq!(licenses[0].0, "Unknown");
-        assert_eq!(licenses[0].1, false);
+        assert!(!licenses[0].1);
     }
 
     #[test]
@@ -407,16 +992,16 @@ mod tests {
             name: "simple-tool".to_string(),
             version: Some("2.0.1".to_string()),
             effective_license: Some("MIT".to_string()),
-            license_classifiers: vec![],
             metadata_source: "METADATA".to_string(),
+            ..Default::default()
         };
 
-        let licenses = get_license_info(&package);
+        let licenses = get_license_info(&package, &OsiOverrides::default());
         
         // Should use license field when it's not a copyright statement
         assert_eq!(licenses.len(), 1);
         assert_eq!(licenses[0].0, "MIT");
-        assert_eq!(licenses[0].1, true); // MIT is OSI approved
+        assert!(licenses[0].1); // MIT is OSI approved
     }
 
     #[test]
@@ -443,6 +1028,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gnu_classifiers_map_to_spdx() {
+        let cases = [
+            ("License :: OSI Approved :: GNU General Public License v2 (GPLv2)", "GPL-2.0-only"),
+            ("License :: OSI Approved :: GNU General Public License v2 or later (GPLv2+)", "GPL-2.0-or-later"),
+            ("License :: OSI Approved :: GNU General Public License v3 (GPLv3)", "GPL-3.0-only"),
+            ("License :: OSI Approved :: GNU General Public License v3 or later (GPLv3+)", "GPL-3.0-or-later"),
+            ("License :: OSI Approved :: GNU Lesser General Public License v2 or later (LGPLv2+)", "LGPL-2.0-or-later"),
+            ("License :: OSI Approved :: GNU Lesser General Public License v3 (LGPLv3)", "LGPL-3.0-only"),
+            ("License :: OSI Approved :: GNU Lesser General Public License v3 or later (LGPLv3+)", "LGPL-3.0-or-later"),
+            ("License :: OSI Approved :: GNU Affero General Public License v3 or later (AGPLv3+)", "AGPL-3.0-or-later"),
+        ];
+        for (classifier, expected) in cases {
+            let name = extract_license_from_classifier(classifier).unwrap();
+            assert_eq!(normalize_license_name(&name), expected, "{}", classifier);
+        }
+
+        // Deeper nesting: the most specific segment wins
+        assert_eq!(
+            extract_license_from_classifier("License :: OSI Approved :: GNU :: GNU General Public License v3 (GPLv3)"),
+            Some("GNU General Public License v3 (GPLv3)".to_string())
+        );
+        // Without a suffix, LGPL is no longer mistaken for GPL
+        assert_eq!(normalize_license_name("LGPL version 3"), "LGPL-3.0");
+    }
+
+    #[test]
+    fn test_proprietary_classifier() {
+        let package = PackageLicense {
+            name: "vendor-sdk".to_string(),
+            version: Some("1.0".to_string()),
+            license_classifiers: vec!["License :: Other/Proprietary License".to_string()],
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+
+        let info = extract_license_info(&package);
+        assert_eq!(info.name.as_deref(), Some(PROPRIETARY));
+        assert!(!info.is_osi_approved);
+        assert_eq!(normalize_license_name("Other/Proprietary License"), "Proprietary");
+    }
+
+    #[test]
+    fn test_license_types_sort_keys() {
+        let mut types = LicenseTypes::default();
+        types.osi_approved.extend([("MIT".to_string(), 5), ("Apache-2.0".to_string(), 3), ("BSD-3-Clause".to_string(), 1)]);
+        types.sort_keys();
+        let keys: Vec<&str> = types.osi_approved.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["Apache-2.0", "BSD-3-Clause", "MIT"]);
+        assert_eq!(types.osi_approved["MIT"], 5);
+    }
+
+    #[test]
+    fn test_public_domain_normalization() {
+        assert_eq!(normalize_license_name("Public Domain"), PUBLIC_DOMAIN);
+        assert_eq!(normalize_license_name("CC0 1.0 Universal (CC0 1.0) Public Domain Dedication"), "CC0-1.0");
+        assert_eq!(normalize_license_name("The Unlicense (Unlicense)"), "Unlicense");
+        assert!(["Public Domain", "CC0-1.0", "Unlicense"].iter().all(|l| is_public_domain(&normalize_license_name(l))));
+
+        let package = PackageLicense {
+            name: "pd-lib".to_string(),
+            version: Some("1.0".to_string()),
+            license_classifiers: vec!["License :: Public Domain".to_string()],
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(extract_license_info(&package).name.as_deref(), Some(PUBLIC_DOMAIN));
+    }
+
     #[test]
     fn test_extract_license_info_classifier_priority() {
         let package = PackageLicense {
@@ -451,27 +1105,52 @@ mod tests {
             effective_license: Some("Copyright (c) 2025".to_string()),
             license_classifiers: vec!["License :: OSI Approved :: MIT License".to_string()],
             metadata_source: "METADATA".to_string(),
+            ..Default::default()
         };
 
         let info = extract_license_info(&package);
         assert_eq!(info.name, Some("MIT".to_string()));
-        assert_eq!(info.is_osi_approved, true);
+        assert!(info.is_osi_approved);
         assert!(matches!(info.source, LicenseSource::Classifier(_)));
     }
 
+    #[test]
+    fn test_dual_license_classifiers() {
+        let mut package = PackageLicense {
+            name: "dual-lib".to_string(),
+            version: Some("1.0.0".to_string()),
+            license_classifiers: vec![
+                "License :: OSI Approved".to_string(),
+                "License :: OSI Approved :: MIT License".to_string(),
+                "License :: OSI Approved :: Apache Software License".to_string(),
+                "License :: OSI Approved :: MIT License".to_string(),
+            ],
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+
+        let info = extract_license_info(&package);
+        assert_eq!(info.name.as_deref(), Some("MIT OR Apache-2.0"));
+        assert!(info.is_osi_approved);
+        assert!(parse_expression(info.name.as_deref().unwrap()).is_some());
+
+        package.license_classifiers.truncate(1);
+        assert_eq!(get_effective_license(&package).as_deref(), Some("OSI Approved"));
+    }
+
     #[test]
     fn test_extract_license_info_license_field_fallback() {
         let package = PackageLicense {
             name: "test-package".to_string(),
             version: Some("1.0.0".to_string()),
             effective_license: Some("Apache-2.0".to_string()),
-            license_classifiers: vec![],
             metadata_source: "METADATA".to_string(),
+            ..Default::default()
         };
 
         let info = extract_license_info(&package);
         assert_eq!(info.name, Some("Apache-2.0".to_string()));
-        assert_eq!(info.is_osi_approved, true);
+        assert!(info.is_osi_approved);
         assert!(matches!(info.source, LicenseSource::LicenseField));
     }
 
@@ -481,16 +1160,40 @@ mod tests {
             name: "test-package".to_string(),
             version: Some("1.0.0".to_string()),
             effective_license: Some("Copyright (c) 2025".to_string()),
-            license_classifiers: vec![],
             metadata_source: "METADATA".to_string(),
+            ..Default::default()
         };
 
         let info = extract_license_info(&package);
         assert_eq!(info.name, None);
-        assert_eq!(info.is_osi_approved, false);
+        assert!(!info.is_osi_approved);
         assert!(matches!(info.source, LicenseSource::Unknown));
     }
 
+    #[test]
+    fn test_mixed_cargo_expressions_stay_intact() {
+        assert_eq!(normalize_license_name("MIT AND (Apache-2.0 OR BSD-3-Clause)"), "MIT AND (Apache-2.0 OR BSD-3-Clause)");
+        assert_eq!(normalize_license_name("MIT/Apache-2.0"), "MIT OR Apache-2.0");
+        // Malformed, but must not be read as plain MIT
+        assert_eq!(normalize_license_name("MIT AND (Apache 2.0 OR BSD)"), "MIT AND (Apache 2.0 OR BSD)");
+        assert_eq!(normalize_license_name("MIT License"), "MIT");
+
+        let policy: crate::policy::LicensePolicy = toml::from_str(
+            "name = \"p\"\n[allowed_licenses]\nexact = [\"MIT\", \"Apache-2.0\"]\n[forbidden_licenses]\nexact = [\"BSD-3-Clause\"]"
+        ).unwrap();
+        let package = |license: &str| PackageLicense {
+            name: "rust-ext".to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: Some(normalize_license_name(license)),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        // The OR branch may take Apache-2.0, so the whole expression is allowed
+        assert_eq!(policy.detect_violations(&[package("MIT AND (Apache-2.0 OR BSD-3-Clause)")]).total, 0);
+        assert_eq!(policy.detect_violations(&[package("MIT AND BSD-3-Clause")]).errors, 1);
+        assert_eq!(policy.detect_violations(&[package("MIT/Apache-2.0")]).total, 0);
+    }
+
     #[test]
     fn test_normalize_bsd_license() {
         // Test BSD License normalization
@@ -498,4 +1201,481 @@ mod tests {
         assert_eq!(normalize_license_name("BSD 3-Clause License"), "BSD-3-Clause");
         assert_eq!(normalize_license_name("BSD 2-Clause License"), "BSD-2-Clause");
     }
+
+    #[test]
+    fn test_osi_filter_keeps_full_summary() {
+        let package = |name: &str, license: Option<&str>, classifiers: Vec<&str>| PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0.0".to_string()),
+            effective_license: license.map(|l| l.to_string()),
+            license_classifiers: classifiers.into_iter().map(|c| c.to_string()).collect(),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let packages = vec![
+            package("requests", Some("Apache-2.0"), vec![]),
+            package("internal", Some("Proprietary"), vec![]),
+            package("mystery", None, vec![]),
+        ];
+
+        let mut report = create_report(packages.clone());
+        report.apply_filter(OsiFilter::NonOsi, &OsiOverrides::default());
+        let names: Vec<_> = report.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["internal", "mystery"]);
+        assert_eq!(report.summary.total_packages, 3);
+        assert_eq!(report.filter, Some(OsiFilter::NonOsi));
+
+        let mut report = create_report(packages.clone());
+        report.apply_filter(OsiFilter::Osi, &OsiOverrides::default());
+        assert_eq!(report.packages.len(), 1);
+        assert_eq!(report.packages[0].name, "requests");
+
+        // The filter follows osi_overrides just like the summary does
+        let overrides = OsiOverrides { approved: vec!["Proprietary".to_string()], not_approved: vec!["Apache-2.0".to_string()] };
+        let mut report = create_report_with(packages, overrides.clone());
+        report.apply_filter(OsiFilter::Osi, &overrides);
+        let names: Vec<_> = report.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["internal"]);
+    }
+
+    #[test]
+    fn test_content_hash_ignores_order() {
+        let package = |name: &str, version: &str, license: &str| PackageLicense {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            effective_license: Some(license.to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let a = create_report(vec![package("requests", "2.31.0", "Apache-2.0"), package("Typing_Extensions", "4.9.0", "PSF-2.0")]);
+        let b = create_report(vec![package("typing-extensions", "4.9.0", "PSF-2.0"), package("requests", "2.31.0", "Apache-2.0")]);
+        let upgraded = create_report(vec![package("requests", "2.32.0", "Apache-2.0"), package("typing-extensions", "4.9.0", "PSF-2.0")]);
+
+        assert!(a.content_hash().starts_with("sha256:"));
+        assert_eq!(a.content_hash().len(), "sha256:".len() + 64);
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), upgraded.content_hash());
+    }
+
+    #[test]
+    fn test_license_index_matches_summary_counts() {
+        let package = |name: &str, license: Option<&str>| PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: license.map(str::to_string),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let report = create_report(vec![
+            package("requests", Some("Apache-2.0")),
+            package("click", Some("BSD-3-Clause")),
+            package("attrs", Some("MIT")),
+            package("idna", Some("BSD-3-Clause")),
+            package("mystery", None),
+        ]);
+
+        let index = report.license_index();
+        assert_eq!(index["BSD-3-Clause"], vec!["click", "idna"]);
+        assert_eq!(index["Unknown"], vec!["mystery"]);
+        let types = &report.summary.license_types;
+        let counts: Vec<(&String, usize)> = types.osi_approved.iter().chain(types.non_osi.iter()).map(|(license, count)| (license, *count)).collect();
+        assert_eq!(index.iter().map(|(license, names)| (license, names.len())).collect::<Vec<_>>(), counts);
+    }
+
+    #[test]
+    fn test_stream_matches_collected_extraction() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let uv_lock_path = temp_dir.path().join("uv.lock");
+        let site_packages = temp_dir.path().join("site-packages");
+        fs::create_dir(&site_packages).unwrap();
+
+        let mut lock = String::from("version = 1\n");
+        for i in 0..20 {
+            lock.push_str(&format!("\n[[package]]\nname = \"pkg{}\"\nversion = \"1.0.{}\"\n", i, i));
+            let dist_info = site_packages.join(format!("pkg{}-1.0.{}.dist-info", i, i));
+            fs::create_dir(&dist_info).unwrap();
+            fs::write(dist_info.join("METADATA"), "Name: pkg\nLicense: MIT\n").unwrap();
+        }
+        fs::write(&uv_lock_path, lock).unwrap();
+
+        let collected = extract_licenses_from_uv_lock(Some(uv_lock_path.clone()), Some(site_packages.clone()), false, false).unwrap();
+
+        let mut streamed = Vec::new();
+        stream_licenses_from_uv_lock(Some(uv_lock_path), Some(site_packages), false, false, |package| {
+            streamed.push(package);
+            Ok(())
+        }).unwrap();
+
+        assert_eq!(streamed.len(), 20);
+        let mut collected_names: Vec<_> = collected.iter().map(|p| p.name.clone()).collect();
+        let mut streamed_names: Vec<_> = streamed.iter().map(|p| p.name.clone()).collect();
+        collected_names.sort();
+        streamed_names.sort();
+        assert_eq!(collected_names, streamed_names);
+
+        let mut summary = SummaryAccumulator::default();
+        for package in streamed {
+            summary.add(&finalize_package(package));
+        }
+        let summary = summary.finish();
+        assert_eq!(summary.total_packages, 20);
+        assert_eq!(summary.license_types.osi_approved.get("MIT"), Some(&20));
+    }
+
+    #[test]
+    fn test_egg_info_nested_and_legacy_file_layouts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path();
+        let pkg_info = |license: &str| format!("Metadata-Version: 1.1\nName: pkg\nLicense: {}\n", license);
+
+        // Legacy: .egg-info is a single file
+        fs::write(site_packages.join("legacy-1.0.egg-info"), pkg_info("MIT")).unwrap();
+
+        // Nested: PKG-INFO lives in src/<name>.egg-info/
+        let nested = site_packages.join("nested-2.0.egg-info").join("src").join("nested.egg-info");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("PKG-INFO"), pkg_info("BSD-3-Clause")).unwrap();
+
+        let mut packages = extract_all_licenses(site_packages, false).unwrap();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(packages.len(), 2);
+        assert_eq!((packages[0].name.as_str(), packages[0].version.as_deref()), ("legacy", Some("1.0")));
+        assert_eq!(packages[0].effective_license.as_deref(), Some("MIT"));
+        assert_eq!((packages[1].name.as_str(), packages[1].version.as_deref()), ("nested", Some("2.0")));
+        assert_eq!(packages[1].effective_license.as_deref(), Some("BSD-3-Clause"));
+        assert_eq!(packages[1].metadata_source, "PKG-INFO");
+    }
+
+    #[test]
+    fn test_license_expression_wins_over_stale_classifier() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dist_info = temp_dir.path().join("migrated-1.0.dist-info");
+        fs::create_dir(&dist_info).unwrap();
+        fs::write(
+            dist_info.join("METADATA"),
+            "Metadata-Version: 2.4\nName: migrated\nLicense-Expression: MIT\nClassifier: License :: OSI Approved :: BSD License\n",
+        ).unwrap();
+
+        let packages = extract_all_licenses(temp_dir.path(), false).unwrap();
+        let package = &packages[0];
+        assert_eq!(package.effective_license.as_deref(), Some("MIT"));
+        assert_eq!(package.license_expression.as_deref(), Some("MIT"));
+        assert_eq!(package.warnings.len(), 1);
+        assert!(package.warnings[0].contains("disagrees"));
+
+        // The summary follows License-Expression too
+        let report = create_report(packages);
+        assert_eq!(report.summary.license_types.osi_approved.get("MIT"), Some(&1));
+
+        // Agreeing metadata produces no warning
+        fs::write(
+            dist_info.join("METADATA"),
+            "Name: migrated\nLicense-Expression: BSD-3-Clause OR MIT\nClassifier: License :: OSI Approved :: BSD License\n",
+        ).unwrap();
+        assert!(extract_all_licenses(temp_dir.path(), false).unwrap()[0].warnings.is_empty());
+    }
+
+    #[test]
+    fn test_dist_info_name_version_cross_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dist_info = temp_dir.path().join("typing_extensions-4.9.0.dist-info");
+        fs::create_dir(&dist_info).unwrap();
+
+        // Normalized names match (typing_extensions == typing-extensions)
+        fs::write(
+            dist_info.join("METADATA"),
+            "Metadata-Version: 2.1\nName: typing-extensions\nVersion: 4.9.0\nLicense: PSF\n\nName: ignored body\n",
+        ).unwrap();
+        assert!(extract_all_licenses(temp_dir.path(), false).unwrap()[0].warnings.is_empty());
+
+        fs::write(
+            dist_info.join("METADATA"),
+            "Metadata-Version: 2.1\nName: evil-package\nVersion: 6.6.6\nLicense: PSF\n",
+        ).unwrap();
+        let packages = extract_all_licenses(temp_dir.path(), false).unwrap();
+        assert_eq!(packages[0].warnings.len(), 1);
+        assert!(packages[0].warnings[0].contains("name 'evil-package' and version '6.6.6'"));
+    }
+
+    #[test]
+    fn test_local_version_segments() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let torch = temp_dir.path().join("torch-2.1.0+cpu.dist-info");
+        fs::create_dir(&torch).unwrap();
+        fs::write(torch.join("METADATA"), "Metadata-Version: 2.1\nName: torch\nVersion: 2.1.0+cpu\nLicense: BSD-3-Clause\n").unwrap();
+        // Unnormalized local segment with a dash, spelled `_` in the directory name
+        let vision = temp_dir.path().join("torchvision-0.16.0+cu_118.dist-info");
+        fs::create_dir(&vision).unwrap();
+        fs::write(vision.join("METADATA"), "Metadata-Version: 2.1\nName: torchvision\nVersion: 0.16.0+cu-118\nLicense: BSD\n").unwrap();
+
+        let mut packages = extract_all_licenses(temp_dir.path(), false).unwrap();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!((packages[0].name.as_str(), packages[0].version.as_deref()), ("torch", Some("2.1.0+cpu")));
+        assert_eq!((packages[1].name.as_str(), packages[1].version.as_deref()), ("torchvision", Some("0.16.0+cu_118")));
+        assert!(packages.iter().all(|package| package.warnings.is_empty()), "{:?}", packages);
+
+        // uv.lock pins the normalized spelling: no drift warning
+        let locked = extract_locked_package(temp_dir.path(), "torchvision", "0.16.0+cu.118", false).unwrap();
+        assert!(locked.warnings.is_empty(), "{:?}", locked.warnings);
+        let locked = extract_locked_package(temp_dir.path(), "torch", "2.1.0", false).unwrap();
+        assert_eq!(locked.warnings.len(), 1);
+
+        assert!(crate::exceptions::version_spec_matches(Some(">=2.0,<3"), Some("2.1.0+cpu")));
+        assert!(crate::exceptions::version_spec_matches(Some("2.1.0+CPU"), Some("2.1.0+cpu")));
+    }
+
+    #[test]
+    fn test_project_url_license_link() {
+        let package = extractor::package_from_metadata(
+            "sparse".to_string(),
+            Some("1.0".to_string()),
+            "Name: sparse\nProject-URL: Homepage, https://example.com\nProject-URL: license , https://github.com/acme/sparse/blob/main/LICENSE\n",
+            "METADATA",
+        );
+        assert_eq!(package.effective_license, None);
+        assert_eq!(package.license_url.as_deref(), Some("https://github.com/acme/sparse/blob/main/LICENSE"));
+    }
+
+    #[test]
+    fn test_osi_overrides_flip_summary_bucket() {
+        let package = PackageLicense {
+            name: "internal".to_string(),
+            version: Some("1.0".to_string()),
+            metadata_source: "METADATA".to_string(),
+            license_expression: Some("LicenseRef-Acme-Internal".to_string()),
+            ..Default::default()
+        };
+
+        let report = create_report(vec![package.clone()]);
+        assert!(report.summary.license_types.non_osi.contains_key("LicenseRef-Acme-Internal"));
+
+        let overrides = OsiOverrides { approved: vec!["LicenseRef-Acme-Internal".to_string()], not_approved: vec![] };
+        let report = create_report_with(vec![package], overrides);
+        assert_eq!(report.summary.license_types.osi_approved.get("LicenseRef-Acme-Internal"), Some(&1));
+        assert!(report.summary.license_types.non_osi.is_empty());
+    }
+
+    #[test]
+    fn test_empty_environment_hint() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path().join("lib").join("python3.12").join("site-packages");
+        fs::create_dir_all(site_packages.join("_distutils_hack")).unwrap();
+        assert!(empty_environment_hint(&site_packages).unwrap().contains("run 'uv sync'"));
+        assert!(empty_environment_hint(temp_dir.path()).unwrap().contains("is this the right path?"));
+
+        fs::create_dir_all(site_packages.join("pip-24.0.dist-info")).unwrap();
+        assert_eq!(empty_environment_hint(&site_packages), None);
+    }
+
+    #[test]
+    fn test_version_drift_warning() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path().join("site-packages");
+        for (name, version) in [("drifted", "1.2"), ("pinned", "2.0")] {
+            let dist_info = site_packages.join(format!("{}-{}.dist-info", name, version));
+            fs::create_dir_all(&dist_info).unwrap();
+            fs::write(dist_info.join("METADATA"), format!("Name: {}\nVersion: {}\nLicense: MIT\n", name, version)).unwrap();
+        }
+        let uv_lock_path = temp_dir.path().join("uv.lock");
+        fs::write(&uv_lock_path, "version = 1\n\n[[package]]\nname = \"drifted\"\nversion = \"1.3\"\n\n[[package]]\nname = \"pinned\"\nversion = \"2.0\"\n").unwrap();
+
+        let packages = extract_licenses_from_uv_lock(Some(uv_lock_path), Some(site_packages), false, false).unwrap();
+        let package = |name: &str| packages.iter().find(|p| p.name == name).unwrap();
+        assert_eq!(package("drifted").version.as_deref(), Some("1.3"));
+        assert_eq!(package("drifted").warnings, vec!["installed drifted@1.2 but lock pins 1.3; license read from installed copy"]);
+        assert!(package("pinned").warnings.is_empty());
+    }
+
+    #[test]
+    fn test_load_report_validates_schema() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let report_path = temp_dir.path().join("report.json");
+        let mut report = create_report(vec![]);
+        report.violations = Some(crate::policy::ViolationSummary::from_details(vec![]));
+        fs::write(&report_path, serde_json::to_string_pretty(&report).unwrap()).unwrap();
+        assert!(LicenseReport::load(&report_path).unwrap().violations.is_some());
+
+        fs::write(&report_path, r#"{"bomFormat": "CycloneDX"}"#).unwrap();
+        let error = format!("{:#}", LicenseReport::load(&report_path).unwrap_err());
+        assert!(error.contains("is not a py-license-auditor JSON report"));
+
+        let mut document = serde_json::to_value(&report).unwrap();
+        assert_eq!(document["schema_version"], "1");
+        assert_eq!(document["tool"]["name"], "py-license-auditor");
+        assert!(document.get("generated_at").is_none());
+        document["schema_version"] = "2".into();
+        fs::write(&report_path, document.to_string()).unwrap();
+        let error = format!("{:#}", LicenseReport::load(&report_path).unwrap_err());
+        assert!(error.contains("has report schema_version 2"));
+
+        // Reports from before the envelope load as version 1
+        let legacy = document.as_object_mut().unwrap();
+        legacy.remove("schema_version");
+        legacy.remove("tool");
+        fs::write(&report_path, document.to_string()).unwrap();
+        assert_eq!(LicenseReport::load(&report_path).unwrap().schema_version, "1");
+    }
+
+    #[test]
+    fn test_license_file_pointers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dist_info = |name: &str, license: &str, files: &[(&str, &str)]| {
+            let dir = temp_dir.path().join(format!("{}-1.0.dist-info", name));
+            fs::create_dir_all(dir.join("licenses")).unwrap();
+            fs::write(dir.join("METADATA"), format!("Name: {}\nLicense: {}\n", name, license)).unwrap();
+            for (file, text) in files {
+                fs::write(dir.join(file), text).unwrap();
+            }
+        };
+        let mit = "Permission is hereby granted, free of charge, to any person ... The above copyright notice";
+        let bsd = "Redistribution and use in source and binary forms ... Neither the name of the copyright holder";
+        dist_info("see_license", "See LICENSE", &[("LICENSE", mit)]);
+        dist_info("file_pointer", "file:LICENSE.txt", &[("licenses/LICENSE.txt", bsd)]);
+        dist_info("bare_name", "LICENSE.txt", &[]);
+
+        let packages = extract_all_licenses(temp_dir.path(), true).unwrap();
+        let package = |name: &str| packages.iter().find(|p| p.name == name).unwrap();
+        assert_eq!(package("see_license").effective_license.as_deref(), Some("MIT"));
+        assert_eq!(package("see_license").raw_license.as_deref(), Some("See LICENSE"));
+        assert_eq!(package("file_pointer").effective_license.as_deref(), Some("BSD-3-Clause"));
+        assert_eq!(package("bare_name").effective_license, None);
+        assert_eq!(package("bare_name").detection_status, DetectionStatus::NoLicenseField);
+        assert_eq!(package("bare_name").warnings, vec!["License field 'LICENSE.txt' refers to a license file that was not found or not recognized"]);
+    }
+
+    #[test]
+    fn test_with_parallelism() {
+        let threads = || Ok(rayon::current_num_threads());
+        assert_eq!(with_parallelism(NonZeroUsize::new(1), threads).unwrap(), 1);
+        assert_eq!(with_parallelism(NonZeroUsize::new(3), threads).unwrap(), 3);
+        assert_eq!(with_parallelism(None, threads).unwrap(), rayon::current_num_threads());
+    }
+
+    #[test]
+    fn test_legacy_metadata_json_dist_info() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dist_info = temp_dir.path().join("oldwheel-0.9.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(dist_info.join("metadata.json"), r#"{
+            "metadata_version": "2.0",
+            "name": "oldwheel",
+            "version": "0.9",
+            "license": "UNKNOWN",
+            "classifiers": ["Programming Language :: Python :: 2", "License :: OSI Approved :: MIT License"]
+        }"#).unwrap();
+
+        let packages = extract_all_licenses(temp_dir.path(), true).unwrap();
+        assert_eq!(packages[0].effective_license.as_deref(), Some("MIT"));
+        assert_eq!(packages[0].license_classifiers, vec!["License :: OSI Approved :: MIT License"]);
+        assert_eq!(packages[0].metadata_source, "metadata.json");
+        assert_eq!(packages[0].detection_status, DetectionStatus::Found);
+    }
+
+    #[test]
+    fn test_display_name() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dist_info = temp_dir.path().join("Typing_Extensions-4.9.0.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(dist_info.join("METADATA"), "Name: typing-extensions\nLicense: PSF-2.0\n").unwrap();
+
+        let packages = extract_all_licenses(temp_dir.path(), true).unwrap();
+        let display = |mode: DisplayName| {
+            let mut packages = packages.clone();
+            mode.apply(&mut packages);
+            packages[0].name.clone()
+        };
+        assert_eq!(display(DisplayName::DistInfo), "Typing_Extensions");
+        assert_eq!(display(DisplayName::Pypi), "typing-extensions");
+        assert_eq!(display(DisplayName::Normalized), "typing-extensions");
+
+        // Exceptions match whichever spelling is displayed
+        let policy: crate::policy::LicensePolicy = toml::from_str(
+            "name = \"p\"\nexceptions = [{ name = \"typing_extensions\", reason = \"ok\" }]"
+        ).unwrap();
+        assert!(policy.is_exception("Typing-Extensions", None).is_some());
+    }
+
+    #[test]
+    fn test_detection_status_for_each_case() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path().join("site-packages");
+        let dist_info = |name: &str, metadata: Option<&str>| {
+            let dir = site_packages.join(format!("{}-1.0.dist-info", name));
+            fs::create_dir_all(&dir).unwrap();
+            if let Some(metadata) = metadata {
+                fs::write(dir.join("METADATA"), metadata).unwrap();
+            }
+        };
+        dist_info("found", Some("Name: found\nLicense: MIT\n"));
+        dist_info("nolicense", Some("Name: nolicense\nLicense: UNKNOWN\n"));
+        dist_info("nometadata", None);
+
+        let uv_lock_path = temp_dir.path().join("uv.lock");
+        let mut lock = String::from("version = 1\n");
+        for name in ["found", "nolicense", "nometadata", "missing"] {
+            lock.push_str(&format!("\n[[package]]\nname = \"{}\"\nversion = \"1.0\"\n", name));
+        }
+        fs::write(&uv_lock_path, lock).unwrap();
+
+        let packages = extract_licenses_from_uv_lock(Some(uv_lock_path), Some(site_packages), true, false).unwrap();
+        let status = |name: &str| packages.iter().find(|p| p.name == name).unwrap().detection_status;
+        assert_eq!(status("found"), DetectionStatus::Found);
+        assert_eq!(status("nolicense"), DetectionStatus::NoLicenseField);
+        assert_eq!(status("nometadata"), DetectionStatus::MetadataMissing);
+        assert_eq!(status("missing"), DetectionStatus::NotInstalled);
+
+        // The verbatim License: value survives even when it is a placeholder
+        let raw = |name: &str| packages.iter().find(|p| p.name == name).unwrap().raw_license.clone();
+        assert_eq!(raw("found").as_deref(), Some("MIT"));
+        assert_eq!(raw("nolicense").as_deref(), Some("UNKNOWN"));
+        assert_eq!(raw("missing"), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_site_packages_from_layout_with_broken_interpreter() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join(".venv");
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        // Absolute symlink from another Docker stage
+        std::os::unix::fs::symlink("/nonexistent/python3.12", venv.join("bin/python")).unwrap();
+        fs::create_dir_all(venv.join("lib/python3.12/site-packages")).unwrap();
+
+        let found = find_site_packages_path(Some(venv.clone())).unwrap();
+        let venv = fs::canonicalize(&venv).unwrap();
+        assert_eq!(found, venv.join("lib/python3.12/site-packages"));
+
+        // Two interpreters: ambiguous unless a version is given
+        fs::create_dir_all(venv.join("lib/python3.11/site-packages")).unwrap();
+        let error = find_site_packages_path(Some(venv.clone())).unwrap_err().to_string();
+        assert!(error.contains("python3.11, python3.12"), "{}", error);
+        assert_eq!(
+            find_site_packages_path_for(Some(venv.clone()), Some("3.11")).unwrap(),
+            venv.join("lib/python3.11/site-packages")
+        );
+        assert!(find_site_packages_path_for(Some(venv.clone()), Some("3.9")).is_err());
+
+        // Missing paths are a clear error; `..` components resolve away
+        let missing = find_site_packages_path(Some(venv.join("missing"))).unwrap_err().to_string();
+        assert!(missing.contains("does not exist"), "{}", missing);
+        let relative = find_site_packages_path(Some(venv.join("lib/python3.12/../python3.12/site-packages"))).unwrap();
+        assert_eq!(relative, venv.join("lib/python3.12/site-packages"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_site_packages_through_symlink_and_parent_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let venv = temp_dir.path().join("real-venv");
+        fs::create_dir_all(venv.join("lib/python3.12/site-packages")).unwrap();
+        fs::create_dir_all(venv.join("bin")).unwrap();
+        std::os::unix::fs::symlink(&venv, temp_dir.path().join("link")).unwrap();
+
+        let expected = fs::canonicalize(venv.join("lib/python3.12/site-packages")).unwrap();
+        let found = find_site_packages_path(Some(temp_dir.path().join("link/bin/.."))).unwrap();
+        assert_eq!(found, expected);
+        assert!(!found.components().any(|c| c == std::path::Component::ParentDir));
+        assert!(!found.starts_with(temp_dir.path().join("link")));
+    }
 }
@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use super::text::identify_license_text;
+use super::{DetectionStatus, PackageLicense};
+
+/// `metadata_source` of licenses resolved from a `Project-URL: License` link
+pub const PROJECT_URL_SOURCE: &str = "project-url";
+/// License files larger than this are not downloaded
+const MAX_LICENSE_BYTES: u32 = 1_000_000;
+
+/// Fetched license texts: `$XDG_CACHE_HOME/py-license-auditor/license-urls`,
+/// falling back to `~/.cache/py-license-auditor/license-urls`
+pub fn license_url_cache_dir() -> Option<PathBuf> {
+    let cache_home = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+
+    Some(cache_home.join("py-license-auditor").join("license-urls"))
+}
+
+/// GitHub/GitLab "blob" pages render HTML; fetch the raw file instead
+fn raw_url(url: &str) -> String {
+    if let Some(path) = url.strip_prefix("https://github.com/") {
+        if let Some((repo, file)) = path.split_once("/blob/") {
+            return format!("https://raw.githubusercontent.com/{}/{}", repo, file);
+        }
+    }
+    if url.starts_with("https://gitlab.com/") && url.contains("/-/blob/") {
+        return url.replacen("/-/blob/", "/-/raw/", 1);
+    }
+    url.to_string()
+}
+
+fn cache_path(url: &str) -> Option<PathBuf> {
+    let digest = Sha256::digest(url.as_bytes());
+    let key: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    license_url_cache_dir().map(|dir| dir.join(format!("{}.txt", key)))
+}
+
+fn download(url: &str) -> Result<String> {
+    let output = crate::curl::command()
+        .args(["--fail", "--location", "--max-time", "30"])
+        .arg("--max-filesize")
+        .arg(MAX_LICENSE_BYTES.to_string())
+        .arg("--")
+        .arg(url)
+        .output()
+        .context("Failed to run curl (is it installed?)")?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The license text behind `url`, from the cache when it was fetched before
+pub fn fetch_license_text(url: &str) -> Result<String> {
+    crate::curl::ensure_http_url(url)?;
+    let cache = cache_path(url);
+    if let Some(text) = cache.as_ref().and_then(|path| fs::read_to_string(path).ok()) {
+        return Ok(text);
+    }
+
+    let text = download(&raw_url(url))?;
+    if let Some(path) = &cache {
+        // A cache that cannot be written only costs a re-download next time
+        if path.parent().is_some_and(|dir| fs::create_dir_all(dir).is_ok()) {
+            let _ = fs::write(path, &text);
+        }
+    }
+    Ok(text)
+}
+
+/// `--resolve-license-urls`: for packages with no license in their metadata, fetch the
+/// `Project-URL: License` file and classify it. Returns how many were resolved.
+pub fn resolve_license_urls(packages: &mut [PackageLicense]) -> usize {
+    let mut resolved = 0;
+    for package in packages.iter_mut().filter(|package| package.effective_license.is_none() && package.license_classifiers.is_empty()) {
+        let Some(url) = package.license_url.clone() else { continue };
+        match fetch_license_text(&url) {
+            Ok(text) => match identify_license_text(&text) {
+                Some((license, confidence)) => {
+                    package.effective_license = Some(license);
+                    package.license_confidence = confidence;
+                    package.metadata_source = PROJECT_URL_SOURCE.to_string();
+                    package.detection_status = DetectionStatus::Found;
+                    resolved += 1;
+                }
+                None => package.warnings.push(format!("license file at {} was not recognized", url)),
+            },
+            Err(error) => package.warnings.push(format!("could not fetch license from {}: {:#}", url, error)),
+        }
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_license_urls() {
+        assert_eq!(
+            raw_url("https://github.com/acme/widget/blob/main/LICENSE"),
+            "https://raw.githubusercontent.com/acme/widget/main/LICENSE"
+        );
+        assert_eq!(
+            raw_url("https://gitlab.com/acme/widget/-/blob/v1.0/LICENSE.txt"),
+            "https://gitlab.com/acme/widget/-/raw/v1.0/LICENSE.txt"
+        );
+        assert_eq!(raw_url("https://example.com/LICENSE"), "https://example.com/LICENSE");
+        assert!(fetch_license_text("file:///etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use super::fingerprint::match_license_text;
+use super::{DetectionStatus, PackageLicense};
+
+/// Distinctive phrases of common license texts (whitespace-collapsed, lowercase),
+/// most specific first (LGPL/AGPL before GPL, BSD-3 before BSD-2).
+const LICENSE_SIGNATURES: &[(&str, &[&str])] = &[
+    // GNU texts mention each other, so match the title line ("<name> Version N")
+    ("AGPL-3.0", &["gnu affero general public license version 3"]),
+    ("LGPL-3.0", &["gnu lesser general public license version 3"]),
+    ("LGPL-2.1", &["gnu lesser general public license version 2.1"]),
+    ("GPL-3.0", &["gnu general public license version 3"]),
+    ("GPL-2.0", &["gnu general public license version 2"]),
+    ("MPL-2.0", &["mozilla public license version 2.0"]),
+    ("Apache-2.0", &["apache license version 2.0"]),
+    ("BSD-3-Clause", &["redistribution and use in source and binary forms", "neither the name"]),
+    ("BSD-2-Clause", &["redistribution and use in source and binary forms"]),
+    ("MIT", &["permission is hereby granted, free of charge", "the above copyright notice"]),
+    ("ISC", &["permission to use, copy, modify, and/or distribute this software for any purpose"]),
+    ("Unlicense", &["this is free and unencumbered software released into the public domain"]),
+    ("CC0-1.0", &["cc0 1.0 universal"]),
+    ("PSF-2.0", &["python software foundation license"]),
+];
+
+/// Guess the SPDX id of a license text, see `identify_license_text`
+pub fn classify_license_text(text: &str) -> Option<String> {
+    identify_license_text(text).map(|(id, _)| id)
+}
+
+/// SPDX id of a license text plus the match confidence: full texts are compared with
+/// the bundled reference texts; fragments fall back to distinctive phrases (no confidence)
+pub fn identify_license_text(text: &str) -> Option<(String, Option<f64>)> {
+    if let Some(matched) = match_license_text(text) {
+        return Some((matched.license, Some(matched.confidence)));
+    }
+
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    LICENSE_SIGNATURES
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|phrase| normalized.contains(phrase)))
+        .map(|(id, _)| (id.to_string(), None))
+}
+
+/// Inventory a folder of vendored license files (one file per dependency, file
+/// name = package name; a sub-directory holding LICENSE*/COPYING* also works)
+pub fn extract_licenses_from_dir(license_dir: &Path, include_unknown: bool) -> Result<Vec<PackageLicense>> {
+    let entries = fs::read_dir(license_dir)
+        .with_context(|| format!("Failed to read license directory: {}", license_dir.display()))?;
+
+    let mut packages = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let (name, license_file) = if path.is_dir() {
+            let Some(file) = find_license_file(&path)? else { continue };
+            (path.file_name().map(|n| n.to_string_lossy().to_string()), file)
+        } else {
+            (path.file_stem().map(|n| n.to_string_lossy().to_string()), path.clone())
+        };
+        let Some(name) = name.filter(|name| !name.starts_with('.')) else { continue };
+
+        let text = fs::read(&license_file)
+            .with_context(|| format!("Failed to read {}", license_file.display()))?;
+        let identified = identify_license_text(&String::from_utf8_lossy(&text));
+        let license_confidence = identified.as_ref().and_then(|(_, confidence)| *confidence);
+        let effective_license = identified.map(|(id, _)| id);
+        let detection_status = if effective_license.is_some() {
+            DetectionStatus::Found
+        } else {
+            DetectionStatus::NoLicenseField
+        };
+
+        if include_unknown || effective_license.is_some() {
+            packages.push(PackageLicense {
+                name,
+                version: None,
+                effective_license,
+                license_classifiers: vec![],
+                metadata_source: "license-dir".to_string(),
+                license_expression: None,
+                warnings: vec![],
+                annotation: None,
+                detection_status,
+                raw_license: None,
+                metadata_name: None,
+                license_confidence,
+                license_url: None,
+            });
+        }
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
+
+pub(super) fn find_license_file(dir: &Path) -> Result<Option<std::path::PathBuf>> {
+    let mut candidates: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_uppercase()).unwrap_or_default();
+            name.starts_with("LICENSE") || name.starts_with("LICENCE") || name.starts_with("COPYING")
+        })
+        .collect();
+    candidates.sort();
+    Ok(candidates.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIT_TEXT: &str = "MIT License\n\nCopyright (c) 2024 Someone\n\nPermission is hereby granted, free of charge, to any person obtaining a copy\nof this software ... The above copyright notice and this permission notice shall be included";
+
+    #[test]
+    fn test_classify_license_text() {
+        assert_eq!(classify_license_text(MIT_TEXT), Some("MIT".to_string()));
+        assert_eq!(
+            classify_license_text("Apache License\n  Version 2.0, January 2004\n http://www.apache.org/licenses/"),
+            Some("Apache-2.0".to_string())
+        );
+        assert_eq!(
+            classify_license_text("GNU LESSER GENERAL PUBLIC LICENSE\n Version 3, 29 June 2007"),
+            Some("LGPL-3.0".to_string())
+        );
+        // GPL-3.0 refers to the AGPL in its body; the title decides
+        assert_eq!(
+            classify_license_text("GNU GENERAL PUBLIC LICENSE\n Version 3, 29 June 2007\n ... version 3 of the GNU Affero General Public License"),
+            Some("GPL-3.0".to_string())
+        );
+        assert_eq!(classify_license_text("All rights reserved."), None);
+    }
+
+    #[test]
+    fn test_extract_licenses_from_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("six.txt"), MIT_TEXT).unwrap();
+        fs::write(temp_dir.path().join("internal"), "Proprietary. All rights reserved.").unwrap();
+        let nested = temp_dir.path().join("gplthing");
+        fs::create_dir(&nested).unwrap();
+        fs::write(nested.join("COPYING"), "GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007").unwrap();
+
+        let packages = extract_licenses_from_dir(temp_dir.path(), false).unwrap();
+        let found: Vec<_> = packages.iter().map(|p| (p.name.as_str(), p.effective_license.as_deref())).collect();
+        assert_eq!(found, vec![("gplthing", Some("GPL-3.0")), ("six", Some("MIT"))]);
+
+        assert_eq!(extract_licenses_from_dir(temp_dir.path(), true).unwrap().len(), 3);
+    }
+}
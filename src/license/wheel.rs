@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use super::extractor::{package_from_metadata, parse_name_version_from_dist_info};
+use super::PackageLicense;
+
+/// Name and version from a wheel file name (PEP 427:
+/// `{distribution}-{version}(-{build tag})?-{python}-{abi}-{platform}.whl`)
+pub fn parse_wheel_filename(file_name: &str) -> Option<(String, String)> {
+    let stem = file_name.strip_suffix(".whl")?;
+    let parts: Vec<&str> = stem.split('-').collect();
+    if !(5..=6).contains(&parts.len()) {
+        return None;
+    }
+    // Wheel names escape '-' as '_'
+    Some((parts[0].replace('_', "-"), parts[1].to_string()))
+}
+
+fn open_archive(path: &Path, kind: &str) -> Result<zip::ZipArchive<fs::File>> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    zip::ZipArchive::new(file)
+        .with_context(|| format!("Not a valid {} archive: {}", kind, path.display()))
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<fs::File>, name: &str, path: &Path) -> Result<String> {
+    let mut content = String::new();
+    archive.by_name(name)?
+        .read_to_string(&mut content)
+        .with_context(|| format!("Failed to read {} in {}", name, path.display()))?;
+    Ok(content)
+}
+
+/// Read `<name>.dist-info/METADATA` from a wheel archive
+pub fn read_wheel_metadata(wheel_path: &Path) -> Result<Option<String>> {
+    let mut archive = open_archive(wheel_path, "wheel")?;
+
+    let Some(metadata_name) = archive.file_names()
+        .find(|name| {
+            name.strip_suffix("/METADATA")
+                .is_some_and(|dir| dir.ends_with(".dist-info") && !dir.contains('/'))
+        })
+        .map(str::to_string)
+    else {
+        return Ok(None);
+    };
+
+    read_entry(&mut archive, &metadata_name, wheel_path).map(Some)
+}
+
+/// Extract license information from a single `.whl` file without installing it
+pub fn extract_from_wheel(wheel_path: &Path) -> Result<Option<PackageLicense>> {
+    let file_name = wheel_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let Some((name, version)) = parse_wheel_filename(&file_name) else {
+        anyhow::bail!("Invalid wheel file name: {}", file_name);
+    };
+    let Some(content) = read_wheel_metadata(wheel_path)? else {
+        return Ok(None);
+    };
+    Ok(Some(package_from_metadata(name, Some(version), &content, "wheel")))
+}
+
+/// Audit a download cache (`pip download -d wheels/`) offline: every `.whl` in `wheels_dir`
+pub fn extract_licenses_from_wheels(wheels_dir: &Path, include_unknown: bool) -> Result<Vec<PackageLicense>> {
+    let entries = fs::read_dir(wheels_dir)
+        .with_context(|| format!("Failed to read wheels directory: {}", wheels_dir.display()))?;
+
+    let mut packages = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "whl") {
+            continue;
+        }
+        if let Some(package) = extract_from_wheel(&path)? {
+            if include_unknown || package.effective_license.is_some() || !package.license_classifiers.is_empty() {
+                packages.push(package);
+            }
+        }
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+    Ok(packages)
+}
+
+/// Audit a zipapp (`.pyz`) or zipped site-packages: every `*.dist-info/METADATA` in the archive
+pub fn extract_licenses_from_zip(zip_path: &Path, include_unknown: bool) -> Result<Vec<PackageLicense>> {
+    let mut archive = open_archive(zip_path, "zip")?;
+    let metadata_names: Vec<String> = archive.file_names()
+        .filter(|name| name.strip_suffix("/METADATA").is_some_and(|dir| dir.ends_with(".dist-info")))
+        .map(str::to_string)
+        .collect();
+
+    let mut packages = Vec::new();
+    for metadata_name in metadata_names {
+        let dist_info = metadata_name.trim_end_matches("/METADATA");
+        let dist_info = dist_info.rsplit('/').next().unwrap_or(dist_info);
+        let (name, version) = parse_name_version_from_dist_info(Path::new(dist_info))?;
+        let content = read_entry(&mut archive, &metadata_name, zip_path)?;
+
+        let package = package_from_metadata(name, version, &content, "zip");
+        if include_unknown || package.effective_license.is_some() || !package.license_classifiers.is_empty() {
+            packages.push(package);
+        }
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_wheel(path: &Path, dist_info: &str, metadata: &str) {
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("pkg/__init__.py", options).unwrap();
+        writer.start_file(format!("{}/METADATA", dist_info), options).unwrap();
+        writer.write_all(metadata.as_bytes()).unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_parse_wheel_filename() {
+        assert_eq!(
+            parse_wheel_filename("typing_extensions-4.12.2-py3-none-any.whl"),
+            Some(("typing-extensions".to_string(), "4.12.2".to_string()))
+        );
+        assert_eq!(
+            parse_wheel_filename("numpy-2.0.0-1-cp312-cp312-manylinux_2_17_x86_64.whl"),
+            Some(("numpy".to_string(), "2.0.0".to_string()))
+        );
+        assert_eq!(parse_wheel_filename("numpy-2.0.0.tar.gz"), None);
+        assert_eq!(parse_wheel_filename("broken-1.0.whl"), None);
+    }
+
+    #[test]
+    fn test_extract_licenses_from_wheels() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_wheel(
+            &temp_dir.path().join("requests-2.31.0-py3-none-any.whl"),
+            "requests-2.31.0.dist-info",
+            "Name: requests\nVersion: 2.31.0\nLicense: Apache 2.0\n",
+        );
+        write_wheel(
+            &temp_dir.path().join("six-1.16.0-py2.py3-none-any.whl"),
+            "six-1.16.0.dist-info",
+            "Name: six\nClassifier: License :: OSI Approved :: MIT License\n",
+        );
+        fs::write(temp_dir.path().join("README.txt"), "not a wheel").unwrap();
+
+        let packages = extract_licenses_from_wheels(temp_dir.path(), false).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!((packages[0].name.as_str(), packages[0].version.as_deref()), ("requests", Some("2.31.0")));
+        assert_eq!(packages[0].effective_license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(packages[0].metadata_source, "wheel");
+        assert_eq!(packages[1].effective_license.as_deref(), Some("MIT"));
+    }
+
+    #[test]
+    fn test_extract_licenses_from_zip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let zipapp = temp_dir.path().join("app.pyz");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&zipapp).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("__main__.py", options).unwrap();
+        for (dist_info, metadata) in [
+            ("requests-2.31.0.dist-info", "Name: requests\nLicense: Apache 2.0\n"),
+            ("_vendor/six-1.16.0.dist-info", "Name: six\nClassifier: License :: OSI Approved :: MIT License\n"),
+            ("mystery-0.1.dist-info", "Name: mystery\n"),
+        ] {
+            writer.start_file(format!("{}/METADATA", dist_info), options).unwrap();
+            writer.write_all(metadata.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let packages = extract_licenses_from_zip(&zipapp, false).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!((packages[0].name.as_str(), packages[0].version.as_deref()), ("requests", Some("2.31.0")));
+        assert_eq!(packages[0].metadata_source, "zip");
+        assert_eq!(packages[1].effective_license.as_deref(), Some("MIT"));
+        assert_eq!(extract_licenses_from_zip(&zipapp, true).unwrap().len(), 3);
+    }
+}
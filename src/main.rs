@@ -5,34 +5,31 @@ mod cli;
 mod commands;
 
 use cli::{Cli, Commands};
-use commands::{handle_check, handle_init, handle_fix, handle_config};
+use commands::{handle_check, handle_init, handle_fix, handle_config, handle_exceptions, ConfigActions, FixOptions};
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Check { 
-            path, 
-            format, 
-            output, 
-            include_unknown, 
-            quiet, 
-            verbose, 
-            exit_zero 
-        } => {
+        Commands::Check(mut args) => {
             // Global options override subcommand options
-            let final_quiet = cli.quiet || quiet;
-            let final_verbose = cli.verbose || verbose;
-            handle_check(path, format, output, include_unknown, final_quiet, final_verbose, exit_zero)
+            args.quiet = cli.quiet || args.quiet;
+            args.verbose = cli.verbose || args.verbose;
+            handle_check(*args)
         }
         Commands::Init { policy } => {
             handle_init(policy, cli.quiet)
         }
-        Commands::Fix { path, dry_run, interactive, format } => {
-            handle_fix(path, dry_run, interactive, format, cli.quiet)
+        Commands::Fix { path, dry_run, interactive, format, dedup_exceptions, from_report, levels, version_scope, include_forbidden } => {
+            let options = FixOptions { dry_run, interactive, dedup_exceptions, from_report, levels, version_scope, include_forbidden };
+            handle_fix(path, format, options, cli.quiet)
         }
-        Commands::Config { show, validate } => {
-            handle_config(show, validate, cli.quiet)
+        Commands::Exceptions { import, dry_run } => {
+            handle_exceptions(&import, dry_run, cli.quiet)
+        }
+        Commands::Config { show, validate, show_policy, diff_policy, exception_coverage, migrate_exceptions, test_policy, format } => {
+            let actions = ConfigActions { show, validate, show_policy, diff_policy, exception_coverage, migrate_exceptions, test_policy };
+            handle_config(actions, format, cli.quiet)
         }
     }
 }
@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use anyhow::Result;
+use crate::license::{extract_license_info, LicenseReport, LicenseSummary, PackageLicense};
+use super::license_list::distinct_licenses;
+use crate::policy::ViolationLevel;
+use crate::uv_lock::{normalize_package_name, LockedPackageInfo};
+
+pub const CSV_HEADER: &str = "name,version,license,license_classifiers,metadata_source";
+
+/// A column selectable with `--columns`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvColumn {
+    Name,
+    Version,
+    License,
+    Classifiers,
+    Osi,
+    Status,
+    Source,
+    Size,
+    Direct,
+}
+
+/// The standard column set (matches `CSV_HEADER`)
+pub const DEFAULT_COLUMNS: &[CsvColumn] = &[
+    CsvColumn::Name,
+    CsvColumn::Version,
+    CsvColumn::License,
+    CsvColumn::Classifiers,
+    CsvColumn::Source,
+];
+
+const COLUMN_NAMES: &[(&str, CsvColumn)] = &[
+    ("name", CsvColumn::Name),
+    ("version", CsvColumn::Version),
+    ("license", CsvColumn::License),
+    ("classifiers", CsvColumn::Classifiers),
+    ("osi", CsvColumn::Osi),
+    ("status", CsvColumn::Status),
+    ("source", CsvColumn::Source),
+    ("size", CsvColumn::Size),
+    ("direct", CsvColumn::Direct),
+];
+
+impl CsvColumn {
+    fn header(&self) -> &'static str {
+        match self {
+            CsvColumn::Name => "name",
+            CsvColumn::Version => "version",
+            CsvColumn::License => "license",
+            CsvColumn::Classifiers => "license_classifiers",
+            CsvColumn::Osi => "osi_approved",
+            CsvColumn::Status => "status",
+            CsvColumn::Source => "metadata_source",
+            CsvColumn::Size => "size",
+            CsvColumn::Direct => "direct",
+        }
+    }
+
+    /// Whether the column needs uv.lock data
+    pub fn needs_lock(&self) -> bool {
+        matches!(self, CsvColumn::Size | CsvColumn::Direct)
+    }
+}
+
+/// Parse a comma-separated `--columns` list; unknown names are an error
+pub fn parse_columns(list: &str) -> Result<Vec<CsvColumn>> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            COLUMN_NAMES
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name))
+                .map(|(_, column)| *column)
+                .ok_or_else(|| {
+                    let known: Vec<&str> = COLUMN_NAMES.iter().map(|(known, _)| *known).collect();
+                    anyhow::anyhow!("Unknown CSV column '{}' (expected: {})", name, known.join(", "))
+                })
+        })
+        .collect()
+}
+
+/// Parse `--delimiter`: a single character, or `tab` for TSV
+pub fn parse_delimiter(value: &str) -> Result<char> {
+    if value.eq_ignore_ascii_case("tab") || value == "\\t" {
+        return Ok('\t');
+    }
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(delimiter), None) if !matches!(delimiter, '"' | '\n' | '\r') => Ok(delimiter),
+        _ => anyhow::bail!("Invalid CSV delimiter '{}' (expected a single character other than a quote, or 'tab')", value),
+    }
+}
+
+/// Column selection plus the uv.lock facts needed by `size` / `direct`
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub columns: Vec<CsvColumn>,
+    pub locked: HashMap<String, LockedPackageInfo>,
+    /// Field separator (`--delimiter`, default comma)
+    pub delimiter: char,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { columns: DEFAULT_COLUMNS.to_vec(), locked: HashMap::new(), delimiter: ',' }
+    }
+}
+
+/// Quote a field per RFC 4180 when it contains a comma, quote or line break
+pub fn csv_escape(field: &str) -> String {
+    csv_escape_with(field, ',')
+}
+
+/// Quote a field when it contains `delimiter`, a quote or a line break
+pub fn csv_escape_with(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn csv_header(options: &CsvOptions) -> String {
+    options.columns.iter().map(|column| column.header()).collect::<Vec<_>>().join(&options.delimiter.to_string())
+}
+
+fn status(package: &PackageLicense, violation: Option<&ViolationLevel>) -> &'static str {
+    match violation {
+        Some(ViolationLevel::Forbidden) => "forbidden",
+        Some(ViolationLevel::ReviewRequired) => "review_required",
+        Some(ViolationLevel::Unknown) => "unknown",
+        _ if package.effective_license.is_none() => "unknown",
+        _ => "ok",
+    }
+}
+
+/// One CSV row; `violation` is the package's policy outcome, if checked
+pub fn csv_row(package: &PackageLicense, violation: Option<&ViolationLevel>, options: &CsvOptions) -> String {
+    let locked = options.locked.get(&normalize_package_name(&package.name));
+    options
+        .columns
+        .iter()
+        .map(|column| {
+            let field = match column {
+                CsvColumn::Name => package.name.clone(),
+                CsvColumn::Version => package.version.clone().unwrap_or_default(),
+                CsvColumn::License => package.effective_license.clone().unwrap_or_default(),
+                CsvColumn::Classifiers => package.license_classifiers.join("; "),
+                CsvColumn::Osi => extract_license_info(package).is_osi_approved.to_string(),
+                CsvColumn::Status => status(package, violation).to_string(),
+                CsvColumn::Source => package.metadata_source.clone(),
+                CsvColumn::Size => locked.and_then(|info| info.size).map(|size| size.to_string()).unwrap_or_default(),
+                CsvColumn::Direct => locked.map(|info| info.direct.to_string()).unwrap_or_default(),
+            };
+            csv_escape_with(&field, options.delimiter)
+        })
+        .collect::<Vec<_>>()
+        .join(&options.delimiter.to_string())
+}
+
+fn violation_level<'a>(package: &PackageLicense, report: &'a LicenseReport) -> Option<&'a ViolationLevel> {
+    report.violations.as_ref()?.details.iter()
+        .find(|v| v.package_name == package.name && v.package_version == package.version)
+        .map(|v| &v.violation_level)
+}
+
+pub fn format_csv_output(report: &LicenseReport, options: &CsvOptions) -> String {
+    let mut output = String::new();
+    output.push_str(&csv_header(options));
+    output.push('\n');
+    for package in report.sorted_packages() {
+        output.push_str(&csv_row(package, violation_level(package, report), options));
+        output.push('\n');
+    }
+    output
+}
+
+/// `--summary-csv`: one row per license with its package count and OSI status
+pub fn format_summary_csv(summary: &LicenseSummary, delimiter: char) -> String {
+    let separator = delimiter.to_string();
+    let mut output = ["license", "count", "osi_approved"].join(&separator);
+    output.push('\n');
+    for entry in distinct_licenses(summary) {
+        let count = entry.packages.to_string();
+        let fields = [csv_escape_with(entry.license, delimiter), count, entry.osi_approved.to_string()];
+        output.push_str(&fields.join(&separator));
+        output.push('\n');
+    }
+    output
+}
+
+/// Header of `format_violations_csv`
+pub const VIOLATIONS_CSV_HEADER: [&str; 6] = ["package", "version", "license", "level", "rule", "message"];
+
+/// `--only-violations`: one row per policy violation, for ticketing systems
+pub fn format_violations_csv(report: &LicenseReport, delimiter: char) -> String {
+    let separator = delimiter.to_string();
+    let mut output = VIOLATIONS_CSV_HEADER.join(&separator);
+    output.push('\n');
+    for violation in report.violations.iter().flat_map(|violations| &violations.details) {
+        let level = match violation.violation_level {
+            ViolationLevel::Forbidden => "forbidden",
+            ViolationLevel::ReviewRequired => "review_required",
+            ViolationLevel::Unknown => "unknown",
+            ViolationLevel::Allowed => "allowed",
+        };
+        let fields = [
+            violation.package_name.as_str(),
+            violation.package_version.as_deref().unwrap_or(""),
+            violation.license.as_deref().unwrap_or(""),
+            level,
+            violation.matched_rule.as_deref().unwrap_or(""),
+            violation.message.as_str(),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_escape_with(field, delimiter)).collect();
+        output.push_str(&row.join(&separator));
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::create_report;
+
+    fn requests() -> PackageLicense {
+        PackageLicense {
+            name: "requests".to_string(),
+            version: Some("2.31.0".to_string()),
+            effective_license: Some("Apache-2.0".to_string()),
+            license_classifiers: vec!["License :: OSI Approved :: Apache Software License".to_string()],
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("MIT"), "MIT");
+        assert_eq!(csv_escape("MIT, Apache"), "\"MIT, Apache\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_csv_row() {
+        let options = CsvOptions::default();
+        assert_eq!(csv_header(&options), CSV_HEADER);
+        assert_eq!(
+            csv_row(&requests(), None, &options),
+            "requests,2.31.0,Apache-2.0,License :: OSI Approved :: Apache Software License,METADATA"
+        );
+    }
+
+    #[test]
+    fn test_selected_columns() {
+        let mut locked = HashMap::new();
+        locked.insert("requests".to_string(), LockedPackageInfo { size: Some(1000), direct: true });
+        let options = CsvOptions { columns: parse_columns("status, name,osi,size,direct").unwrap(), locked, ..Default::default() };
+
+        assert_eq!(csv_header(&options), "status,name,osi_approved,size,direct");
+        assert_eq!(csv_row(&requests(), None, &options), "ok,requests,true,1000,true");
+        assert_eq!(
+            csv_row(&requests(), Some(&ViolationLevel::ReviewRequired), &options),
+            "review_required,requests,true,1000,true"
+        );
+
+        let error = parse_columns("name,colour").unwrap_err().to_string();
+        assert!(error.contains("Unknown CSV column 'colour'"));
+    }
+
+    #[test]
+    fn test_delimiter() {
+        let mut package = requests();
+        package.effective_license = Some("MIT; Apache-2.0".to_string());
+        let options = CsvOptions { delimiter: parse_delimiter(";").unwrap(), ..Default::default() };
+        assert_eq!(csv_header(&options), "name;version;license;license_classifiers;metadata_source");
+        assert_eq!(
+            csv_row(&package, None, &options),
+            "requests;2.31.0;\"MIT; Apache-2.0\";License :: OSI Approved :: Apache Software License;METADATA"
+        );
+
+        let tsv = CsvOptions { delimiter: parse_delimiter("tab").unwrap(), ..Default::default() };
+        assert_eq!(csv_row(&requests(), None, &tsv).split('\t').count(), 5);
+        assert!(parse_delimiter("\"").is_err());
+        assert!(parse_delimiter(";;").is_err());
+    }
+
+    #[test]
+    fn test_csv_rows_sorted_independent_of_scan_order() {
+        let package = |name: &str, version: &str| PackageLicense { name: name.to_string(), version: Some(version.to_string()), ..requests() };
+        let scanned = vec![package("zipp", "3.17"), package("Click", "8.1"), package("attrs", "23.1"), package("click", "7.0")];
+        let mut reordered = scanned.clone();
+        reordered.reverse();
+
+        let first = format_csv_output(&create_report(scanned), &CsvOptions::default());
+        let second = format_csv_output(&create_report(reordered), &CsvOptions::default());
+        assert_eq!(first.as_bytes(), second.as_bytes());
+        let names: Vec<&str> = first.lines().skip(1).map(|line| line.split(',').next().unwrap()).collect();
+        assert_eq!(names, vec!["attrs", "click", "Click", "zipp"]);
+    }
+
+    #[test]
+    fn test_summary_csv() {
+        let mut summary = crate::license::LicenseSummary::default();
+        summary.license_types.osi_approved.insert("MIT".to_string(), 3);
+        summary.license_types.non_osi.insert("Custom, Inc.".to_string(), 1);
+
+        assert_eq!(
+            format_summary_csv(&summary, ','),
+            "license,count,osi_approved\n\"Custom, Inc.\",1,false\nMIT,3,true\n"
+        );
+        assert_eq!(format_summary_csv(&summary, ';').lines().nth(2), Some("MIT;3;true"));
+    }
+
+    #[test]
+    fn test_violations_csv() {
+        use crate::policy::{Violation, ViolationSummary};
+
+        let mut report = crate::license::create_report(vec![requests()]);
+        report.violations = Some(ViolationSummary::from_details(vec![Violation {
+            package_name: "gpl-thing".to_string(),
+            package_version: Some("1.0".to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: ViolationLevel::Forbidden,
+            matched_rule: Some("GPL-*".to_string()),
+            message: "License 'GPL-3.0' is forbidden, see \"policy\", section 2".to_string(),
+            ..Default::default()
+        }]));
+
+        assert_eq!(
+            format_violations_csv(&report, ','),
+            concat!(
+                "package,version,license,level,rule,message\n",
+                "gpl-thing,1.0,GPL-3.0,forbidden,GPL-*,\"License 'GPL-3.0' is forbidden, see \"\"policy\"\", section 2\"\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::io::Write;
+use std::time::Instant;
+use serde::Serialize;
+use anyhow::Result;
+use crate::license::{get_effective_license, LicenseReport, PackageLicense};
+
+/// One line of the `check --events` log
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event<'a> {
+    Package {
+        name: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        version: Option<&'a str>,
+        license: Option<String>,
+        elapsed_ms: u128,
+    },
+    Summary {
+        total_packages: usize,
+        with_license: usize,
+        without_license: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        violations: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        errors: Option<usize>,
+        elapsed_ms: u128,
+    },
+}
+
+/// `check --events`: an NDJSON event log written while the audit runs. Each line is
+/// flushed immediately so CI tooling tailing the stream sees progress live.
+pub struct EventLog<W: Write> {
+    writer: W,
+    started: Instant,
+}
+
+impl<W: Write> EventLog<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer, started: Instant::now() }
+    }
+
+    /// A package was extracted
+    pub fn package(&mut self, package: &PackageLicense) -> Result<()> {
+        let license = package.effective_license.clone().or_else(|| get_effective_license(package));
+        let elapsed_ms = self.started.elapsed().as_millis();
+        self.emit(&Event::Package { name: &package.name, version: package.version.as_deref(), license, elapsed_ms })
+    }
+
+    /// The audit finished: totals and violation counts of the final report
+    pub fn summary(&mut self, report: &LicenseReport) -> Result<()> {
+        let summary = &report.summary;
+        self.emit(&Event::Summary {
+            total_packages: summary.total_packages,
+            with_license: summary.with_license,
+            without_license: summary.without_license,
+            violations: report.violations.as_ref().map(|violations| violations.total),
+            errors: report.violations.as_ref().map(|violations| violations.errors),
+            elapsed_ms: self.started.elapsed().as_millis(),
+        })
+    }
+
+    fn emit(&mut self, event: &Event) -> Result<()> {
+        writeln!(self.writer, "{}", serde_json::to_string(event)?)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::create_report;
+
+    #[test]
+    fn test_event_log() {
+        let package = PackageLicense {
+            name: "requests".to_string(),
+            version: Some("2.31.0".to_string()),
+            metadata_source: "METADATA".to_string(),
+            license_expression: Some("Apache-2.0".to_string()),
+            ..Default::default()
+        };
+
+        let mut written = Vec::new();
+        let mut log = EventLog::new(&mut written);
+        log.package(&package).unwrap();
+        log.summary(&create_report(vec![package])).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(written).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "package");
+        assert_eq!(lines[0]["license"], "Apache-2.0");
+        assert!(lines[0]["elapsed_ms"].is_u64());
+        assert_eq!(lines[1]["event"], "summary");
+        assert_eq!(lines[1]["total_packages"], 1);
+        assert!(lines[1].get("violations").is_none());
+    }
+}
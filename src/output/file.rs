@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Whether an output path asks for gzip by its `.gz` extension
+pub fn is_gzip_path(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "gz")
+}
+
+/// Write a report to `path`, gzip-compressed (default level 6) when `gzip` is set
+pub fn write_output_file(path: &Path, content: &str, gzip: bool) -> Result<()> {
+    if !gzip {
+        return fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()));
+    }
+
+    let file = fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    encoder.finish().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_gzip_output() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("report.json.gz");
+        assert!(is_gzip_path(&path));
+        assert!(!is_gzip_path(&temp_dir.path().join("report.json")));
+
+        let content = "{\"packages\": []}\n".repeat(100);
+        write_output_file(&path, &content, true).unwrap();
+        let compressed = fs::read(&path).unwrap();
+        assert!(compressed.starts_with(&[0x1f, 0x8b]));
+        assert!(compressed.len() < content.len());
+
+        let mut decoded = String::new();
+        GzDecoder::new(compressed.as_slice()).read_to_string(&mut decoded).unwrap();
+        assert_eq!(decoded, content);
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use std::fs;
+use std::path::Path;
+use crate::license::LicenseReport;
+use crate::policy::ViolationLevel;
+use super::junit::{escape_xml, find_violation};
+
+const DEFAULT_TITLE: &str = "License Report";
+
+/// An image inlined into the report header
+#[derive(Debug, Clone, PartialEq)]
+pub struct HtmlLogo {
+    pub mime: &'static str,
+    pub data: Vec<u8>,
+}
+
+/// Branding for shared reports (`--report-title`, `--report-logo`)
+#[derive(Debug, Clone, Default)]
+pub struct HtmlOptions {
+    pub title: Option<String>,
+    pub logo: Option<HtmlLogo>,
+}
+
+/// Read a logo for inlining; only PNG and SVG are accepted
+pub fn load_logo(path: &Path) -> Result<HtmlLogo> {
+    let data = fs::read(path).with_context(|| format!("Failed to read logo: {}", path.display()))?;
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_lowercase);
+
+    let mime = match extension.as_deref() {
+        Some("png") if data.starts_with(b"\x89PNG\r\n\x1a\n") => "image/png",
+        Some("svg") if String::from_utf8_lossy(&data).contains("<svg") => "image/svg+xml",
+        Some("png" | "svg") => anyhow::bail!("{} does not contain a valid {} image", path.display(), extension.unwrap_or_default()),
+        _ => anyhow::bail!("Unsupported logo type: {} (expected .png or .svg)", path.display()),
+    };
+    Ok(HtmlLogo { mime, data })
+}
+
+/// Render the report as a standalone HTML page with one table row per package
+pub fn format_html_output(report: &LicenseReport, options: &HtmlOptions) -> String {
+    let title = escape_xml(options.title.as_deref().unwrap_or(DEFAULT_TITLE));
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str(&format!("<title>{}</title>\n", title));
+    output.push_str(concat!(
+        "<style>\n",
+        "body { font-family: sans-serif; margin: 2em; }\n",
+        "header { display: flex; align-items: center; gap: 1em; }\n",
+        "header img { max-height: 64px; }\n",
+        "table { border-collapse: collapse; }\n",
+        "th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }\n",
+        ".forbidden { background: #fdd; }\n",
+        ".review_required, .unknown { background: #ffd; }\n",
+        "</style>\n</head>\n<body>\n<header>\n",
+    ));
+    if let Some(logo) = &options.logo {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&logo.data);
+        output.push_str(&format!("<img src=\"data:{};base64,{}\" alt=\"logo\">\n", logo.mime, encoded));
+    }
+    output.push_str(&format!("<h1>{}</h1>\n</header>\n", title));
+
+    let summary = &report.summary;
+    output.push_str(&format!(
+        "<p>{} packages: {} with licenses, {} unknown",
+        summary.total_packages, summary.with_license, summary.without_license
+    ));
+    if let Some(violations) = &report.violations {
+        output.push_str(&format!(", {} violations", violations.total));
+    }
+    output.push_str("</p>\n");
+
+    output.push_str("<table>\n<tr><th>Package</th><th>Version</th><th>License</th><th>Status</th></tr>\n");
+    for package in &report.packages {
+        let status = match find_violation(package, report).map(|v| &v.violation_level) {
+            Some(ViolationLevel::Forbidden) => "forbidden",
+            Some(ViolationLevel::ReviewRequired) => "review_required",
+            Some(ViolationLevel::Unknown) => "unknown",
+            _ if package.effective_license.is_none() => "unknown",
+            _ => "ok",
+        };
+        output.push_str(&format!(
+            "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            status,
+            escape_xml(&package.name),
+            escape_xml(package.version.as_deref().unwrap_or("")),
+            escape_xml(package.effective_license.as_deref().unwrap_or("")),
+            status
+        ));
+    }
+    output.push_str("</table>\n");
+    if let Some(footer) = &report.footer {
+        output.push_str("<footer>\n");
+        if let Some(text) = &footer.footer_text {
+            output.push_str(&format!("<p>{}</p>\n", escape_xml(text.trim_end())));
+        }
+        if let Some(url) = &footer.policy_url {
+            output.push_str(&format!("<p><a href=\"{}\">License policy</a></p>\n", escape_xml(url)));
+        }
+        output.push_str("</footer>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::create_report;
+
+    #[test]
+    fn test_html_branding() {
+        let report = create_report(vec![]);
+        let plain = format_html_output(&report, &HtmlOptions::default());
+        assert!(plain.contains("<h1>License Report</h1>"));
+        assert!(!plain.contains("<img"));
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let svg = temp_dir.path().join("logo.svg");
+        fs::write(&svg, "<svg xmlns=\"http://www.w3.org/2000/svg\"/>").unwrap();
+        let options = HtmlOptions { title: Some("Acme <Audit>".to_string()), logo: Some(load_logo(&svg).unwrap()) };
+        let branded = format_html_output(&report, &options);
+        assert!(branded.contains("<title>Acme &lt;Audit&gt;</title>"));
+        assert!(branded.contains("<img src=\"data:image/svg+xml;base64,PHN2Zy"));
+
+        let jpg = temp_dir.path().join("logo.jpg");
+        fs::write(&jpg, "not really").unwrap();
+        assert!(load_logo(&jpg).unwrap_err().to_string().contains("Unsupported logo type"));
+        let fake_png = temp_dir.path().join("logo.png");
+        fs::write(&fake_png, "<svg/>").unwrap();
+        assert!(load_logo(&fake_png).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use anyhow::Result;
+use std::str::FromStr;
+
+/// Indentation of pretty-printed JSON (`--indent 4`, `--indent tab`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsonIndent {
+    Spaces(usize),
+    Tab,
+}
+
+impl Default for JsonIndent {
+    /// serde_json's own default
+    fn default() -> Self {
+        JsonIndent::Spaces(2)
+    }
+}
+
+impl FromStr for JsonIndent {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.eq_ignore_ascii_case("tab") {
+            return Ok(JsonIndent::Tab);
+        }
+        match value.parse::<usize>() {
+            Ok(width) if width <= 16 => Ok(JsonIndent::Spaces(width)),
+            _ => Err(format!("expected a number of spaces (0-16) or 'tab', got '{}'", value)),
+        }
+    }
+}
+
+/// Pretty-print `value` with the given indentation
+pub fn to_json_string<T: Serialize>(value: &T, indent: JsonIndent) -> Result<String> {
+    let indent = match indent {
+        JsonIndent::Spaces(width) => " ".repeat(width),
+        JsonIndent::Tab => "\t".to_string(),
+    };
+    let mut buffer = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+    value.serialize(&mut serializer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_indent() {
+        let value = json!({"packages": [{"name": "requests"}]});
+        assert_eq!(to_json_string(&value, JsonIndent::default()).unwrap(), serde_json::to_string_pretty(&value).unwrap());
+        assert_eq!(
+            to_json_string(&value, "tab".parse().unwrap()).unwrap(),
+            "{\n\t\"packages\": [\n\t\t{\n\t\t\t\"name\": \"requests\"\n\t\t}\n\t]\n}"
+        );
+        assert!(to_json_string(&value, "4".parse().unwrap()).unwrap().contains("\n    \"packages\""));
+        assert!("wide".parse::<JsonIndent>().is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use serde::Serialize;
+use anyhow::Result;
+use crate::license::{LicenseReport, LicenseSummary, PackageLicense};
+use crate::policy::{Violation, ViolationLevel, ViolationSummary};
+
+/// Final line of JSON-lines output
+#[derive(Serialize)]
+struct SummaryLine<'a> {
+    summary: &'a LicenseSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    violations: Option<&'a ViolationSummary>,
+}
+
+pub fn package_line(package: &PackageLicense) -> Result<String> {
+    Ok(serde_json::to_string(package)?)
+}
+
+pub fn summary_line(summary: &LicenseSummary, violations: Option<&ViolationSummary>) -> Result<String> {
+    Ok(serde_json::to_string(&SummaryLine { summary, violations })?)
+}
+
+/// One JSON object per package, followed by a single summary object
+pub fn format_json_lines_output(report: &LicenseReport) -> Result<String> {
+    let mut output = String::new();
+    for package in report.sorted_packages() {
+        output.push_str(&package_line(package)?);
+        output.push('\n');
+    }
+    output.push_str(&summary_line(&report.summary, report.violations.as_ref())?);
+    output.push('\n');
+    Ok(output)
+}
+
+/// One violation with every field inlined, for log shippers (Elastic, Splunk)
+#[derive(Serialize)]
+pub struct FlatViolation<'a> {
+    pub package: &'a str,
+    pub version: Option<&'a str>,
+    pub license: Option<&'a str>,
+    pub level: &'static str,
+    pub matched_rule: Option<&'a str>,
+    pub message: &'a str,
+    pub timestamp: &'a str,
+}
+
+fn level_name(level: &ViolationLevel) -> &'static str {
+    match level {
+        ViolationLevel::Allowed => "allowed",
+        ViolationLevel::ReviewRequired => "review_required",
+        ViolationLevel::Forbidden => "forbidden",
+        ViolationLevel::Unknown => "unknown",
+    }
+}
+
+pub fn flatten_violation<'a>(violation: &'a Violation, timestamp: &'a str) -> FlatViolation<'a> {
+    FlatViolation {
+        package: &violation.package_name,
+        version: violation.package_version.as_deref(),
+        license: violation.license.as_deref(),
+        level: level_name(&violation.violation_level),
+        matched_rule: violation.matched_rule.as_deref(),
+        message: &violation.message,
+        timestamp,
+    }
+}
+
+/// `--flatten-violations`: a JSON array, or one object per line with `lines`
+pub fn format_flat_violations(report: &LicenseReport, timestamp: &str, lines: bool) -> Result<String> {
+    let flat: Vec<FlatViolation> = report.violations
+        .iter()
+        .flat_map(|violations| &violations.details)
+        .map(|violation| flatten_violation(violation, timestamp))
+        .collect();
+
+    if !lines {
+        return Ok(serde_json::to_string_pretty(&flat)?);
+    }
+    let mut output = String::new();
+    for violation in &flat {
+        output.push_str(&serde_json::to_string(violation)?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::create_report;
+
+    #[test]
+    fn test_flat_violations() {
+        let mut report = create_report(vec![]);
+        report.violations = Some(ViolationSummary::from_details(vec![Violation {
+            package_name: "gpl-lib".to_string(),
+            package_version: Some("1.0".to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: ViolationLevel::Forbidden,
+            matched_rule: Some("exact: GPL-3.0".to_string()),
+            message: "License 'GPL-3.0' is forbidden".to_string(),
+            ..Default::default()
+        }]));
+
+        let output = format_flat_violations(&report, "2024-05-01T00:00:00Z", true).unwrap();
+        assert_eq!(
+            output,
+            "{\"package\":\"gpl-lib\",\"version\":\"1.0\",\"license\":\"GPL-3.0\",\"level\":\"forbidden\",\
+             \"matched_rule\":\"exact: GPL-3.0\",\"message\":\"License 'GPL-3.0' is forbidden\",\
+             \"timestamp\":\"2024-05-01T00:00:00Z\"}\n"
+        );
+
+        let array: serde_json::Value = serde_json::from_str(&format_flat_violations(&report, "t", false).unwrap()).unwrap();
+        assert_eq!(array[0]["level"], "forbidden");
+    }
+}
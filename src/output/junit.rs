@@ -0,0 +1,166 @@
+use crate::license::{LicenseReport, PackageLicense};
+use crate::policy::{Violation, ViolationLevel};
+
+/// How review-required and unknown-license violations are reported in JUnit
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JunitWarnings {
+    /// Emit `<skipped>` (visible, but does not fail the suite)
+    #[default]
+    Skipped,
+    /// Emit `<error>`
+    Error,
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub(crate) fn find_violation<'a>(package: &PackageLicense, report: &'a LicenseReport) -> Option<&'a Violation> {
+    report.violations.as_ref()?.details.iter().find(|v|
+        v.package_name == package.name &&
+        v.package_version.as_deref() == package.version.as_deref()
+    )
+}
+
+/// Render the report as JUnit XML: one `<testcase>` per package, with
+/// classname = license and name = package
+pub fn format_junit_output(report: &LicenseReport, warnings: JunitWarnings) -> String {
+    let mut cases = String::new();
+    let (mut failures, mut errors, mut skipped) = (0, 0, 0);
+
+    for package in &report.packages {
+        let license = package.effective_license.as_deref().unwrap_or("unknown");
+        let version = package.version.as_deref().unwrap_or("unknown");
+        let open = format!(
+            "    <testcase classname=\"{}\" name=\"{}\">\n      <system-out>version: {}</system-out>\n",
+            escape_xml(license),
+            escape_xml(&package.name),
+            escape_xml(version)
+        );
+
+        let result = match find_violation(package, report) {
+            Some(violation) => {
+                let rule = violation.matched_rule.as_deref().unwrap_or("");
+                let level = format!("{:?}", violation.violation_level);
+                let tag = match (&violation.violation_level, warnings) {
+                    (ViolationLevel::Forbidden, _) => {
+                        failures += 1;
+                        "failure"
+                    }
+                    (ViolationLevel::Allowed, _) => "",
+                    (_, JunitWarnings::Error) => {
+                        errors += 1;
+                        "error"
+                    }
+                    (_, JunitWarnings::Skipped) => {
+                        skipped += 1;
+                        "skipped"
+                    }
+                };
+                if tag.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "      <{} message=\"{}\" type=\"{}\">{}</{}>\n",
+                        tag,
+                        escape_xml(&violation.message),
+                        level,
+                        escape_xml(rule),
+                        tag
+                    )
+                }
+            }
+            None => String::new(),
+        };
+
+        cases.push_str(&open);
+        cases.push_str(&result);
+        cases.push_str("    </testcase>\n");
+    }
+
+    let tests = report.packages.len();
+    let counts = format!(
+        "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\"",
+        tests, failures, errors, skipped
+    );
+
+    let mut output = String::new();
+    output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(&format!("<testsuites name=\"py-license-auditor\" {}>\n", counts));
+    output.push_str(&format!("  <testsuite name=\"license-audit\" {}>\n", counts));
+    output.push_str(&cases);
+    output.push_str("  </testsuite>\n");
+    output.push_str("</testsuites>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::create_report;
+    use crate::policy::ViolationSummary;
+
+    fn package(name: &str, license: Option<&str>) -> PackageLicense {
+        PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0.0".to_string()),
+            effective_license: license.map(|l| l.to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn violation(name: &str, level: ViolationLevel) -> Violation {
+        Violation {
+            package_name: name.to_string(),
+            package_version: Some("1.0.0".to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: level,
+            matched_rule: Some("pattern: GPL-*".to_string()),
+            message: "License 'GPL-3.0' is <forbidden>".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn sample_report() -> LicenseReport {
+        let mut report = create_report(vec![
+            package("requests", Some("Apache-2.0")),
+            package("gpl-lib", Some("GPL-3.0")),
+            package("mystery", None),
+        ]);
+        report.violations = Some(ViolationSummary::from_details(vec![
+            violation("gpl-lib", ViolationLevel::Forbidden),
+            violation("mystery", ViolationLevel::Unknown),
+        ]));
+        report
+    }
+
+    #[test]
+    fn test_junit_counts_and_escaping() {
+        let output = format_junit_output(&sample_report(), JunitWarnings::Skipped);
+
+        assert!(output.contains("tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\""));
+        assert!(output.contains("<testcase classname=\"Apache-2.0\" name=\"requests\">"));
+        assert!(output.contains("<failure message=\"License &apos;GPL-3.0&apos; is &lt;forbidden&gt;\" type=\"Forbidden\">"));
+        assert!(output.contains("<skipped "));
+    }
+
+    #[test]
+    fn test_junit_warnings_as_errors() {
+        let output = format_junit_output(&sample_report(), JunitWarnings::Error);
+
+        assert!(output.contains("tests=\"3\" failures=\"1\" errors=\"1\" skipped=\"0\""));
+        assert!(output.contains("<error message="));
+    }
+}
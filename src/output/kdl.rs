@@ -0,0 +1,128 @@
+use indexmap::IndexMap;
+use crate::license::LicenseReport;
+
+/// Quote and escape a KDL string value
+pub(crate) fn kdl_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{08}' => escaped.push_str("\\b"),
+            '\u{0C}' => escaped.push_str("\\f"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// `key="value"` for present values; absent values are omitted
+fn property(key: &str, value: Option<&str>) -> String {
+    value.map(|value| format!(" {}={}", key, kdl_string(value))).unwrap_or_default()
+}
+
+fn license_counts(output: &mut String, node: &str, counts: &IndexMap<String, usize>) {
+    if counts.is_empty() {
+        return;
+    }
+    output.push_str(&format!("    {} {{\n", node));
+    for (license, count) in counts {
+        output.push_str(&format!("        license {} count={}\n", kdl_string(license), count));
+    }
+    output.push_str("    }\n");
+}
+
+/// Render the report as KDL: one `package` node per package, then `summary`
+/// and (when checked) `violations` nodes
+pub fn format_kdl_output(report: &LicenseReport) -> String {
+    let mut output = String::new();
+
+    for package in &report.packages {
+        output.push_str(&format!(
+            "package name={}{}{} source={}",
+            kdl_string(&package.name),
+            property("version", package.version.as_deref()),
+            property("license", package.effective_license.as_deref()),
+            kdl_string(&package.metadata_source),
+        ));
+        if package.license_classifiers.is_empty() {
+            output.push('\n');
+        } else {
+            output.push_str(" {\n");
+            for classifier in &package.license_classifiers {
+                output.push_str(&format!("    classifier {}\n", kdl_string(classifier)));
+            }
+            output.push_str("}\n");
+        }
+    }
+
+    let summary = &report.summary;
+    output.push_str(&format!(
+        "summary total={} with-license={} without-license={} {{\n",
+        summary.total_packages, summary.with_license, summary.without_license
+    ));
+    license_counts(&mut output, "osi-approved", &summary.license_types.osi_approved);
+    license_counts(&mut output, "non-osi", &summary.license_types.non_osi);
+    output.push_str("}\n");
+
+    if let Some(violations) = &report.violations {
+        output.push_str(&format!(
+            "violations total={} errors={} warnings={}",
+            violations.total, violations.errors, violations.warnings
+        ));
+        if violations.details.is_empty() {
+            output.push('\n');
+        } else {
+            output.push_str(" {\n");
+            for violation in &violations.details {
+                output.push_str(&format!(
+                    "    violation package={}{}{} level={}{} message={}\n",
+                    kdl_string(&violation.package_name),
+                    property("version", violation.package_version.as_deref()),
+                    property("license", violation.license.as_deref()),
+                    kdl_string(&format!("{:?}", violation.violation_level)),
+                    property("rule", violation.matched_rule.as_deref()),
+                    kdl_string(&violation.message),
+                ));
+            }
+            output.push_str("}\n");
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::{create_report, PackageLicense};
+
+    #[test]
+    fn test_kdl_string_escaping() {
+        assert_eq!(kdl_string("plain"), "\"plain\"");
+        assert_eq!(kdl_string("say \"hi\"\\\n"), "\"say \\\"hi\\\"\\\\\\n\"");
+        assert_eq!(kdl_string("bell\u{07}"), "\"bell\\u{7}\"");
+    }
+
+    #[test]
+    fn test_kdl_package_and_summary_nodes() {
+        let report = create_report(vec![PackageLicense {
+            name: "requests".to_string(),
+            version: Some("2.31.0".to_string()),
+            effective_license: Some("Apache-2.0".to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }]);
+
+        let output = format_kdl_output(&report);
+        assert!(output.starts_with("package name=\"requests\" version=\"2.31.0\" license=\"Apache-2.0\" source=\"METADATA\"\n"));
+        assert!(output.contains("summary total=1 with-license=1 without-license=0 {\n"));
+        assert!(output.contains("        license \"Apache-2.0\" count=1\n"));
+    }
+}
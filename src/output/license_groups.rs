@@ -0,0 +1,89 @@
+use serde::Serialize;
+use anyhow::Result;
+use crate::policy::{group_violations_by_license, Violation};
+
+/// One violating license with the packages that use it
+#[derive(Debug, Serialize)]
+struct GroupEntry<'a> {
+    license: &'a str,
+    violation_type: &'a str,
+    count: usize,
+    packages: Vec<PackageRef<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageRef<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+}
+
+/// `--group-by-license`: each violating license once with its affected packages, or a JSON array with `json`
+pub fn format_license_groups(violations: &[Violation], json: bool) -> Result<String> {
+    let groups = group_violations_by_license(violations.to_vec());
+    let entries: Vec<GroupEntry> = groups
+        .iter()
+        .map(|group| GroupEntry {
+            license: &group.normalized_license,
+            violation_type: &group.violation_type,
+            count: group.packages.len(),
+            packages: group
+                .packages
+                .iter()
+                .map(|violation| PackageRef { name: &violation.package_name, version: violation.package_version.as_deref() })
+                .collect(),
+        })
+        .collect();
+    if json {
+        return Ok(serde_json::to_string_pretty(&entries)?);
+    }
+    if entries.is_empty() {
+        return Ok("No license violations".to_string());
+    }
+    let mut lines = Vec::new();
+    for entry in &entries {
+        let noun = if entry.count == 1 { "package" } else { "packages" };
+        lines.push(format!("{} — {} ({} {})", entry.license, entry.violation_type, entry.count, noun));
+        for package in &entry.packages {
+            match package.version {
+                Some(version) => lines.push(format!("  • {} {}", package.name, version)),
+                None => lines.push(format!("  • {}", package.name)),
+            }
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::ViolationLevel;
+
+    fn violation(name: &str, license: &str, level: ViolationLevel) -> Violation {
+        Violation {
+            package_name: name.to_string(),
+            package_version: Some("1.0".to_string()),
+            license: Some(license.to_string()),
+            violation_level: level,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_license_groups() {
+        let violations = vec![
+            violation("gpl-b", "GPL-3.0", ViolationLevel::Forbidden),
+            violation("lgpl", "LGPL-2.1", ViolationLevel::ReviewRequired),
+            violation("gpl-a", "GPL-3.0", ViolationLevel::Forbidden),
+        ];
+
+        assert_eq!(
+            format_license_groups(&violations, false).unwrap(),
+            "GPL-3.0 — Forbidden license (2 packages)\n  • gpl-b 1.0\n  • gpl-a 1.0\nLGPL-2.1 — Review required (1 package)\n  • lgpl 1.0"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&format_license_groups(&violations, true).unwrap()).unwrap();
+        assert_eq!(json[0]["count"], 2);
+        assert_eq!(json[1]["packages"], serde_json::json!([{"name": "lgpl", "version": "1.0"}]));
+    }
+}
@@ -0,0 +1,56 @@
+use serde::Serialize;
+use anyhow::Result;
+use crate::license::LicenseSummary;
+
+/// One distinct license and how many packages use it
+#[derive(Debug, Serialize, PartialEq)]
+pub struct LicenseCount<'a> {
+    pub license: &'a str,
+    pub packages: usize,
+    pub osi_approved: bool,
+}
+
+/// Distinct licenses from the summary, sorted by name
+pub fn distinct_licenses(summary: &LicenseSummary) -> Vec<LicenseCount<'_>> {
+    let types = &summary.license_types;
+    let osi = types.osi_approved.iter().map(|(license, count)| (license, count, true));
+    let non_osi = types.non_osi.iter().map(|(license, count)| (license, count, false));
+
+    let mut licenses: Vec<LicenseCount> = osi
+        .chain(non_osi)
+        .map(|(license, &packages, osi_approved)| LicenseCount { license, packages, osi_approved })
+        .collect();
+    licenses.sort_by(|a, b| a.license.cmp(b.license));
+    licenses
+}
+
+/// `--list-licenses`: "license<TAB>count" lines, or a JSON array with `json`
+pub fn format_license_list(summary: &LicenseSummary, json: bool) -> Result<String> {
+    let licenses = distinct_licenses(summary);
+    if json {
+        return Ok(serde_json::to_string_pretty(&licenses)?);
+    }
+    Ok(licenses
+        .iter()
+        .map(|entry| format!("{}\t{}", entry.license, entry.packages))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_license_list() {
+        let mut summary = LicenseSummary::default();
+        summary.license_types.osi_approved.insert("MIT".to_string(), 3);
+        summary.license_types.osi_approved.insert("Apache-2.0".to_string(), 1);
+        summary.license_types.non_osi.insert("GPL-3.0".to_string(), 2);
+
+        assert_eq!(format_license_list(&summary, false).unwrap(), "Apache-2.0\t1\nGPL-3.0\t2\nMIT\t3");
+
+        let json: serde_json::Value = serde_json::from_str(&format_license_list(&summary, true).unwrap()).unwrap();
+        assert_eq!(json[1], serde_json::json!({"license": "GPL-3.0", "packages": 2, "osi_approved": false}));
+    }
+}
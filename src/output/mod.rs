@@ -0,0 +1,519 @@
+use crate::license::{DetectionStatus, LicenseReport, OsiFilter, PackageLicense};
+use crate::i18n::Locale;
+use crate::policy::ViolationLevel;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+pub mod file;
+pub mod html;
+pub mod json;
+pub mod junit;
+pub mod csv;
+pub mod events;
+pub mod json_lines;
+pub mod kdl;
+pub mod license_groups;
+pub mod license_list;
+pub mod policy_doc;
+pub mod pr_comment;
+
+pub use file::{is_gzip_path, write_output_file};
+pub use html::{format_html_output, load_logo, HtmlOptions};
+pub use json::{to_json_string, JsonIndent};
+pub use junit::{format_junit_output, JunitWarnings};
+pub use csv::format_csv_output;
+pub use events::EventLog;
+pub use json_lines::format_json_lines_output;
+pub use kdl::format_kdl_output;
+pub use license_groups::format_license_groups;
+pub use license_list::format_license_list;
+pub use policy_doc::format_policy_markdown;
+pub use pr_comment::{format_new_violations_comment, format_pr_comment};
+
+/// Remove ANSI escape sequences (CSI such as `\x1b[1;31m`, plus two-byte escapes)
+pub fn strip_ansi(text: &str) -> String {
+    enum State {
+        Text,
+        Escape,
+        Csi,
+    }
+
+    let mut state = State::Text;
+    let mut output = String::with_capacity(text.len());
+    for c in text.chars() {
+        state = match state {
+            State::Text if c == '\x1b' => State::Escape,
+            State::Text => {
+                output.push(c);
+                State::Text
+            }
+            State::Escape if c == '[' => State::Csi,
+            State::Escape => State::Text,
+            // CSI parameters run until a final byte in 0x40..=0x7E
+            State::Csi if ('\x40'..='\x7e').contains(&c) => State::Text,
+            State::Csi => State::Csi,
+        };
+    }
+    output
+}
+
+/// Terminal columns taken by `text`, ignoring ANSI escapes and counting wide characters as two
+fn display_width(text: &str) -> usize {
+    strip_ansi(text).width()
+}
+
+fn format_with_padding(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PackageStatus {
+    Ok,
+    Unknown,      // No license info
+    Violation,    // Policy violation
+}
+
+fn get_package_status(package: &PackageLicense, report: &LicenseReport) -> PackageStatus {
+    // Check if no license info (including inferred from classifiers)
+    if package.effective_license.is_none() {
+        return PackageStatus::Unknown;
+    }
+    
+    // Check for policy violations
+    if let Some(violations) = &report.violations {
+        if violations.details.iter().any(|v| 
+            v.package_name == package.name && 
+            v.package_version.as_deref() == package.version.as_deref() &&
+            v.license.is_some()
+        ) {
+            return PackageStatus::Violation;
+        }
+    }
+    
+    PackageStatus::Ok
+}
+
+/// One-word status used by the plain and compact outputs
+fn status_label(package: &PackageLicense, report: &LicenseReport) -> &'static str {
+    match junit::find_violation(package, report).map(|v| &v.violation_level) {
+        Some(ViolationLevel::Forbidden) => "FORBIDDEN",
+        Some(ViolationLevel::ReviewRequired) => "REVIEW",
+        Some(ViolationLevel::Unknown) => "UNKNOWN",
+        _ if package.effective_license.is_none() => "UNKNOWN",
+        _ => "OK",
+    }
+}
+
+/// `N packages, N with license, N unknown[, N violations]`
+fn summary_line(report: &LicenseReport, locale: Locale) -> String {
+    let summary = &report.summary;
+    let mut line = locale.format("summary.line", &[&summary.total_packages, &summary.with_license, &summary.without_license]);
+    if let Some(violations) = &report.violations {
+        line.push_str(&locale.format("summary.line_violations", &[&violations.total]));
+    }
+    line.push('\n');
+    line
+}
+
+/// Plain-text output for logs and email: `name version license [STATUS]` per package,
+/// then one summary line in `locale`. No colors, emoji or box drawing.
+pub fn format_plain_output(report: &LicenseReport, locale: Locale) -> String {
+    let mut output = String::new();
+    for package in &report.packages {
+        output.push_str(&format!(
+            "{} {} {} [{}]\n",
+            package.name,
+            package.version.as_deref().unwrap_or("-"),
+            package.effective_license.as_deref().unwrap_or("-"),
+            status_label(package, report)
+        ));
+    }
+    output.push_str(&summary_line(report, locale));
+    output
+}
+
+/// The configured footer text and policy link, after a blank line
+fn table_footer(report: &LicenseReport, locale: Locale) -> String {
+    let Some(footer) = &report.footer else { return String::new() };
+    let mut output = String::from("\n");
+    if let Some(text) = &footer.footer_text {
+        output.push_str(&format!("{}\n", text.trim_end()));
+    }
+    if let Some(url) = &footer.policy_url {
+        output.push_str(&format!("{}\n", locale.format("footer.policy", &[url])));
+    }
+    output
+}
+
+/// `--format table --compact`: borderless `package  version  license  STATUS` columns,
+/// issues only unless `verbose`, then the summary line
+pub fn format_compact_output(report: &LicenseReport, verbose: bool, locale: Locale) -> String {
+    let rows: Vec<[&str; 4]> = report
+        .packages
+        .iter()
+        .map(|package| [
+            package.name.as_str(),
+            package.version.as_deref().unwrap_or("-"),
+            package.effective_license.as_deref().unwrap_or("-"),
+            status_label(package, report),
+        ])
+        .filter(|row| verbose || row[3] != "OK")
+        .collect();
+
+    let mut widths = [0; 3];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    let mut output = String::new();
+    for [name, version, license, status] in &rows {
+        let (color, id) = match *status {
+            "OK" => ("32", "status.ok"),
+            "FORBIDDEN" => ("31", "status.forbidden"),
+            "REVIEW" => ("33", "status.review"),
+            _ => ("33", "status.unknown_upper"),
+        };
+        output.push_str(&format!(
+            "{}  {}  {}  \x1b[{}m{}\x1b[0m\n",
+            format_with_padding(name, widths[0]),
+            format_with_padding(version, widths[1]),
+            format_with_padding(license, widths[2]),
+            color,
+            locale.text(id)
+        ));
+    }
+    output.push_str(&summary_line(report, locale));
+    output.push_str(&table_footer(report, locale));
+    output
+}
+
+/// Split packages into listed ones and per-license counts of collapsed ones
+fn collapse_packages(packages: &[PackageLicense], collapse: &[String]) -> (Vec<PackageLicense>, Vec<(String, usize)>) {
+    let mut listed = Vec::new();
+    let mut collapsed: Vec<(String, usize)> = collapse.iter().map(|license| (license.clone(), 0)).collect();
+
+    for package in packages {
+        let license = package.effective_license.as_deref().unwrap_or("");
+        match collapsed.iter_mut().find(|(name, _)| name.eq_ignore_ascii_case(license)) {
+            Some((_, count)) => *count += 1,
+            None => listed.push(package.clone()),
+        }
+    }
+
+    collapsed.retain(|(_, count)| *count > 0);
+    (listed, collapsed)
+}
+
+pub fn format_table_output(report: &LicenseReport, verbose: bool, collapse: &[String], locale: Locale) -> String {
+    let mut output = String::new();
+    
+    // Summary header
+    let total = report.summary.total_packages;
+    let with_license = report.summary.with_license;
+    let without_license = report.summary.without_license;
+    let violations = report.violations.as_ref().map(|v| v.total).unwrap_or(0);
+    
+    output.push_str(&format!("{}\n", locale.format("summary.header", &[&total])));
+    output.push_str(&format!("{}\n\n", locale.format("summary.counts", &[&with_license, &without_license, &violations])));
+
+    if let Some(filter) = report.filter {
+        let label = match filter {
+            OsiFilter::Osi => locale.text("filter.osi"),
+            OsiFilter::NonOsi => locale.text("filter.non_osi"),
+        };
+        output.push_str(&format!("{}\n\n", locale.format("filter.showing", &[&label, &report.packages.len(), &total])));
+    }
+    
+    let (listed, collapsed) = collapse_packages(&report.packages, collapse);
+    if !collapsed.is_empty() {
+        let lines: Vec<String> = collapsed.iter().map(|(license, count)| format!("{} ×{}", license, count)).collect();
+        output.push_str(&format!("{}\n\n", locale.format("table.collapsed", &[&lines.join(", ")])));
+    }
+    
+    if verbose {
+        // Show all packages (collapsed licenses are counted above)
+        output.push_str(&format!("{}\n", locale.text("table.all_packages")));
+        output.push_str(&format_package_table(&listed, true, Some(report), locale));
+
+        let annotated: Vec<&PackageLicense> = listed.iter().filter(|p| p.annotation.is_some()).collect();
+        if !annotated.is_empty() {
+            output.push_str(&format!("\n{}\n", locale.text("table.annotations")));
+            for package in annotated {
+                output.push_str(&format!("   • {}: {}\n", package.name, package.annotation.as_deref().unwrap_or_default()));
+            }
+        }
+    } else {
+        // Show only issues
+        let issues = get_issue_packages(report, locale);
+        if !issues.is_empty() {
+            output.push_str(&format!("{}\n", locale.text("table.issues_found")));
+            output.push_str(&format_issue_table(&issues, locale));
+        } else {
+            output.push_str(&format!("{}\n", locale.text("table.no_issues")));
+        }
+        
+        let not_installed = issues.iter().filter(|(p, _)| p.detection_status == DetectionStatus::NotInstalled).count();
+        if not_installed > 0 {
+            output.push_str(&format!("\n{}\n", locale.format("table.not_installed_hint", &[&not_installed])));
+        }
+
+        if !verbose && report.packages.len() > issues.len() {
+            output.push_str(&format!("\n{}\n", locale.format("table.verbose_hint", &[&report.packages.len()])));
+        }
+    }
+
+    let with_warnings: Vec<&PackageLicense> = report.packages.iter().filter(|p| !p.warnings.is_empty()).collect();
+    if !with_warnings.is_empty() {
+        output.push_str(&format!("\n{}\n", locale.format("table.metadata_warnings", &[&with_warnings.len()])));
+        for package in with_warnings {
+            for warning in &package.warnings {
+                output.push_str(&format!("   • {}: {}\n", package.name, warning));
+            }
+        }
+    }
+
+    output.push_str(&table_footer(report, locale));
+    output
+}
+
+const TABLE_TOP: &str = "┌─────────────────┬─────────┬─────────────┬─────────────────┐\n";
+const TABLE_RULE: &str = "├─────────────────┼─────────┼─────────────┼─────────────────┤\n";
+const TABLE_BOTTOM: &str = "└─────────────────┴─────────┴─────────────┴─────────────────┘\n";
+
+/// One table line; cells are padded by display width, so wide (CJK) text stays aligned
+fn table_row(name: &str, version: &str, license: &str, last: &str) -> String {
+    format!(
+        "│ {} │ {} │ {} │ {} │\n",
+        format_with_padding(&truncate(name, 15), 15),
+        format_with_padding(&truncate(version, 7), 7),
+        format_with_padding(&truncate(license, 11), 11),
+        format_with_padding(last, 15)
+    )
+}
+
+fn table_header(last_column: &'static str, locale: Locale) -> String {
+    let header = table_row(
+        locale.text("column.package"),
+        locale.text("column.version"),
+        locale.text("column.license"),
+        locale.text(last_column),
+    );
+    format!("{}{}{}", TABLE_TOP, header, TABLE_RULE)
+}
+
+fn format_package_table(packages: &[PackageLicense], show_status: bool, report: Option<&LicenseReport>, locale: Locale) -> String {
+    if packages.is_empty() {
+        return format!("{}\n", locale.text("table.no_packages"));
+    }
+    
+    let mut output = table_header(if show_status { "column.status" } else { "column.problem" }, locale);
+    
+    // Table rows
+    for package in packages {
+        let version = package.version.as_deref().unwrap_or("unknown");
+        let license = package.effective_license.as_deref().unwrap_or("(unknown)");
+        
+        let last = if show_status {
+            match get_package_status(package, report.unwrap_or(&LicenseReport::default())) {
+                PackageStatus::Ok => format!("\x1b[32m{}\x1b[0m", locale.text("status.ok")),            // Green
+                PackageStatus::Unknown => format!("\x1b[33m{}\x1b[0m", locale.text("status.unknown")),  // Yellow
+                PackageStatus::Violation => format!("\x1b[31m{}\x1b[0m", locale.text("status.problem")), // Red
+            }
+        } else {
+            let issue = if package.effective_license.is_none() { "issue.no_license" } else { "issue.requires_review" };
+            truncate(locale.text(issue), 15)
+        };
+        output.push_str(&table_row(&package.name, version, license, &last));
+    }
+    
+    output.push_str(TABLE_BOTTOM);
+    output
+}
+
+fn format_issue_table(issues: &[(PackageLicense, String)], locale: Locale) -> String {
+    if issues.is_empty() {
+        return format!("{}\n", locale.text("table.no_issues"));
+    }
+    
+    let mut output = table_header("column.problem", locale);
+    
+    for (package, issue) in issues {
+        let version = package.version.as_deref().unwrap_or("unknown");
+        let license = package.effective_license.as_deref().unwrap_or("(unknown)");
+        output.push_str(&table_row(&package.name, version, license, &truncate(issue, 15)));
+    }
+    
+    output.push_str(TABLE_BOTTOM);
+    output
+}
+
+fn get_issue_packages(report: &LicenseReport, locale: Locale) -> Vec<(PackageLicense, String)> {
+    let mut issues = Vec::new();
+    
+    for package in &report.packages {
+        let status = get_package_status(package, report);
+        match status {
+            PackageStatus::Unknown => {
+                let issue = match package.detection_status {
+                    DetectionStatus::NotInstalled => "issue.not_installed",
+                    DetectionStatus::MetadataMissing => "issue.no_metadata",
+                    _ => "issue.no_license",
+                };
+                issues.push((package.clone(), locale.text(issue).to_string()));
+            }
+            PackageStatus::Violation => {
+                // Find the specific violation message
+                if let Some(violations) = &report.violations {
+                    if let Some(violation) = violations.details.iter().find(|v| 
+                        v.package_name == package.name && 
+                        v.package_version.as_deref() == package.version.as_deref()
+                    ) {
+                        let issue = match violation.violation_level {
+                            ViolationLevel::Forbidden => "issue.forbidden",
+                            ViolationLevel::ReviewRequired => "issue.review_required",
+                            ViolationLevel::Unknown => "issue.not_allowed",
+                            ViolationLevel::Allowed => continue,
+                        };
+                        issues.push((package.clone(), locale.text(issue).to_string()));
+                    }
+                }
+            }
+            PackageStatus::Ok => {} // Skip OK packages
+        }
+    }
+    
+    // Remove duplicates
+    issues.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+    issues.dedup_by(|a, b| a.0.name == b.0.name && a.0.version == b.0.version);
+    
+    issues
+}
+
+/// Truncate to at most `max_width` display columns, ending with "…" when cut.
+/// Works on char boundaries, so multi-byte names never panic.
+fn truncate(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+
+    let budget = max_width.saturating_sub(1);
+    let mut width = 0;
+    let mut truncated = String::new();
+    for c in s.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width > budget {
+            break;
+        }
+        width += char_width;
+        truncated.push(c);
+    }
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_packages() {
+        let package = |name: &str, license: &str| PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: Some(license.to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let packages = vec![package("a", "MIT"), package("b", "MIT"), package("c", "Zlib"), package("d", "mit")];
+
+        let (listed, collapsed) = collapse_packages(&packages, &["MIT".to_string(), "ISC".to_string()]);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "c");
+        assert_eq!(collapsed, vec![("MIT".to_string(), 3)]);
+
+        let report = crate::license::create_report(packages);
+        let output = format_table_output(&report, true, &["MIT".to_string()], Locale::En);
+        assert!(output.contains("📚 Collapsed: MIT ×3"));
+        assert!(!output.contains("│ a "));
+    }
+
+    #[test]
+    fn test_truncate_multibyte() {
+        assert_eq!(truncate("requests", 15), "requests");
+        assert_eq!(truncate("ライセンス監査ツール", 7), "ライセ…");
+        assert_eq!(truncate("café-utilities", 6), "café-…");
+        assert!(truncate("ライセンス監査ツール", 7).width() <= 7);
+    }
+
+    #[test]
+    fn test_plain_output() {
+        let package = |name: &str, license: Option<&str>| PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: license.map(str::to_string),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let report = crate::license::create_report(vec![package("requests", Some("Apache-2.0")), package("mystery", None)]);
+        let output = format_plain_output(&report, Locale::En);
+        assert_eq!(output, "requests 1.0 Apache-2.0 [OK]\nmystery 1.0 - [UNKNOWN]\n2 packages, 1 with license, 1 unknown\n");
+        assert!(output.is_ascii());
+        assert!(format_plain_output(&report, Locale::Ja).ends_with("2 パッケージ、ライセンスあり 1、不明 1\n"));
+    }
+
+    #[test]
+    fn test_compact_output() {
+        let package = |name: &str, license: Option<&str>| PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: license.map(str::to_string),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let report = crate::license::create_report(vec![package("requests", Some("Apache-2.0")), package("six", None)]);
+
+        let issues = strip_ansi(&format_compact_output(&report, false, Locale::En));
+        assert_eq!(issues, "six  1.0  -  UNKNOWN\n2 packages, 1 with license, 1 unknown\n");
+
+        let all = format_compact_output(&report, true, Locale::En);
+        assert!(all.contains("\x1b[32mOK\x1b[0m"));
+        assert_eq!(
+            strip_ansi(&all).lines().take(2).collect::<Vec<_>>(),
+            vec!["requests  1.0  Apache-2.0  OK", "six       1.0  -           UNKNOWN"]
+        );
+    }
+
+    #[test]
+    fn test_report_footer() {
+        let mut report = crate::license::create_report(vec![]);
+        assert!(!format_table_output(&report, false, &[], Locale::En).contains("License policy"));
+
+        report.footer = Some(crate::license::ReportFooter {
+            footer_text: Some("Questions? #oss-compliance".to_string()),
+            policy_url: Some("https://wiki.example.com/oss?a=1&b=2".to_string()),
+        });
+        let table = format_table_output(&report, false, &[], Locale::En);
+        assert!(table.ends_with("\nQuestions? #oss-compliance\n📖 License policy: https://wiki.example.com/oss?a=1&b=2\n"));
+        assert!(format_compact_output(&report, false, Locale::Ja).ends_with("📖 ライセンスポリシー: https://wiki.example.com/oss?a=1&b=2\n"));
+
+        let markdown = pr_comment::format_pr_comment(&report, &[], 0);
+        assert!(markdown.ends_with("---\nQuestions? #oss-compliance\n\n📖 [License policy](https://wiki.example.com/oss?a=1&b=2)\n"));
+        let html = html::format_html_output(&report, &html::HtmlOptions::default());
+        assert!(html.contains("<footer>\n<p>Questions? #oss-compliance</p>\n<p><a href=\"https://wiki.example.com/oss?a=1&amp;b=2\">"));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["footer"]["policy_url"], "https://wiki.example.com/oss?a=1&b=2");
+    }
+
+    #[test]
+    fn test_padding_wide_and_ansi() {
+        assert_eq!(strip_ansi("\x1b[1;31mGPL\x1b[0m"), "GPL");
+        assert_eq!(display_width("\x1b[32m✓ ok\x1b[0m"), 4);
+
+        let padded = format_with_padding("監査", 8);
+        assert_eq!(padded, "監査    ");
+        assert_eq!(padded.width(), 8);
+        assert_eq!(display_width(&format_with_padding("\x1b[33mライセンス\x1b[0m", 12)), 12);
+    }
+}
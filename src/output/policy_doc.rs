@@ -0,0 +1,120 @@
+use crate::exceptions::Exception;
+use crate::policy::{CategoryAction, LicensePolicy, LicenseRule, BUILTIN_BACKPORTS};
+
+fn rule_section(output: &mut String, title: &str, rule: &LicenseRule) {
+    output.push_str(&format!("## {}\n\n", title));
+    if rule.exact.is_empty() && rule.patterns.is_empty() {
+        output.push_str("_None_\n\n");
+        return;
+    }
+    for license in &rule.exact {
+        output.push_str(&format!("- `{}`\n", license));
+    }
+    for pattern in &rule.patterns {
+        output.push_str(&format!("- `{}` (pattern)\n", pattern));
+    }
+    output.push('\n');
+}
+
+fn action_label(action: CategoryAction) -> &'static str {
+    match action {
+        CategoryAction::Allowed => "allowed",
+        CategoryAction::Review => "review required",
+        CategoryAction::Forbidden => "forbidden",
+    }
+}
+
+/// Escape `|` so free text stays inside its table cell
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Render the effective policy as Markdown for compliance docs: rules, then
+/// policy exceptions (pyproject.toml) and recorded exceptions (.exceptions.toml)
+pub fn format_policy_markdown(policy: &LicensePolicy, recorded: &[Exception]) -> String {
+    let mut output = format!("# {}\n\n", policy.name);
+    if let Some(description) = &policy.description {
+        output.push_str(&format!("{}\n\n", description));
+    }
+
+    rule_section(&mut output, "Allowed licenses", &policy.allowed_licenses);
+    rule_section(&mut output, "Forbidden licenses", &policy.forbidden_licenses);
+    rule_section(&mut output, "Review required", &policy.review_required);
+    output.push_str("Licenses matching no rule are reported as unknown.\n\n");
+
+    if let Some(action) = policy.on_missing_license {
+        output.push_str(&format!("Packages without license information: **{}**\n\n", action_label(action)));
+    }
+    if !policy.internal_packages.is_empty() {
+        let patterns: Vec<String> = policy.internal_packages.iter().map(|pattern| format!("`{}`", pattern)).collect();
+        output.push_str(&format!("Internal packages allowed without license information: {}\n\n", patterns.join(", ")));
+    }
+    let backports: Vec<String> = match &policy.backport_allowlist {
+        Some(patterns) => patterns.iter().map(|pattern| format!("`{}`", pattern)).collect(),
+        None => BUILTIN_BACKPORTS.iter().map(|pattern| format!("`{}`", pattern)).collect(),
+    };
+    if !backports.is_empty() {
+        let source = if policy.backport_allowlist.is_some() { "" } else { " (built-in list)" };
+        output.push_str(&format!("Standard library backports allowed regardless of license{}: {}\n\n", source, backports.join(", ")));
+    }
+    if let Some(action) = policy.proprietary {
+        output.push_str(&format!("Proprietary licenses: **{}**\n\n", action_label(action)));
+    }
+    if let Some(action) = policy.public_domain {
+        output.push_str(&format!("Public domain (Public Domain, CC0-1.0, Unlicense): **{}**\n\n", action_label(action)));
+    }
+
+    if !policy.exceptions.is_empty() || !recorded.is_empty() {
+        output.push_str("## Exceptions\n\n");
+        output.push_str("| Package | Version | Reason | Expires |\n");
+        output.push_str("|---------|---------|--------|---------|\n");
+        let entries = policy.exceptions.iter()
+            .map(|e| (&e.name, &e.version, &e.reason, e.expires))
+            .chain(recorded.iter().map(|e| (&e.name, &e.version, &e.reason, e.expires)));
+        for (name, version, reason, expires) in entries {
+            let expires = match expires {
+                Some(date) => date.format("%Y-%m-%d").to_string(),
+                None => "never".to_string(),
+            };
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                cell(name),
+                cell(version.as_deref().unwrap_or("*")),
+                cell(reason),
+                expires
+            ));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::PackageException;
+
+    #[test]
+    fn test_policy_markdown() {
+        let policy = LicensePolicy {
+            name: "Green".to_string(),
+            description: Some("Permissive only".to_string()),
+            allowed_licenses: LicenseRule { exact: vec!["MIT".to_string()], patterns: vec!["BSD-*".to_string()], ..Default::default() },
+            forbidden_licenses: LicenseRule { exact: vec!["GPL-3.0".to_string()], ..Default::default() },
+            exceptions: vec![PackageException {
+                name: "legacy".to_string(),
+                version: Some("1.0".to_string()),
+                reason: "approved | by legal".to_string(),
+                expires: None,
+            }],
+            proprietary: Some(CategoryAction::Review),
+            ..Default::default()
+        };
+
+        let markdown = format_policy_markdown(&policy, &[]);
+        assert!(markdown.starts_with("# Green\n\nPermissive only\n\n## Allowed licenses\n\n- `MIT`\n- `BSD-*` (pattern)\n"));
+        assert!(markdown.contains("## Review required\n\n_None_\n"));
+        assert!(markdown.contains("Proprietary licenses: **review required**"));
+        assert!(markdown.contains("| legacy | 1.0 | approved \\| by legal | never |"));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::license::LicenseReport;
+use crate::policy::{Violation, ViolationLevel};
+
+/// Marker on the first line, so CI scripts can find and update an earlier comment
+pub const PR_COMMENT_MARKER: &str = "<!-- py-license-auditor -->";
+
+/// Escape a value for a Markdown table cell
+fn cell(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// Render a GitHub pull request comment: status and counts, a table of violations, and
+/// the full package list in a collapsed `<details>` block. `failing` lists the reasons
+/// the check fails (empty when it passes); `exit_code` is the status the run exits with.
+pub fn format_pr_comment(report: &LicenseReport, failing: &[String], exit_code: i32) -> String {
+    let mut output = format!("{}\n", PR_COMMENT_MARKER);
+    if failing.is_empty() {
+        output.push_str("## ✅ License check passed\n\n");
+    } else {
+        output.push_str(&format!("## ❌ License check failed: {}\n\n", failing.join(", ")));
+    }
+
+    let summary = &report.summary;
+    let violations = report.violations.as_ref();
+    output.push_str(&format!(
+        "**{}** packages · **{}** with license · **{}** unknown · **{}** violations · exit status `{}`\n",
+        summary.total_packages,
+        summary.with_license,
+        summary.without_license,
+        violations.map_or(0, |violations| violations.total),
+        exit_code
+    ));
+
+    if let Some(violations) = violations.filter(|violations| !violations.details.is_empty()) {
+        output.push_str("\n### Violations\n\n");
+        output.push_str(&violations_table(&violations.details));
+    }
+
+    output.push_str(&format!(
+        "\n<details>\n<summary>All packages ({})</summary>\n\n| Package | Version | License | Status |\n|---|---|---|---|\n",
+        report.packages.len()
+    ));
+    for package in &report.packages {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            cell(&package.name),
+            cell(package.version.as_deref().unwrap_or("-")),
+            cell(package.effective_license.as_deref().unwrap_or("-")),
+            super::status_label(package, report)
+        ));
+    }
+    output.push_str("\n</details>\n");
+
+    if violations.is_some_and(|violations| violations.total > 0) {
+        output.push_str(concat!(
+            "\n---\n",
+            "**How to fix:** replace or remove the flagged packages, or record an approved exception ",
+            "with `py-license-auditor fix` (or `fix --interactive`) and commit `.exceptions.toml`. ",
+            "Run `py-license-auditor check --explain <package>` to see why a package was flagged.\n",
+        ));
+    }
+    push_footer(&mut output, report);
+    output
+}
+
+/// `check --baseline --comment`: a PR comment listing only the violations the change
+/// introduces. `report.violations` must already have the baselined ones subtracted.
+pub fn format_new_violations_comment(report: &LicenseReport, failing: &[String]) -> String {
+    let mut output = format!("{}\n", PR_COMMENT_MARKER);
+    let details = report.violations.as_ref().map(|violations| violations.details.as_slice()).unwrap_or_default();
+    if details.is_empty() {
+        output.push_str("## ✅ No new license violations\n");
+    } else {
+        let heading = if failing.is_empty() { "✅" } else { "❌" };
+        output.push_str(&format!("## {} {} new license violation(s)\n\n", heading, details.len()));
+        output.push_str(&violations_table(details));
+        output.push_str(concat!(
+            "\nRecord an approved exception with `py-license-auditor fix`, or see why a package ",
+            "was flagged with `py-license-auditor check --explain <package>`.\n",
+        ));
+    }
+    push_footer(&mut output, report);
+    output
+}
+
+/// Markdown table of violations
+fn violations_table(details: &[Violation]) -> String {
+    let mut table = String::from("| Package | Version | License | Problem |\n|---|---|---|---|\n");
+    for violation in details {
+        let problem = match violation.violation_level {
+            ViolationLevel::Forbidden => "Forbidden",
+            ViolationLevel::ReviewRequired => "Review required",
+            _ => "Unknown",
+        };
+        let note = if violation.is_note() { " (note)" } else { "" };
+        table.push_str(&format!(
+            "| {} | {} | {} | {}{} |\n",
+            cell(&violation.package_name),
+            cell(violation.package_version.as_deref().unwrap_or("-")),
+            cell(violation.license.as_deref().unwrap_or("-")),
+            problem,
+            note
+        ));
+    }
+    table
+}
+
+fn push_footer(output: &mut String, report: &LicenseReport) {
+    if let Some(footer) = &report.footer {
+        output.push_str("\n---\n");
+        if let Some(text) = &footer.footer_text {
+            output.push_str(&format!("{}\n", text.trim_end()));
+        }
+        if let Some(url) = &footer.policy_url {
+            output.push_str(&format!("\n📖 [License policy]({})\n", url));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::{create_report, PackageLicense};
+    use crate::policy::ViolationSummary;
+
+    #[test]
+    fn test_pr_comment() {
+        let package = |name: &str, license: Option<&str>| PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: license.map(str::to_string),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        };
+        let mut report = create_report(vec![package("requests", Some("Apache-2.0")), package("gpl|thing", Some("GPL-3.0"))]);
+        report.violations = Some(ViolationSummary::from_details(vec![Violation {
+            package_name: "gpl|thing".to_string(),
+            package_version: Some("1.0".to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: ViolationLevel::Forbidden,
+            matched_rule: Some("GPL-*".to_string()),
+            message: "Forbidden license: GPL-3.0".to_string(),
+            ..Default::default()
+        }]));
+
+        let output = format_pr_comment(&report, &["forbidden licenses".to_string()], 1);
+        assert!(output.starts_with("<!-- py-license-auditor -->\n## ❌ License check failed: forbidden licenses\n"));
+        assert!(output.contains("exit status `1`"));
+        assert!(output.contains("| gpl\\|thing | 1.0 | GPL-3.0 | Forbidden |\n"));
+        assert!(output.contains("<summary>All packages (2)</summary>"));
+        assert!(output.contains("| requests | 1.0 | Apache-2.0 | OK |\n"));
+        assert!(output.contains("**How to fix:**"));
+
+        report.violations = None;
+        let output = format_pr_comment(&report, &[], 0);
+        assert!(output.contains("## ✅ License check passed"));
+        assert!(!output.contains("### Violations"));
+        assert!(!output.contains("How to fix"));
+
+        // Net-new only: just the violations table, no package list
+        report.violations = Some(ViolationSummary::from_details(vec![Violation {
+            package_name: "gpl|thing".to_string(),
+            package_version: Some("1.0".to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: ViolationLevel::Forbidden,
+            ..Default::default()
+        }]));
+        let output = format_new_violations_comment(&report, &["forbidden licenses".to_string()]);
+        assert!(output.contains("## ❌ 1 new license violation(s)\n"));
+        assert!(output.contains("| gpl\\|thing | 1.0 | GPL-3.0 | Forbidden |\n"));
+        assert!(!output.contains("All packages"));
+        report.violations = Some(ViolationSummary::from_details(vec![]));
+        assert!(format_new_violations_comment(&report, &[]).contains("## ✅ No new license violations"));
+    }
+}
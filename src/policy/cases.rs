@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use super::config::LicensePolicy;
+use super::matcher::ViolationLevel;
+
+/// `config --test-policy` のケースファイル（`[[cases]]` の配列）
+#[derive(Debug, Deserialize)]
+struct PolicyTestFile {
+    #[serde(default)]
+    cases: Vec<PolicyTestCase>,
+}
+
+/// ライセンス文字列と、ポリシーが返すべき判定
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PolicyTestCase {
+    pub license: String,
+    #[serde(deserialize_with = "deserialize_level")]
+    pub expected: ViolationLevel,
+    /// ケースの説明（任意）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 1ケースの実行結果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PolicyTestResult {
+    #[serde(flatten)]
+    pub case: PolicyTestCase,
+    pub actual: ViolationLevel,
+    pub passed: bool,
+}
+
+/// `allowed` / `review-required` / `ReviewRequired` などの表記ゆれを受け付ける
+fn deserialize_level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ViolationLevel, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    match value.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+        "allowed" => Ok(ViolationLevel::Allowed),
+        "review" | "reviewrequired" => Ok(ViolationLevel::ReviewRequired),
+        "forbidden" => Ok(ViolationLevel::Forbidden),
+        "unknown" => Ok(ViolationLevel::Unknown),
+        _ => Err(serde::de::Error::custom(format!(
+            "unknown level '{}' (expected allowed, review-required, forbidden or unknown)", value
+        ))),
+    }
+}
+
+/// ケースファイルを読み込む
+pub fn load_policy_tests(path: &Path) -> Result<Vec<PolicyTestCase>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read policy test cases: {}", path.display()))?;
+    let file: PolicyTestFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse policy test cases: {}", path.display()))?;
+    if file.cases.is_empty() {
+        anyhow::bail!("{} defines no [[cases]]", path.display());
+    }
+    Ok(file.cases)
+}
+
+impl LicensePolicy {
+    /// 各ケースのライセンスを `check_license` で判定し、期待値と比較する
+    pub fn run_tests(&self, cases: &[PolicyTestCase]) -> Vec<PolicyTestResult> {
+        cases
+            .iter()
+            .map(|case| {
+                let actual = self.check_license(&case.license);
+                PolicyTestResult { passed: actual == case.expected, case: case.clone(), actual }
+            })
+            .collect()
+    }
+}
+
+/// 人間向けの表示（ケースごとの結果と集計）
+pub fn format_policy_test_results(results: &[PolicyTestResult]) -> String {
+    let mut output = String::new();
+    for result in results {
+        let description = result.case.description.as_deref().map(|d| format!(" ({})", d)).unwrap_or_default();
+        if result.passed {
+            output.push_str(&format!("✅ {} → {:?}{}\n", result.case.license, result.actual, description));
+        } else {
+            output.push_str(&format!(
+                "❌ {}: expected {:?}, got {:?}{}\n",
+                result.case.license, result.case.expected, result.actual, description
+            ));
+        }
+    }
+    let failed = results.iter().filter(|result| !result.passed).count();
+    output.push_str(&format!("\n{} passed, {} failed", results.len() - failed, failed));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_cases() {
+        let policy: LicensePolicy = toml::from_str(r#"
+name = "strict"
+[allowed_licenses]
+exact = ["MIT", "Apache-2.0"]
+[forbidden_licenses]
+patterns = ["GPL-*"]
+[review_required]
+exact = ["MPL-2.0"]
+"#).unwrap();
+        let file: PolicyTestFile = toml::from_str(r#"
+[[cases]]
+license = "MIT"
+expected = "allowed"
+
+[[cases]]
+license = "GPL-3.0"
+expected = "Forbidden"
+
+[[cases]]
+license = "MPL-2.0"
+expected = "review-required"
+description = "weak copyleft needs legal review"
+
+[[cases]]
+license = "LGPL-2.1"
+expected = "allowed"
+"#).unwrap();
+
+        let results = policy.run_tests(&file.cases);
+        assert_eq!(results.iter().map(|r| r.passed).collect::<Vec<_>>(), vec![true, true, true, false]);
+        let output = format_policy_test_results(&results);
+        assert!(output.contains("✅ MPL-2.0 → ReviewRequired (weak copyleft needs legal review)\n"));
+        assert!(output.contains("❌ LGPL-2.1: expected Allowed, got "));
+        assert!(output.ends_with("3 passed, 1 failed"));
+
+        assert!(toml::from_str::<PolicyTestFile>("[[cases]]\nlicense = \"MIT\"\nexpected = \"fine\"").is_err());
+    }
+}
@@ -1,10 +1,12 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use super::matcher::ViolationLevel;
-use super::config::LicensePolicy;
-use crate::license::{PackageLicense, normalize_license_name};
+use super::config::{CategoryAction, LicensePolicy, RuleSeverity};
+use crate::i18n::Locale;
+use crate::license::{PackageLicense, normalize_license_name, parse_expression};
 
 /// 違反の詳細情報
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Violation {
     pub package_name: String,
     pub package_version: Option<String>,
@@ -12,6 +14,16 @@ pub struct Violation {
     pub violation_level: ViolationLevel,
     pub matched_rule: Option<String>,
     pub message: String,
+    /// マッチしたルール要素に指定された重大度
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<RuleSeverity>,
+}
+
+impl Violation {
+    /// 記録のみの違反（severity = "note"）
+    pub fn is_note(&self) -> bool {
+        self.severity == Some(RuleSeverity::Note)
+    }
 }
 
 /// 違反のサマリー情報
@@ -19,37 +31,105 @@ pub struct Violation {
 pub struct ViolationSummary {
     pub total: usize,
     pub errors: usize,    // Forbidden
-    pub warnings: usize,  // ReviewRequired + Unknown
+    pub warnings: usize,  // ReviewRequired + Unknown（後方互換のための合計）
+    #[serde(default)]
+    pub review_required: usize,
+    #[serde(default)]
+    pub unknown: usize,
     pub details: Vec<Violation>,
 }
 
+impl ViolationSummary {
+    /// 違反リストからサマリーを計算
+    pub fn from_details(details: Vec<Violation>) -> Self {
+        let count = |level: ViolationLevel| details.iter().filter(|v| v.violation_level == level).count();
+        let errors = count(ViolationLevel::Forbidden);
+        let review_required = count(ViolationLevel::ReviewRequired);
+        let unknown = count(ViolationLevel::Unknown);
+
+        ViolationSummary {
+            total: details.len(),
+            errors,
+            warnings: review_required + unknown,
+            review_required,
+            unknown,
+            details,
+        }
+    }
+
+    /// 指定レベルの違反数
+    pub fn count(&self, level: &ViolationLevel) -> usize {
+        match level {
+            ViolationLevel::Forbidden => self.errors,
+            ViolationLevel::ReviewRequired => self.review_required,
+            ViolationLevel::Unknown => self.unknown,
+            _ => 0,
+        }
+    }
+}
+
 impl LicensePolicy {
-    /// パッケージリストから違反を検出
+    /// パッケージリストから違反を検出（既定のメッセージは英語）
     pub fn detect_violations(&self, packages: &[PackageLicense]) -> ViolationSummary {
+        self.detect_violations_at(packages, chrono::Utc::now().date_naive(), Locale::En)
+    }
+
+    /// `today` 時点の例外の有効期限で違反を検出し、既定のメッセージを `locale` で書く
+    pub fn detect_violations_at(&self, packages: &[PackageLicense], today: NaiveDate, locale: Locale) -> ViolationSummary {
         let mut violations = Vec::new();
         
         for package in packages {
             // 例外チェック
-            if self.is_exception(&package.name, package.version.as_deref()).is_some() {
+            if self.is_exception_at(&package.name, package.version.as_deref(), today).is_some() {
                 continue; // 例外なのでスキップ
             }
             
-            // ライセンスがない場合
+            // ライセンスがない場合（認識できないライセンスとは区別し、on_missing_license で扱いを決める）
             let license = match &package.effective_license {
                 Some(license) if !license.trim().is_empty() => license,
+                // 社内パッケージはライセンス未記載でよい
+                _ if self.is_internal_package(&package.name) => continue,
                 _ => {
+                    let violation_level = match self.on_missing_license {
+                        None => ViolationLevel::Unknown,
+                        Some(CategoryAction::Allowed) => continue,
+                        Some(CategoryAction::Review) => ViolationLevel::ReviewRequired,
+                        Some(CategoryAction::Forbidden) => ViolationLevel::Forbidden,
+                    };
                     violations.push(Violation {
                         package_name: package.name.clone(),
                         package_version: package.version.clone(),
                         license: None,
-                        violation_level: ViolationLevel::Unknown,
-                        matched_rule: None,
-                        message: "No license information found".to_string(),
+                        violation_level,
+                        matched_rule: self.on_missing_license.map(|_| "on_missing_license".to_string()),
+                        message: locale.text("violation.no_license").to_string(),
+                        severity: None,
                     });
                     continue;
                 }
             };
             
+            // SPDX式は分岐ごとに評価し、判定を決めた分岐をメッセージに残す
+            if let Some(expression) = parse_expression(license) {
+                let outcome = self.evaluate_expression(&expression);
+                if outcome.level != ViolationLevel::Allowed {
+                    let severity = self.matched_severity(&outcome.level, &outcome.license);
+                    let message = locale.format("violation.expression", &[
+                        &self.violation_message(&outcome.level, &outcome.license, &package.name, severity, locale), license, &outcome.license,
+                    ]);
+                    violations.push(Violation {
+                        package_name: package.name.clone(),
+                        package_version: package.version.clone(),
+                        license: Some(license.clone()),
+                        matched_rule: self.matched_rule(&outcome.level, &outcome.license),
+                        violation_level: outcome.level,
+                        message,
+                        severity,
+                    });
+                }
+                continue;
+            }
+
             // ライセンス名を正規化
             let normalized_license = normalize_license_name(license);
             
@@ -58,18 +138,9 @@ impl LicensePolicy {
             
             // Allowedでない場合は違反として記録
             if violation_level != ViolationLevel::Allowed {
-                let matched_rule = match violation_level {
-                    ViolationLevel::Forbidden => self.forbidden_licenses.find_match(&normalized_license),
-                    ViolationLevel::ReviewRequired => self.review_required.find_match(&normalized_license),
-                    _ => None,
-                };
-                
-                let message = match violation_level {
-                    ViolationLevel::Forbidden => format!("License '{}' is forbidden by policy", normalized_license),
-                    ViolationLevel::ReviewRequired => format!("License '{}' requires review", normalized_license),
-                    ViolationLevel::Unknown => format!("License '{}' is not in allowed list", normalized_license),
-                    ViolationLevel::Allowed => unreachable!(),
-                };
+                let matched_rule = self.matched_rule(&violation_level, &normalized_license);
+                let severity = self.matched_severity(&violation_level, &normalized_license);
+                let message = self.violation_message(&violation_level, &normalized_license, &package.name, severity, locale);
                 
                 violations.push(Violation {
                     package_name: package.name.clone(),
@@ -78,22 +149,305 @@ impl LicensePolicy {
                     violation_level,
                     matched_rule,
                     message,
+                    severity,
                 });
             }
         }
-        
-        // サマリーを計算
-        let errors = violations.iter().filter(|v| v.violation_level == ViolationLevel::Forbidden).count();
-        let warnings = violations.iter().filter(|v| 
-            v.violation_level == ViolationLevel::ReviewRequired || 
-            v.violation_level == ViolationLevel::Unknown
-        ).count();
-        
-        ViolationSummary {
-            total: violations.len(),
-            errors,
-            warnings,
-            details: violations,
+
+        // 標準ライブラリのバックポートは禁止以外の違反だけを免除する（禁止ライセンスは常に報告）
+        violations.retain(|violation| violation.violation_level == ViolationLevel::Forbidden || !self.is_backport_package(&violation.package_name));
+        ViolationSummary::from_details(violations)
+    }
+
+    /// 違反メッセージ: ポリシーのテンプレートがあれば使い、なければ既定の文言
+    fn violation_message(&self, level: &ViolationLevel, license: &str, package: &str, severity: Option<RuleSeverity>, locale: Locale) -> String {
+        let template = match level {
+            ViolationLevel::Forbidden => self.messages.forbidden.as_deref(),
+            ViolationLevel::ReviewRequired => self.messages.review_required.as_deref(),
+            ViolationLevel::Unknown => self.messages.unknown.as_deref(),
+            ViolationLevel::Allowed => unreachable!(),
+        };
+
+        let message = match template {
+            Some(template) => template.replace("{license}", license).replace("{package}", package),
+            None => locale.format(match level {
+                ViolationLevel::Forbidden => "violation.forbidden",
+                ViolationLevel::ReviewRequired => "violation.review_required",
+                _ => "violation.not_allowed",
+            }, &[&license]),
+        };
+        match severity {
+            Some(RuleSeverity::Note) => locale.format("violation.note", &[&message]),
+            _ => message,
+        }
+    }
+
+    /// 違反レベルに対応するルールでマッチした要素の重大度
+    fn matched_severity(&self, level: &ViolationLevel, license: &str) -> Option<RuleSeverity> {
+        if self.category_action(license).is_some() {
+            return None;
+        }
+        match level {
+            ViolationLevel::Forbidden => self.forbidden_licenses.severity_for(license),
+            ViolationLevel::ReviewRequired => self.review_required.severity_for(license),
+            _ => None,
         }
     }
+
+    /// 違反レベルに対応するマッチしたルール
+    fn matched_rule(&self, level: &ViolationLevel, license: &str) -> Option<String> {
+        if self.category_action(license).is_some() {
+            return Self::category(license).map(|category| format!("category: {}", category));
+        }
+        match level {
+            ViolationLevel::Forbidden => self.forbidden_licenses.find_match(license),
+            ViolationLevel::ReviewRequired => self.review_required.find_match(license),
+            _ => None,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{LicenseRule, PackageException};
+
+    fn gpl_forbidding_policy() -> LicensePolicy {
+        LicensePolicy {
+            name: "test".to_string(),
+            allowed_licenses: LicenseRule { exact: vec!["MIT".to_string()], patterns: vec![], ..Default::default() },
+            forbidden_licenses: LicenseRule { exact: vec![], patterns: vec!["GPL-*".to_string()], ..Default::default() },
+            review_required: LicenseRule { exact: vec![], patterns: vec!["LGPL-*".to_string()], ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    fn package(license: &str) -> PackageLicense {
+        PackageLicense {
+            name: "dual".to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: Some(license.to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_proprietary_category() {
+        let mut policy = gpl_forbidding_policy();
+        let violations = policy.detect_violations(&[package("Proprietary")]);
+        assert_eq!(violations.details[0].violation_level, ViolationLevel::Unknown);
+
+        policy.proprietary = Some(crate::policy::CategoryAction::Review);
+        let violations = policy.detect_violations(&[package("Proprietary")]);
+        assert_eq!(violations.details[0].violation_level, ViolationLevel::ReviewRequired);
+        assert_eq!(violations.details[0].matched_rule.as_deref(), Some("category: proprietary"));
+
+        policy.proprietary = Some(crate::policy::CategoryAction::Allowed);
+        assert_eq!(policy.detect_violations(&[package("Proprietary")]).total, 0);
+    }
+
+    #[test]
+    fn test_public_domain_category() {
+        let mut policy = gpl_forbidding_policy();
+        let packages = [package("Public-Domain"), package("CC0-1.0"), package("Unlicense")];
+        assert_eq!(policy.detect_violations(&packages).unknown, 3);
+
+        policy.public_domain = Some(CategoryAction::Allowed);
+        assert_eq!(policy.detect_violations(&packages).total, 0);
+
+        policy.public_domain = Some(CategoryAction::Review);
+        let violations = policy.detect_violations(&packages);
+        assert_eq!(violations.review_required, 3);
+        assert_eq!(violations.details[1].matched_rule.as_deref(), Some("category: public-domain"));
+    }
+
+    #[test]
+    fn test_on_missing_license() {
+        let mut unlicensed = package("MIT");
+        unlicensed.effective_license = None;
+        let packages = [unlicensed, package("Weird-1.0")];
+
+        // Default: both are unknown, but the messages tell them apart
+        let violations = gpl_forbidding_policy().detect_violations(&packages);
+        assert_eq!(violations.unknown, 2);
+        assert_eq!(violations.details[0].message, "No license information found");
+        assert_eq!(violations.details[1].message, "License 'Weird-1.0' is not in allowed list");
+
+        // --locale ja は既定のメッセージだけを翻訳する
+        let today = chrono::Utc::now().date_naive();
+        let violations = gpl_forbidding_policy().detect_violations_at(&packages, today, Locale::Ja);
+        assert_eq!(violations.details[0].message, "ライセンス情報が見つかりません");
+        assert_eq!(violations.details[1].message, "ライセンス 'Weird-1.0' は許可リストにありません");
+
+        let policy: LicensePolicy = toml::from_str("name = \"strict\"\non_missing_license = \"forbid\"").unwrap();
+        let violations = policy.detect_violations(&packages);
+        assert_eq!(violations.details[0].violation_level, ViolationLevel::Forbidden);
+        assert_eq!(violations.details[0].matched_rule.as_deref(), Some("on_missing_license"));
+        assert_eq!(violations.details[1].violation_level, ViolationLevel::Unknown);
+
+        let mut policy = gpl_forbidding_policy();
+        policy.on_missing_license = Some(CategoryAction::Allowed);
+        let violations = policy.detect_violations(&packages);
+        assert_eq!(violations.total, 1);
+        assert_eq!(violations.details[0].license.as_deref(), Some("Weird-1.0"));
+    }
+
+    #[test]
+    fn test_internal_packages_may_be_unlicensed() {
+        let mut internal = package("MIT");
+        internal.name = "Acme_Billing".to_string();
+        internal.effective_license = None;
+        let mut internal_gpl = package("GPL-3.0");
+        internal_gpl.name = "acme-cli".to_string();
+        let mut external = package("MIT");
+        external.effective_license = None;
+
+        let mut policy = gpl_forbidding_policy();
+        policy.internal_packages = vec!["acme-*".to_string()];
+        policy.on_missing_license = Some(CategoryAction::Forbidden);
+        let violations = policy.detect_violations(&[internal, internal_gpl, external]);
+
+        // 未記載の社内パッケージだけが除外され、記載された GPL は通常どおり禁止
+        assert_eq!(violations.total, 2);
+        assert_eq!(violations.details[0].package_name, "acme-cli");
+        assert_eq!(violations.details[0].violation_level, ViolationLevel::Forbidden);
+        assert_eq!(violations.details[1].package_name, "dual");
+        assert_eq!(violations.details[1].matched_rule.as_deref(), Some("on_missing_license"));
+    }
+
+    #[test]
+    fn test_invalid_internal_package_pattern_is_rejected_on_load() {
+        let result: Result<LicensePolicy, _> = toml::from_str("name = \"p\"\ninternal_packages = [\"acme-[\"]\n");
+        assert!(result.unwrap_err().to_string().contains("invalid package pattern 'acme-['"));
+        let policy: LicensePolicy = toml::from_str("name = \"p\"\ninternal_packages = [\"acme-*\"]\n").unwrap();
+        assert!(policy.is_internal_package("Acme_Billing"));
+    }
+
+    #[test]
+    fn test_summary_counts_review_and_unknown_separately() {
+        let packages = [package("LGPL-2.1"), package("LGPL-3.0"), package("Weird-1.0"), package("GPL-3.0"), package("MIT")];
+        let violations = gpl_forbidding_policy().detect_violations(&packages);
+        assert_eq!(violations.total, 4);
+        assert_eq!(violations.errors, 1);
+        assert_eq!(violations.review_required, 2);
+        assert_eq!(violations.unknown, 1);
+        // 後方互換の warnings は要レビューと不明の合計
+        assert_eq!(violations.warnings, 3);
+        assert_eq!(violations.count(&ViolationLevel::ReviewRequired), 2);
+        assert_eq!(violations.count(&ViolationLevel::Allowed), 0);
+    }
+
+    #[test]
+    fn test_builtin_backport_allowlist() {
+        let backport = |name: &str, license: &str| {
+            let mut package = package(license);
+            package.name = name.to_string();
+            package
+        };
+        let packages = [backport("typing_extensions", "PSF-2.0"), backport("backports.zoneinfo", "Weird-1.0"), package("Weird-1.0")];
+
+        // 組み込みリストのバックポートはライセンスに関係なく許可
+        let policy = gpl_forbidding_policy();
+        let violations = policy.detect_violations(&packages);
+        assert_eq!(violations.details.iter().map(|v| v.package_name.as_str()).collect::<Vec<_>>(), vec!["dual"]);
+
+        // 禁止ライセンスはバックポートでも報告する
+        let violations = policy.detect_violations(&[backport("zipp", "GPL-3.0")]);
+        assert_eq!(violations.errors, 1);
+
+        // 設定で置き換え、空リストで無効化
+        let policy = LicensePolicy { backport_allowlist: Some(vec!["typing-extensions".to_string()]), ..gpl_forbidding_policy() };
+        assert_eq!(policy.detect_violations(&packages).total, 2);
+        let policy = LicensePolicy { backport_allowlist: Some(vec![]), ..gpl_forbidding_policy() };
+        assert_eq!(policy.detect_violations(&packages).total, 3);
+    }
+
+    #[test]
+    fn test_version_range_exceptions() {
+        let versioned = |version: &str| {
+            let mut package = package("GPL-3.0");
+            package.version = Some(version.to_string());
+            package
+        };
+        let mut policy = gpl_forbidding_policy();
+        policy.exceptions = vec![PackageException {
+            name: "dual".to_string(),
+            version: Some(">=1.0, <2.0".to_string()),
+            reason: "範囲指定".to_string(),
+            expires: None,
+        }];
+
+        // 範囲内のバージョンだけが例外になる
+        assert_eq!(policy.detect_violations(&[versioned("1.4.2")]).total, 0);
+        assert_eq!(policy.detect_violations(&[versioned("1.0")]).total, 0);
+        assert_eq!(policy.detect_violations(&[versioned("2.0.0")]).errors, 1);
+        assert_eq!(policy.detect_violations(&[versioned("0.9")]).errors, 1);
+
+        // 完全一致と `*` は従来どおり
+        policy.exceptions[0].version = Some("1.4.2".to_string());
+        assert_eq!(policy.detect_violations(&[versioned("1.4.2")]).total, 0);
+        assert_eq!(policy.detect_violations(&[versioned("1.4.3")]).errors, 1);
+        policy.exceptions[0].version = Some("*".to_string());
+        assert_eq!(policy.detect_violations(&[versioned("9.9")]).total, 0);
+    }
+
+    #[test]
+    fn test_message_templates() {
+        let mut policy = gpl_forbidding_policy();
+        policy.messages.forbidden = Some("{package} uses {license}; contact legal@example.com".to_string());
+
+        let violations = policy.detect_violations(&[package("GPL-3.0"), package("Weird-1.0")]);
+        assert_eq!(violations.details[0].message, "dual uses GPL-3.0; contact legal@example.com");
+        // Levels without a template keep the default wording
+        assert_eq!(violations.details[1].message, "License 'Weird-1.0' is not in allowed list");
+    }
+
+    #[test]
+    fn test_mixed_severity_review_list() {
+        let mut policy = gpl_forbidding_policy();
+        policy.review_required = toml::from_str(r#"
+exact = ["Apache-2.0", { name = "MPL-2.0", severity = "note" }]
+patterns = [{ name = "CDDL-*", severity = "warning" }]
+"#).unwrap();
+
+        let violations = policy.detect_violations(&[package("Apache-2.0"), package("MPL-2.0"), package("CDDL-1.0")]);
+        assert_eq!(violations.review_required, 3);
+        assert_eq!(violations.details[0].severity, None);
+        assert_eq!(violations.details[1].message, "Note: License 'MPL-2.0' requires review");
+        assert!(violations.details[1].is_note());
+        assert_eq!(violations.details[2].severity, Some(RuleSeverity::Warning));
+        assert_eq!(violations.details[2].matched_rule.as_deref(), Some("pattern: CDDL-*"));
+
+        // Plain entries stay plain strings when written back
+        let written = toml::to_string(&policy.review_required).unwrap();
+        assert!(written.contains("\"Apache-2.0\""));
+        assert!(written.contains("severity = \"note\""));
+    }
+
+    #[test]
+    fn test_or_expression_passes_when_any_branch_allowed() {
+        let violations = gpl_forbidding_policy().detect_violations(&[package("GPL-2.0-only OR MIT")]);
+        assert_eq!(violations.total, 0);
+    }
+
+    #[test]
+    fn test_and_expression_fails_on_forbidden_branch() {
+        let violations = gpl_forbidding_policy().detect_violations(&[package("GPL-2.0-only AND MIT")]);
+        assert_eq!(violations.errors, 1);
+
+        let violation = &violations.details[0];
+        assert_eq!(violation.license.as_deref(), Some("GPL-2.0-only AND MIT"));
+        assert_eq!(violation.matched_rule.as_deref(), Some("pattern: GPL-*"));
+        assert!(violation.message.contains("deciding branch 'GPL-2.0-only'"));
+    }
+
+    #[test]
+    fn test_or_expression_reports_least_severe_branch() {
+        let violations = gpl_forbidding_policy().detect_violations(&[package("GPL-3.0-only OR LGPL-3.0-only")]);
+        assert_eq!((violations.errors, violations.warnings), (0, 1));
+        assert_eq!((violations.review_required, violations.unknown), (1, 0));
+        assert_eq!(violations.details[0].matched_rule.as_deref(), Some("pattern: LGPL-*"));
+    }
 }
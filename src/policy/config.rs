@@ -1,26 +1,137 @@
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use glob::Pattern;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use crate::uv_lock::normalize_package_name;
+
+/// ルール要素ごとの重大度（同じリスト内で扱いを分けたい場合に指定）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    /// 記録のみ（`--fail-on review-required` / `--max-review` の対象外）
+    Note,
+    /// 通常の違反として扱う（既定）
+    Warning,
+}
 
 /// ライセンスルール: 完全一致とパターンマッチングをサポート
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(from = "RawLicenseRule", into = "RawLicenseRule")]
 pub struct LicenseRule {
     /// 完全一致するライセンス名のリスト
-    #[serde(default)]
     pub exact: Vec<String>,
     /// Globパターン（例: "GPL-*", "BSD-*"）
-    #[serde(default)]
     pub patterns: Vec<String>,
+    /// 要素（exact / patterns の文字列）ごとの重大度
+    pub severities: BTreeMap<String, RuleSeverity>,
+    /// コンパイル済みパターン（初回マッチ時に一度だけ生成）
+    pub(crate) compiled: CompiledPatterns,
+}
+
+/// 設定ファイル上のルール要素: 文字列、または `{ name = "...", severity = "note" }`
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum RuleEntry {
+    Plain(String),
+    Detailed { name: String, severity: RuleSeverity },
+}
+
+/// 設定ファイル上の `LicenseRule` の表現（文字列だけのリストとの後方互換を保つ）
+#[derive(Serialize, Deserialize)]
+struct RawLicenseRule {
+    #[serde(default)]
+    exact: Vec<RuleEntry>,
+    #[serde(default)]
+    patterns: Vec<RuleEntry>,
+}
+
+impl From<RawLicenseRule> for LicenseRule {
+    fn from(raw: RawLicenseRule) -> Self {
+        let mut severities = BTreeMap::new();
+        let mut names = |entries: Vec<RuleEntry>| -> Vec<String> {
+            entries.into_iter().map(|entry| match entry {
+                RuleEntry::Plain(name) => name,
+                RuleEntry::Detailed { name, severity } => {
+                    severities.insert(name.clone(), severity);
+                    name
+                }
+            }).collect()
+        };
+        let exact = names(raw.exact);
+        let patterns = names(raw.patterns);
+        LicenseRule { exact, patterns, severities, compiled: CompiledPatterns::default() }
+    }
+}
+
+impl From<LicenseRule> for RawLicenseRule {
+    fn from(rule: LicenseRule) -> Self {
+        let LicenseRule { exact, patterns, severities, .. } = rule;
+        let entries = |names: Vec<String>| -> Vec<RuleEntry> {
+            names.into_iter().map(|name| match severities.get(&name) {
+                Some(&severity) => RuleEntry::Detailed { name, severity },
+                None => RuleEntry::Plain(name),
+            }).collect()
+        };
+        RawLicenseRule { exact: entries(exact), patterns: entries(patterns) }
+    }
+}
+
+/// `patterns` のコンパイル結果のキャッシュ。無効なパターンは除外される。
+/// 比較では無視する（ルールの同一性は exact / patterns で決まる）
+#[derive(Debug, Default)]
+pub(crate) struct CompiledPatterns(OnceLock<Vec<(String, Pattern)>>);
+
+impl CompiledPatterns {
+    pub(crate) fn get_or_compile(&self, patterns: &[String]) -> &[(String, Pattern)] {
+        self.0.get_or_init(|| {
+            patterns
+                .iter()
+                .filter_map(|pattern| Pattern::new(pattern).ok().map(|compiled| (pattern.clone(), compiled)))
+                .collect()
+        })
+    }
+
+    /// パッケージ名のパターン用: 正規化した名前でコンパイルする
+    pub(crate) fn get_or_compile_names<'a>(&self, patterns: impl IntoIterator<Item = &'a str>) -> &[(String, Pattern)] {
+        self.0.get_or_init(|| {
+            patterns
+                .into_iter()
+                .filter_map(|pattern| {
+                    Pattern::new(&normalize_package_name(pattern)).ok().map(|compiled| (pattern.to_string(), compiled))
+                })
+                .collect()
+        })
+    }
+}
+
+/// 複製はキャッシュを引き継がない（`..policy.clone()` でパターンを差し替えた複製を作るため）
+impl Clone for CompiledPatterns {
+    fn clone(&self) -> Self {
+        CompiledPatterns::default()
+    }
+}
+
+impl PartialEq for CompiledPatterns {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 /// パッケージ固有の例外設定
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PackageException {
     pub name: String,
+    /// 完全一致のバージョン、`*`、または範囲指定（`>=1.0,<2.0`）
     pub version: Option<String>,
     pub reason: String,
+    /// 有効期限（この日を過ぎると例外として扱わない）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
 }
 
 /// ライセンスポリシー設定
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct LicensePolicy {
     /// ポリシー名
     pub name: String,
@@ -38,4 +149,84 @@ pub struct LicensePolicy {
     /// パッケージ固有の例外
     #[serde(default)]
     pub exceptions: Vec<PackageException>,
+    /// 違反メッセージのテンプレート（未設定なら既定のメッセージ）
+    #[serde(default, skip_serializing_if = "ViolationMessages::is_empty")]
+    pub messages: ViolationMessages,
+    /// プロプライエタリ（`License :: Other/Proprietary License`）の扱い。未設定なら通常のルールで判定
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proprietary: Option<CategoryAction>,
+    /// ライセンス情報が全くないパッケージの扱い（"forbid" / "review" / "allow"）。未設定なら Unknown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_missing_license: Option<CategoryAction>,
+    /// パブリックドメイン（Public Domain / CC0-1.0 / Unlicense）の扱い。未設定なら通常のルールで判定
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_domain: Option<CategoryAction>,
+    /// 社内パッケージ名のパターン（`acme-*` 等）。ライセンス未記載でも違反にしない（記載があれば通常どおり判定）
+    #[serde(default, skip_serializing_if = "Vec::is_empty", deserialize_with = "deserialize_name_patterns")]
+    pub internal_packages: Vec<String>,
+    /// コンパイル済みの社内パッケージのパターン（初回マッチ時に一度だけ生成）
+    #[serde(skip)]
+    pub(crate) compiled_internal: CompiledPatterns,
+    /// ライセンスに関係なく許可する標準ライブラリのバックポート。未設定なら組み込みリスト
+    /// （`BUILTIN_BACKPORTS`）、空リストで無効
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backport_allowlist: Option<Vec<String>>,
+    /// コンパイル済みのバックポートのパターン（初回マッチ時に一度だけ生成）
+    #[serde(skip)]
+    pub(crate) compiled_backports: CompiledPatterns,
+}
+
+/// パッケージ名のパターンのリスト。無効な glob は設定の読み込み時にエラーにする
+fn deserialize_name_patterns<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let patterns = Vec::<String>::deserialize(deserializer)?;
+    for pattern in &patterns {
+        if let Err(error) = Pattern::new(&normalize_package_name(pattern)) {
+            return Err(serde::de::Error::custom(format!("invalid package pattern '{}': {}", pattern, error)));
+        }
+    }
+    Ok(patterns)
+}
+
+/// 組み込みの標準ライブラリ・バックポート（ほぼ常に問題のないパッケージ）
+pub const BUILTIN_BACKPORTS: &[&str] = &[
+    "backports-*",
+    "contextlib2",
+    "dataclasses",
+    "enum34",
+    "exceptiongroup",
+    "funcsigs",
+    "futures",
+    "importlib-metadata",
+    "importlib-resources",
+    "pathlib2",
+    "singledispatch",
+    "tomli",
+    "typing",
+    "typing-extensions",
+    "zipp",
+];
+
+/// ライセンスカテゴリに対する扱い
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CategoryAction {
+    #[serde(alias = "allow")]
+    Allowed,
+    Review,
+    #[serde(alias = "forbid")]
+    Forbidden,
+}
+
+/// 違反レベルごとのメッセージテンプレート: `{license}` と `{package}` を置換
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ViolationMessages {
+    pub forbidden: Option<String>,
+    pub review_required: Option<String>,
+    pub unknown: Option<String>,
+}
+
+impl ViolationMessages {
+    pub fn is_empty(&self) -> bool {
+        self == &ViolationMessages::default()
+    }
 }
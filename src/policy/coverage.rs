@@ -0,0 +1,236 @@
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use super::config::LicensePolicy;
+use crate::exceptions::version_spec_matches;
+use crate::i18n::Locale;
+use crate::license::PackageLicense;
+use crate::uv_lock::normalize_package_name;
+
+/// 期限切れが近いとみなす日数
+pub const EXPIRY_WARNING_DAYS: u64 = 90;
+
+/// 例外で抑制されているリスクの量（管理下にあるコンプライアンス負債）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExceptionCoverage {
+    /// 例外を適用しない場合の違反数
+    pub potential_violations: usize,
+    /// 有効な例外で抑制された違反数
+    pub suppressed: usize,
+    /// 抑制に使われた例外のうち、90日以内に期限が切れるもの
+    pub expiring_soon: usize,
+    /// バックポートの許可リストで違反を免除されたパッケージ
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_backports: Vec<String>,
+}
+
+impl ExceptionCoverage {
+    /// 抑制された違反の割合（%）
+    pub fn percentage(&self) -> f64 {
+        if self.potential_violations == 0 {
+            return 0.0;
+        }
+        self.suppressed as f64 * 100.0 / self.potential_violations as f64
+    }
+}
+
+impl LicensePolicy {
+    /// 例外あり／なしで違反を検出し、その差分から例外のカバー率を求める
+    pub fn exception_coverage(&self, packages: &[PackageLicense], today: NaiveDate) -> ExceptionCoverage {
+        let without_exceptions = LicensePolicy { exceptions: vec![], ..self.clone() };
+        let potential = without_exceptions.detect_violations_at(packages, today, Locale::En);
+        let remaining = self.detect_violations_at(packages, today, Locale::En);
+
+        let horizon = today.checked_add_days(Days::new(EXPIRY_WARNING_DAYS)).unwrap_or(NaiveDate::MAX);
+        let mut used = Vec::new();
+        for violation in &potential.details {
+            if let Some(exception) = self.is_exception_at(&violation.package_name, violation.package_version.as_deref(), today) {
+                if !used.contains(&exception) {
+                    used.push(exception);
+                }
+            }
+        }
+
+        // 例外の対象外で、許可リストがなければ違反になるバックポート
+        let without_backports = LicensePolicy { exceptions: vec![], backport_allowlist: Some(vec![]), ..self.clone() };
+        let reported: Vec<&str> = remaining.details.iter().map(|v| v.package_name.as_str()).collect();
+        let allowed_backports = packages
+            .iter()
+            .filter(|package| self.is_backport_package(&package.name) && !reported.contains(&package.name.as_str()))
+            .filter(|package| self.is_exception_at(&package.name, package.version.as_deref(), today).is_none())
+            .filter(|package| without_backports.detect_violations_at(std::slice::from_ref(*package), today, Locale::En).total > 0)
+            .map(|package| package.name.clone())
+            .collect();
+
+        ExceptionCoverage {
+            potential_violations: potential.total,
+            suppressed: potential.total - remaining.total,
+            expiring_soon: used.iter().filter(|e| e.expires.is_some_and(|date| date <= horizon)).count(),
+            allowed_backports,
+        }
+    }
+}
+
+/// 何もしていない例外の種類
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleExceptionKind {
+    /// 期限切れ
+    Expired,
+    /// 該当するパッケージがインストールされていない
+    Stale,
+    /// 該当するパッケージは例外なしでも許可されている
+    Unnecessary,
+}
+
+/// 効果のない例外（`config --validate`）
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct IdleException {
+    pub name: String,
+    pub version: Option<String>,
+    pub kind: IdleExceptionKind,
+    /// 期限切れの場合の期限
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<NaiveDate>,
+}
+
+impl LicensePolicy {
+    /// 現在の環境で効果のない例外を探す。`(名前, バージョン, 期限)` を受け取るので、
+    /// pyproject の例外と `.exceptions.toml` の両方に使える
+    pub fn idle_exceptions<'a, I>(&self, entries: I, packages: &[PackageLicense], today: NaiveDate) -> Vec<IdleException>
+    where
+        I: IntoIterator<Item = (&'a str, Option<&'a str>, Option<NaiveDate>)>,
+    {
+        let without_exceptions = LicensePolicy { exceptions: vec![], ..self.clone() };
+        entries
+            .into_iter()
+            .filter_map(|(name, version, expires)| {
+                let normalized = normalize_package_name(name);
+                let matching: Vec<&PackageLicense> = packages
+                    .iter()
+                    .filter(|package| {
+                        normalize_package_name(&package.name) == normalized
+                            && version_spec_matches(version, package.version.as_deref())
+                    })
+                    .collect();
+
+                let kind = if expires.is_some_and(|date| date < today) {
+                    IdleExceptionKind::Expired
+                } else if matching.is_empty() {
+                    IdleExceptionKind::Stale
+                } else if matching.iter().all(|package| without_exceptions.detect_violations_at(std::slice::from_ref(*package), today, Locale::En).total == 0) {
+                    IdleExceptionKind::Unnecessary
+                } else {
+                    return None;
+                };
+                Some(IdleException {
+                    name: name.to_string(),
+                    version: version.map(str::to_string),
+                    kind,
+                    expires: expires.filter(|_| kind == IdleExceptionKind::Expired),
+                })
+            })
+            .collect()
+    }
+}
+
+/// 人間向けの表示
+pub fn format_exception_coverage(coverage: &ExceptionCoverage) -> String {
+    let mut text = format!(
+        "Potential violations: {}\nSuppressed by exceptions: {} ({:.1}%)\nExceptions expiring within {} days: {}",
+        coverage.potential_violations,
+        coverage.suppressed,
+        coverage.percentage(),
+        EXPIRY_WARNING_DAYS,
+        coverage.expiring_soon
+    );
+    if !coverage.allowed_backports.is_empty() {
+        text.push_str(&format!("\nAllowed as stdlib backports: {}", coverage.allowed_backports.join(", ")));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, license: &str) -> PackageLicense {
+        PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: Some(license.to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exception_coverage() {
+        let today = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let policy: LicensePolicy = toml::from_str(r#"
+name = "strict"
+exceptions = [
+    { name = "soon", reason = "migrating", expires = "2030-02-01" },
+    { name = "later", reason = "approved", expires = "2031-01-01" },
+    { name = "forever", reason = "approved" },
+    { name = "unused", reason = "stale", expires = "2030-01-15" },
+]
+[allowed_licenses]
+exact = ["MIT"]
+[forbidden_licenses]
+patterns = ["GPL-*"]
+"#).unwrap();
+        let packages = [
+            package("soon", "GPL-3.0"),
+            package("later", "GPL-3.0"),
+            package("forever", "Weird-1.0"),
+            package("open", "GPL-3.0"),
+            package("fine", "MIT"),
+        ];
+
+        let coverage = policy.exception_coverage(&packages, today);
+        assert_eq!(coverage, ExceptionCoverage { potential_violations: 4, suppressed: 3, expiring_soon: 1, allowed_backports: vec![] });
+        assert!(format_exception_coverage(&coverage).contains("Suppressed by exceptions: 3 (75.0%)"));
+
+        // 期限は渡した日付で判定する（実行日に依存しない）
+        let later = NaiveDate::from_ymd_opt(2032, 1, 1).unwrap();
+        assert_eq!(policy.exception_coverage(&packages, later).suppressed, 1);
+
+        // バックポートで免除された違反も報告する（禁止ライセンスは免除されない）
+        let packages = [package("typing_extensions", "Weird-1.0"), package("zipp", "GPL-3.0"), package("tomli", "MIT")];
+        let coverage = policy.exception_coverage(&packages, today);
+        assert_eq!(coverage.allowed_backports, vec!["typing_extensions"]);
+        assert!(format_exception_coverage(&coverage).ends_with("Allowed as stdlib backports: typing_extensions"));
+    }
+
+    #[test]
+    fn test_idle_exceptions() {
+        let today = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let policy: LicensePolicy = toml::from_str(r#"
+name = "strict"
+[allowed_licenses]
+exact = ["MIT"]
+[forbidden_licenses]
+patterns = ["GPL-*"]
+"#).unwrap();
+        let packages = [package("gpl-lib", "GPL-3.0"), package("fine", "MIT")];
+        let expired = NaiveDate::from_ymd_opt(2029, 6, 30);
+        let entries = [
+            ("gpl-lib", None, None),
+            ("Fine", None, None),
+            ("removed", None, None),
+            ("gpl-lib", Some("2.0"), None),
+            ("gpl-lib", Some(">=1.0,<2"), expired),
+        ];
+
+        let idle = policy.idle_exceptions(entries, &packages, today);
+        let kinds: Vec<_> = idle.iter().map(|e| (e.name.as_str(), e.kind)).collect();
+        // 有効な gpl-lib の例外は対象外、バージョン違いは未インストール扱い
+        assert_eq!(kinds, vec![
+            ("Fine", IdleExceptionKind::Unnecessary),
+            ("removed", IdleExceptionKind::Stale),
+            ("gpl-lib", IdleExceptionKind::Stale),
+            ("gpl-lib", IdleExceptionKind::Expired),
+        ]);
+        assert_eq!(idle[3].expires, expired);
+    }
+}
@@ -0,0 +1,170 @@
+use serde::Serialize;
+use super::config::{LicensePolicy, LicenseRule, PackageException};
+
+/// リストの追加・削除
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct ListDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl ListDiff {
+    fn between(old: &[String], new: &[String]) -> Self {
+        ListDiff {
+            added: new.iter().filter(|item| !old.contains(item)).cloned().collect(),
+            removed: old.iter().filter(|item| !new.contains(item)).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// ルール（exact / patterns）の差分
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct RuleDiff {
+    pub exact: ListDiff,
+    pub patterns: ListDiff,
+}
+
+impl RuleDiff {
+    fn between(old: &LicenseRule, new: &LicenseRule) -> Self {
+        RuleDiff {
+            exact: ListDiff::between(&old.exact, &new.exact),
+            patterns: ListDiff::between(&old.patterns, &new.patterns),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty() && self.patterns.is_empty()
+    }
+}
+
+/// 名前・説明などのスカラー設定の変更
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// 二つのポリシーの差分（ポリシー変更のレビュー用）
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct PolicyDiff {
+    pub metadata: Vec<FieldChange>,
+    pub allowed_licenses: RuleDiff,
+    pub forbidden_licenses: RuleDiff,
+    pub review_required: RuleDiff,
+    pub exceptions_added: Vec<PackageException>,
+    pub exceptions_removed: Vec<PackageException>,
+}
+
+impl PolicyDiff {
+    pub fn is_empty(&self) -> bool {
+        self == &PolicyDiff::default()
+    }
+}
+
+/// 列挙値などを設定ファイル上の表記で文字列化
+fn setting<T: Serialize>(value: &Option<T>) -> Option<String> {
+    let value = serde_json::to_value(value.as_ref()?).ok()?;
+    Some(value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string()))
+}
+
+pub fn diff_policies(old: &LicensePolicy, new: &LicensePolicy) -> PolicyDiff {
+    let fields = [
+        ("name", Some(old.name.clone()), Some(new.name.clone())),
+        ("description", old.description.clone(), new.description.clone()),
+        ("proprietary", setting(&old.proprietary), setting(&new.proprietary)),
+        ("on_missing_license", setting(&old.on_missing_license), setting(&new.on_missing_license)),
+        ("public_domain", setting(&old.public_domain), setting(&new.public_domain)),
+        ("internal_packages", Some(old.internal_packages.join(", ")).filter(|p| !p.is_empty()), Some(new.internal_packages.join(", ")).filter(|p| !p.is_empty())),
+        ("backport_allowlist", old.backport_allowlist.as_ref().map(|list| list.join(", ")), new.backport_allowlist.as_ref().map(|list| list.join(", "))),
+        ("messages.forbidden", old.messages.forbidden.clone(), new.messages.forbidden.clone()),
+        ("messages.review_required", old.messages.review_required.clone(), new.messages.review_required.clone()),
+        ("messages.unknown", old.messages.unknown.clone(), new.messages.unknown.clone()),
+    ];
+    let metadata = fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| FieldChange { field: field.to_string(), old, new })
+        .collect();
+
+    PolicyDiff {
+        metadata,
+        allowed_licenses: RuleDiff::between(&old.allowed_licenses, &new.allowed_licenses),
+        forbidden_licenses: RuleDiff::between(&old.forbidden_licenses, &new.forbidden_licenses),
+        review_required: RuleDiff::between(&old.review_required, &new.review_required),
+        exceptions_added: new.exceptions.iter().filter(|e| !old.exceptions.contains(e)).cloned().collect(),
+        exceptions_removed: old.exceptions.iter().filter(|e| !new.exceptions.contains(e)).cloned().collect(),
+    }
+}
+
+/// 人間向けの差分表示（`+` 追加 / `-` 削除 / `~` 変更）
+pub fn format_policy_diff(diff: &PolicyDiff) -> String {
+    if diff.is_empty() {
+        return "No policy changes".to_string();
+    }
+
+    let mut lines = Vec::new();
+    for change in &diff.metadata {
+        let show = |value: &Option<String>| value.as_deref().map_or("(unset)".to_string(), |v| format!("\"{}\"", v));
+        lines.push(format!("~ {}: {} → {}", change.field, show(&change.old), show(&change.new)));
+    }
+    let rules = [
+        ("allowed_licenses", &diff.allowed_licenses),
+        ("forbidden_licenses", &diff.forbidden_licenses),
+        ("review_required", &diff.review_required),
+    ];
+    for (name, rule) in rules {
+        for (kind, list) in [("exact", &rule.exact), ("patterns", &rule.patterns)] {
+            lines.extend(list.added.iter().map(|item| format!("+ {}.{}: {}", name, kind, item)));
+            lines.extend(list.removed.iter().map(|item| format!("- {}.{}: {}", name, kind, item)));
+        }
+    }
+    let exception = |e: &PackageException| format!("{} {} ({})", e.name, e.version.as_deref().unwrap_or("*"), e.reason);
+    lines.extend(diff.exceptions_added.iter().map(|e| format!("+ exception: {}", exception(e))));
+    lines.extend(diff.exceptions_removed.iter().map(|e| format!("- exception: {}", exception(e))));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_diff() {
+        let old: LicensePolicy = toml::from_str(r#"
+name = "Green"
+[allowed_licenses]
+exact = ["MIT", "ISC"]
+[forbidden_licenses]
+patterns = ["GPL-*"]
+"#).unwrap();
+        let new: LicensePolicy = toml::from_str(r#"
+name = "Green"
+proprietary = "review"
+exceptions = [{ name = "legacy", version = "1.0", reason = "approved" }]
+[allowed_licenses]
+exact = ["MIT", "Apache-2.0"]
+[forbidden_licenses]
+patterns = ["GPL-*", "AGPL-*"]
+"#).unwrap();
+
+        let diff = diff_policies(&old, &new);
+        assert_eq!(diff.allowed_licenses.exact, ListDiff { added: vec!["Apache-2.0".to_string()], removed: vec!["ISC".to_string()] });
+        assert_eq!(diff.forbidden_licenses.patterns.added, vec!["AGPL-*"]);
+        assert!(diff.review_required.is_empty());
+        assert_eq!(diff.exceptions_added.len(), 1);
+        assert_eq!(format_policy_diff(&diff), [
+            "~ proprietary: (unset) → \"review\"",
+            "+ allowed_licenses.exact: Apache-2.0",
+            "- allowed_licenses.exact: ISC",
+            "+ forbidden_licenses.patterns: AGPL-*",
+            "+ exception: legacy 1.0 (approved)",
+        ].join("\n"));
+
+        assert_eq!(format_policy_diff(&diff_policies(&new, &new)), "No policy changes");
+    }
+}
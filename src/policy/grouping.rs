@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use super::checker::Violation;
+use super::matcher::ViolationLevel;
+use crate::license::normalize_license_name;
+
+/// 同じライセンスの違反をまとめたグループ
+#[derive(Debug, Serialize)]
+pub struct LicenseGroup {
+    pub license: String,
+    pub normalized_license: String,
+    pub packages: Vec<Violation>,
+    pub violation_type: String,
+}
+
+/// 違反を正規化したライセンスごとにまとめる（ライセンス名順）
+pub fn group_violations_by_license(violations: Vec<Violation>) -> Vec<LicenseGroup> {
+    let mut groups: HashMap<String, LicenseGroup> = HashMap::new();
+
+    for violation in violations {
+        let license = violation.license.clone().unwrap_or_else(|| "Unknown".to_string());
+        let normalized = normalize_license_name(&license);
+
+        let violation_type = match violation.violation_level {
+            ViolationLevel::Forbidden => "Forbidden license",
+            ViolationLevel::ReviewRequired => "Review required",
+            ViolationLevel::Unknown => "Unknown license",
+            _ => "Violation",
+        };
+
+        groups.entry(normalized.clone())
+            .or_insert_with(|| LicenseGroup {
+                license,
+                normalized_license: normalized,
+                packages: Vec::new(),
+                violation_type: violation_type.to_string(),
+            })
+            .packages.push(violation);
+    }
+
+    let mut result: Vec<_> = groups.into_values().collect();
+    result.sort_by(|a, b| a.normalized_license.cmp(&b.normalized_license));
+    result
+}
@@ -1,61 +1,123 @@
 use serde::{Deserialize, Serialize};
 use glob::Pattern;
-use super::config::{LicenseRule, LicensePolicy, PackageException};
+use super::config::{CategoryAction, LicenseRule, LicensePolicy, PackageException, RuleSeverity, BUILTIN_BACKPORTS};
+use crate::license::{is_public_domain, normalize_license_name, LicenseExpression, PROPRIETARY};
+use crate::uv_lock::normalize_package_name;
+use crate::exceptions::version_spec_matches;
 
 /// 違反レベル
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum ViolationLevel {
     Allowed,
     ReviewRequired,
     Forbidden,
+    #[default]
     Unknown,
 }
 
+impl ViolationLevel {
+    /// 厳しさの順位（ORは最も緩い分岐、ANDは最も厳しい分岐を採用）
+    fn severity(&self) -> u8 {
+        match self {
+            ViolationLevel::Allowed => 0,
+            ViolationLevel::ReviewRequired => 1,
+            ViolationLevel::Unknown => 2,
+            ViolationLevel::Forbidden => 3,
+        }
+    }
+}
+
+/// ライセンス式の評価結果（判定を決めた分岐のライセンスとレベル）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionOutcome {
+    pub level: ViolationLevel,
+    pub license: String,
+}
+
+/// `GPL-3.0-only` / `GPL-3.0-or-later` / `GPL-3.0+` の基本ID（`GPL-3.0`）
+fn variant_base(license: &str) -> Option<&str> {
+    license
+        .strip_suffix("-only")
+        .or_else(|| license.strip_suffix("-or-later"))
+        .or_else(|| license.strip_suffix('+'))
+}
+
 impl LicenseRule {
+    /// パターンは初回呼び出し時に一度だけコンパイルされる
+    pub(crate) fn compiled_patterns(&self) -> &[(String, Pattern)] {
+        self.compiled.get_or_compile(&self.patterns)
+    }
+
+    /// 完全一致（基本IDの展開を含む）した exact エントリ
+    fn exact_match(&self, license: &str) -> Option<&String> {
+        self.exact.iter().find(|exact| *exact == license).or_else(|| {
+            // 互換: `GPL-3.0` は `-only` / `-or-later` の両方にマッチする
+            let base = variant_base(license)?;
+            self.exact.iter().find(|exact| *exact == base)
+        })
+    }
+
     /// ライセンス名がこのルールにマッチするかチェック
     pub fn matches(&self, license: &str) -> bool {
         // 完全一致をチェック
-        if self.exact.iter().any(|exact| exact == license) {
+        if self.exact_match(license).is_some() {
             return true;
         }
         
-        // パターンマッチングをチェック
-        for pattern_str in &self.patterns {
-            if let Ok(pattern) = Pattern::new(pattern_str) {
-                if pattern.matches(license) {
-                    return true;
-                }
-            }
-        }
-        
-        false
+        // パターンマッチングをチェック（コンパイル済みパターンを再利用）
+        self.compiled_patterns().iter().any(|(_, pattern)| pattern.matches(license))
     }
     
     /// マッチしたルール（完全一致またはパターン）を返す
     pub fn find_match(&self, license: &str) -> Option<String> {
         // 完全一致をチェック
-        for exact in &self.exact {
-            if exact == license {
-                return Some(format!("exact: {}", exact));
-            }
+        if let Some(exact) = self.exact_match(license) {
+            return Some(format!("exact: {}", exact));
         }
         
         // パターンマッチングをチェック
-        for pattern_str in &self.patterns {
-            if let Ok(pattern) = Pattern::new(pattern_str) {
-                if pattern.matches(license) {
-                    return Some(format!("pattern: {}", pattern_str));
-                }
-            }
-        }
-        
-        None
+        self.compiled_patterns()
+            .iter()
+            .find(|(_, pattern)| pattern.matches(license))
+            .map(|(pattern_str, _)| format!("pattern: {}", pattern_str))
+    }
+
+    /// マッチした要素に指定された重大度（指定がなければ None）
+    pub fn severity_for(&self, license: &str) -> Option<RuleSeverity> {
+        let entry = self.exact_match(license).or_else(|| {
+            self.compiled_patterns()
+                .iter()
+                .find(|(_, pattern)| pattern.matches(license))
+                .map(|(pattern_str, _)| pattern_str)
+        })?;
+        self.severities.get(entry).copied()
     }
 }
 
 impl LicensePolicy {
     /// ライセンスの違反レベルをチェック
     pub fn check_license(&self, license: &str) -> ViolationLevel {
+        // カテゴリ指定があればそれを優先
+        if let Some(action) = self.category_action(license) {
+            return match action {
+                CategoryAction::Allowed => ViolationLevel::Allowed,
+                CategoryAction::Review => ViolationLevel::ReviewRequired,
+                CategoryAction::Forbidden => ViolationLevel::Forbidden,
+            };
+        }
+
+        // バリアントを明示したルール（`GPL-3.0-or-later` 等）は基本IDの展開より優先
+        if variant_base(license).is_some() {
+            let rules = [
+                (&self.forbidden_licenses, ViolationLevel::Forbidden),
+                (&self.allowed_licenses, ViolationLevel::Allowed),
+                (&self.review_required, ViolationLevel::ReviewRequired),
+            ];
+            if let Some((_, level)) = rules.into_iter().find(|(rule, _)| rule.exact.iter().any(|exact| exact == license)) {
+                return level;
+            }
+        }
+
         // 禁止リストを最初にチェック（最も重要）
         if self.forbidden_licenses.matches(license) {
             return ViolationLevel::Forbidden;
@@ -75,12 +137,82 @@ impl LicensePolicy {
         ViolationLevel::Unknown
     }
     
-    /// パッケージが例外リストに含まれているかチェック
+    /// ライセンスが属するカテゴリ名（"proprietary" / "public-domain"）
+    pub(crate) fn category(license: &str) -> Option<&'static str> {
+        if license == PROPRIETARY {
+            Some("proprietary")
+        } else if is_public_domain(license) {
+            Some("public-domain")
+        } else {
+            None
+        }
+    }
+
+    /// ライセンスが属するカテゴリの扱い（設定されている場合のみ）
+    pub(crate) fn category_action(&self, license: &str) -> Option<CategoryAction> {
+        match Self::category(license)? {
+            "proprietary" => self.proprietary,
+            _ => self.public_domain,
+        }
+    }
+
+    /// 単一のSPDX識別子をチェック（未知なら正規化した名前で再チェック）
+    fn check_identifier(&self, id: &str) -> ExpressionOutcome {
+        let level = self.check_license(id);
+        if level != ViolationLevel::Unknown {
+            return ExpressionOutcome { level, license: id.to_string() };
+        }
+
+        let normalized = normalize_license_name(id);
+        ExpressionOutcome { level: self.check_license(&normalized), license: normalized }
+    }
+
+    /// SPDXライセンス式を評価: ORはいずれかの分岐が通れば良く、ANDは全分岐が通る必要がある
+    pub fn evaluate_expression(&self, expression: &LicenseExpression) -> ExpressionOutcome {
+        match expression {
+            LicenseExpression::License(id) | LicenseExpression::With(id, _) => self.check_identifier(id),
+            LicenseExpression::And(left, right) => {
+                let (left, right) = (self.evaluate_expression(left), self.evaluate_expression(right));
+                if right.level.severity() > left.level.severity() { right } else { left }
+            }
+            LicenseExpression::Or(left, right) => {
+                let (left, right) = (self.evaluate_expression(left), self.evaluate_expression(right));
+                if right.level.severity() < left.level.severity() { right } else { left }
+            }
+        }
+    }
+
+    /// `internal_packages` のパターンに一致する社内パッケージか（正規化した名前で比較）
+    pub fn is_internal_package(&self, package_name: &str) -> bool {
+        let package_name = normalize_package_name(package_name);
+        self.compiled_internal
+            .get_or_compile_names(self.internal_packages.iter().map(String::as_str))
+            .iter()
+            .any(|(_, pattern)| pattern.matches(&package_name))
+    }
+
+    /// 標準ライブラリのバックポートとして許可リストに含まれるか（正規化した名前で比較）
+    pub fn is_backport_package(&self, package_name: &str) -> bool {
+        let package_name = normalize_package_name(package_name);
+        let compiled = match &self.backport_allowlist {
+            Some(patterns) => self.compiled_backports.get_or_compile_names(patterns.iter().map(String::as_str)),
+            None => self.compiled_backports.get_or_compile_names(BUILTIN_BACKPORTS.iter().copied()),
+        };
+        compiled.iter().any(|(_, pattern)| pattern.matches(&package_name))
+    }
+
+    /// パッケージが例外リストに含まれているかチェック（期限切れの例外は除く）
     pub fn is_exception(&self, package_name: &str, package_version: Option<&str>) -> Option<&PackageException> {
+        self.is_exception_at(package_name, package_version, chrono::Utc::now().date_naive())
+    }
+
+    /// `today` 時点で有効な例外を探す（カバー率など日付を固定して評価する場合）
+    pub fn is_exception_at(&self, package_name: &str, package_version: Option<&str>, today: chrono::NaiveDate) -> Option<&PackageException> {
+        let package_name = normalize_package_name(package_name);
         self.exceptions.iter().find(|exception| {
-            exception.name == package_name && 
-            (exception.version.is_none() || 
-             exception.version.as_deref() == package_version)
+            normalize_package_name(&exception.name) == package_name && 
+            version_spec_matches(exception.version.as_deref(), package_version) &&
+            exception.expires.is_none_or(|expires| today <= expires)
         })
     }
 }
@@ -94,6 +226,7 @@ mod tests {
         let rule = LicenseRule {
             exact: vec!["MIT".to_string(), "Apache-2.0".to_string()],
             patterns: vec![],
+            ..Default::default()
         };
 
         assert!(rule.matches("MIT"));
@@ -106,6 +239,7 @@ mod tests {
         let rule = LicenseRule {
             exact: vec![],
             patterns: vec!["GPL-*".to_string(), "BSD-*".to_string()],
+            ..Default::default()
         };
 
         assert!(rule.matches("GPL-3.0"));
@@ -113,24 +247,67 @@ mod tests {
         assert!(!rule.matches("MIT"));
     }
 
+    #[test]
+    fn test_patterns_compiled_once() {
+        let rule = LicenseRule {
+            exact: vec![],
+            patterns: vec!["GPL-*".to_string(), "[invalid".to_string()],
+            ..Default::default()
+        };
+
+        let first = rule.compiled_patterns().as_ptr();
+        for license in ["GPL-2.0", "GPL-3.0", "MIT", "BSD-3-Clause"] {
+            rule.matches(license);
+            rule.find_match(license);
+        }
+        // The same compiled patterns are reused for every package
+        assert!(std::ptr::eq(first, rule.compiled_patterns().as_ptr()));
+        assert_eq!(rule.compiled_patterns().len(), 1);
+        assert_eq!(rule.find_match("GPL-3.0"), Some("pattern: GPL-*".to_string()));
+    }
+
+    #[test]
+    fn test_base_id_matches_gpl_variants() {
+        let forbidden = LicenseRule { exact: vec!["GPL-3.0".to_string()], ..Default::default() };
+        assert!(forbidden.matches("GPL-3.0-only"));
+        assert!(forbidden.matches("GPL-3.0-or-later"));
+        assert!(forbidden.matches("GPL-3.0+"));
+        assert!(!forbidden.matches("LGPL-3.0-only"));
+        assert!(!forbidden.matches("GPL-2.0-only"));
+        assert_eq!(forbidden.find_match("GPL-3.0-or-later"), Some("exact: GPL-3.0".to_string()));
+
+        // An explicit variant elsewhere in the policy wins over the base-id expansion
+        let policy = LicensePolicy {
+            name: "Variants".to_string(),
+            allowed_licenses: LicenseRule { exact: vec!["GPL-3.0-or-later".to_string()], ..Default::default() },
+            forbidden_licenses: forbidden,
+            ..Default::default()
+        };
+        assert_eq!(policy.check_license("GPL-3.0-only"), ViolationLevel::Forbidden);
+        assert_eq!(policy.check_license("GPL-3.0-or-later"), ViolationLevel::Allowed);
+        assert_eq!(policy.check_license("GPL-3.0"), ViolationLevel::Forbidden);
+    }
+
     #[test]
     fn test_license_policy_check() {
         let policy = LicensePolicy {
             name: "Test Policy".to_string(),
-            description: None,
             allowed_licenses: LicenseRule {
                 exact: vec!["MIT".to_string()],
                 patterns: vec![],
+                ..Default::default()
             },
             forbidden_licenses: LicenseRule {
                 exact: vec!["GPL-3.0".to_string()],
                 patterns: vec![],
+                ..Default::default()
             },
             review_required: LicenseRule {
                 exact: vec!["Apache-2.0".to_string()],
                 patterns: vec![],
+                ..Default::default()
             },
-            exceptions: vec![],
+            ..Default::default()
         };
 
         assert_eq!(policy.check_license("MIT"), ViolationLevel::Allowed);
@@ -1,8 +1,16 @@
 pub mod config;
 pub mod matcher;
 pub mod checker;
+pub mod diff;
+pub mod coverage;
+pub mod cases;
+pub mod grouping;
 
 // Re-export main types
-pub use config::{LicensePolicy, LicenseRule, PackageException};
+pub use config::{CategoryAction, BUILTIN_BACKPORTS, LicensePolicy, LicenseRule, PackageException, RuleSeverity, ViolationMessages};
 pub use matcher::ViolationLevel;
 pub use checker::{Violation, ViolationSummary};
+pub use coverage::{format_exception_coverage, ExceptionCoverage, IdleException, IdleExceptionKind};
+pub use cases::{format_policy_test_results, load_policy_tests, PolicyTestCase, PolicyTestResult};
+pub use grouping::{group_violations_by_license, LicenseGroup};
+pub use diff::{diff_policies, format_policy_diff, PolicyDiff};
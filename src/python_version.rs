@@ -0,0 +1,133 @@
+use std::fs;
+use std::path::Path;
+
+/// A Python version such as `3.12` or `3.12.1`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonVersion(pub Vec<u32>);
+
+impl PythonVersion {
+    pub fn parse(version: &str) -> Option<Self> {
+        let parts: Option<Vec<u32>> = version.trim().split('.').map(|part| part.parse().ok()).collect();
+        parts.filter(|parts| !parts.is_empty()).map(PythonVersion)
+    }
+
+    /// Component-wise comparison over the first `len` components, padding with zeros
+    fn cmp_prefix(&self, other: &PythonVersion, len: usize) -> std::cmp::Ordering {
+        let component = |v: &PythonVersion, i: usize| v.0.get(i).copied().unwrap_or(0);
+        (0..len)
+            .map(|i| component(self, i).cmp(&component(other, i)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+
+    /// Release ordering: `1.10` sorts after `1.9`, and `1.0` equals `1.0.0`
+    pub fn cmp_release(&self, other: &PythonVersion) -> std::cmp::Ordering {
+        self.cmp_prefix(other, self.0.len().max(other.0.len()))
+    }
+}
+
+impl std::fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(|part| part.to_string()).collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+/// Check a PEP 440 `requires-python` spec (e.g. `>=3.9, <4`, `==3.12.*`, `~=3.10`).
+/// Returns None when the spec cannot be understood.
+pub fn satisfies(spec: &str, version: &PythonVersion) -> Option<bool> {
+    for clause in spec.split(',').map(str::trim).filter(|clause| !clause.is_empty()) {
+        let (operator, target) = ["~=", "==", "!=", ">=", "<=", ">", "<"]
+            .iter()
+            .find_map(|op| clause.strip_prefix(op).map(|rest| (*op, rest.trim())))?;
+
+        let (target, wildcard) = match target.strip_suffix(".*") {
+            Some(prefix) => (prefix, true),
+            None => (target, false),
+        };
+        let target = PythonVersion::parse(target)?;
+        let len = if wildcard { target.0.len() } else { target.0.len().max(version.0.len()) };
+        let ordering = version.cmp_prefix(&target, len);
+
+        let ok = match operator {
+            "==" => ordering.is_eq(),
+            "!=" => ordering.is_ne(),
+            ">=" => ordering.is_ge(),
+            "<=" => ordering.is_le(),
+            ">" => ordering.is_gt(),
+            "<" => ordering.is_lt(),
+            // ~=3.10 means >=3.10, ==3.*
+            _ => ordering.is_ge() && version.cmp_prefix(&target, target.0.len().saturating_sub(1)).is_eq(),
+        };
+        if !ok {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+/// Detect the Python version of the environment that owns `site_packages`,
+/// from `pyvenv.cfg` or the `lib/pythonX.Y` directory name
+pub fn detect_python_version(site_packages: &Path) -> Option<PythonVersion> {
+    for dir in site_packages.ancestors().skip(1).take(3) {
+        if let Ok(content) = fs::read_to_string(dir.join("pyvenv.cfg")) {
+            let version = content.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                matches!(key.trim(), "version_info" | "version").then(|| value.trim().to_string())
+            });
+            if let Some(version) = version.and_then(|v| PythonVersion::parse(&v)) {
+                return Some(version);
+            }
+        }
+    }
+
+    site_packages
+        .ancestors()
+        .filter_map(|dir| dir.file_name()?.to_str()?.strip_prefix("python").map(str::to_string))
+        .find_map(|version| PythonVersion::parse(&version))
+}
+
+/// Informational note when the audited interpreter is outside uv.lock's `requires-python`
+pub fn requires_python_note(requires_python: &str, version: &PythonVersion) -> Option<String> {
+    match satisfies(requires_python, version) {
+        Some(false) => Some(format!(
+            "Auditing a Python {} environment, but uv.lock requires-python is '{}'; \
+             packages locked for other interpreters may be missing, so counts can differ across environments",
+            version, requires_python
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> PythonVersion {
+        PythonVersion::parse(version).unwrap()
+    }
+
+    #[test]
+    fn test_satisfies_requires_python_specs() {
+        assert_eq!(satisfies(">=3.10", &v("3.12.1")), Some(true));
+        assert_eq!(satisfies(">=3.10", &v("3.9")), Some(false));
+        assert_eq!(satisfies(">=3.8, <4", &v("3.12")), Some(true));
+        assert_eq!(satisfies("==3.12.*", &v("3.12.4")), Some(true));
+        assert_eq!(satisfies("==3.12.*", &v("3.11.9")), Some(false));
+        assert_eq!(satisfies("~=3.10", &v("3.13")), Some(true));
+        assert_eq!(satisfies("~=3.10", &v("4.0")), Some(false));
+        assert_eq!(satisfies("!=3.9.0", &v("3.9")), Some(false));
+        assert_eq!(satisfies("whatever", &v("3.9")), None);
+    }
+
+    #[test]
+    fn test_detect_python_version() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let site_packages = temp_dir.path().join(".venv/lib/python3.11/site-packages");
+        fs::create_dir_all(&site_packages).unwrap();
+        assert_eq!(detect_python_version(&site_packages), Some(v("3.11")));
+
+        fs::write(temp_dir.path().join(".venv/pyvenv.cfg"), "home = /usr/bin\nversion_info = 3.11.7\n").unwrap();
+        assert_eq!(detect_python_version(&site_packages), Some(v("3.11.7")));
+    }
+}
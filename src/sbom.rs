@@ -0,0 +1,487 @@
+use serde_json::{json, Value};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use uuid::Uuid;
+use crate::license::{normalize_license_name, DetectionStatus, LicenseReport, PackageLicense};
+use crate::python_version::PythonVersion;
+use crate::uv_lock::normalize_package_name;
+
+/// Component ordering for SBOM output
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SbomSort {
+    /// Sort by normalized package name, then version (default)
+    #[default]
+    Name,
+    /// Keep the order in which packages were extracted
+    None,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SbomOptions {
+    pub sort: SbomSort,
+    /// Derive serial numbers / namespaces from content and omit wall-clock timestamps
+    pub deterministic: bool,
+}
+
+const TOOL_NAME: &str = env!("CARGO_PKG_NAME");
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+fn purl(package: &PackageLicense) -> String {
+    match &package.version {
+        Some(version) => format!("pkg:pypi/{}@{}", normalize_package_name(&package.name), version),
+        None => format!("pkg:pypi/{}", normalize_package_name(&package.name)),
+    }
+}
+
+/// Returns true if the license string can be used as an SPDX identifier/expression as-is
+fn is_spdx_like(license: &str) -> bool {
+    let mut expect_id = true;
+    for token in license.split_whitespace() {
+        let is_operator = matches!(token, "AND" | "OR" | "WITH");
+        if is_operator == expect_id {
+            return false;
+        }
+        if !is_operator {
+            let id = token.trim_matches(|c| c == '(' || c == ')');
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '+') {
+                return false;
+            }
+        }
+        expect_id = is_operator;
+    }
+    !expect_id
+}
+
+/// Numeric release order; versions that don't parse (pre-releases, local builds) come
+/// after the parsed ones, in their original order
+fn compare_versions(a: Option<&str>, b: Option<&str>) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (a.and_then(PythonVersion::parse), b.and_then(PythonVersion::parse)) {
+        (Some(a), Some(b)) => a.cmp_release(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn ordered_packages(report: &LicenseReport, sort: SbomSort) -> Vec<&PackageLicense> {
+    let mut packages: Vec<&PackageLicense> = report.packages.iter().collect();
+    if sort == SbomSort::Name {
+        packages.sort_by(|a, b| {
+            normalize_package_name(&a.name)
+                .cmp(&normalize_package_name(&b.name))
+                .then_with(|| compare_versions(a.version.as_deref(), b.version.as_deref()))
+        });
+    }
+    packages
+}
+
+/// Content-derived UUID used for serial numbers under `--deterministic`
+fn content_uuid(packages: &[&PackageLicense]) -> Uuid {
+    let mut canonical = String::new();
+    for package in packages {
+        canonical.push_str(&format!(
+            "{}@{}={}\n",
+            normalize_package_name(&package.name),
+            package.version.as_deref().unwrap_or(""),
+            package.effective_license.as_deref().unwrap_or("")
+        ));
+    }
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, canonical.as_bytes())
+}
+
+/// Creation time for deterministic documents: honors SOURCE_DATE_EPOCH, else the Unix epoch
+fn deterministic_timestamp() -> DateTime<Utc> {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<i64>().ok())
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .unwrap_or(DateTime::UNIX_EPOCH)
+}
+
+/// Render the report as a CycloneDX 1.5 JSON document
+pub fn format_cyclonedx(report: &LicenseReport, options: &SbomOptions) -> Result<String> {
+    let packages = ordered_packages(report, options.sort);
+
+    let serial = if options.deterministic {
+        content_uuid(&packages)
+    } else {
+        Uuid::new_v4()
+    };
+
+    let components: Vec<Value> = packages
+        .iter()
+        .map(|package| {
+            let mut component = json!({
+                "type": "library",
+                "bom-ref": purl(package),
+                "name": package.name,
+                "purl": purl(package),
+            });
+            if let Some(version) = &package.version {
+                component["version"] = json!(version);
+            }
+            if let Some(license) = &package.effective_license {
+                component["licenses"] = if license.contains(' ') && is_spdx_like(license) {
+                    json!([{ "expression": license }])
+                } else if is_spdx_like(license) {
+                    json!([{ "license": { "id": license } }])
+                } else {
+                    json!([{ "license": { "name": license } }])
+                };
+            }
+            component
+        })
+        .collect();
+
+    let mut metadata = json!({
+        "tools": [{ "name": TOOL_NAME, "version": TOOL_VERSION }],
+    });
+    if !options.deterministic {
+        metadata["timestamp"] = json!(Utc::now().to_rfc3339());
+    }
+
+    let document = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", serial),
+        "version": 1,
+        "metadata": metadata,
+        "components": components,
+    });
+
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Render the report as an SPDX 2.3 JSON document
+pub fn format_spdx(report: &LicenseReport, options: &SbomOptions) -> Result<String> {
+    let packages = ordered_packages(report, options.sort);
+
+    let (namespace_id, created) = if options.deterministic {
+        (content_uuid(&packages), deterministic_timestamp())
+    } else {
+        (Uuid::new_v4(), Utc::now())
+    };
+
+    let spdx_packages: Vec<Value> = packages
+        .iter()
+        .enumerate()
+        .map(|(i, package)| {
+            let declared = match &package.effective_license {
+                Some(license) if is_spdx_like(license) => license.clone(),
+                _ => "NOASSERTION".to_string(),
+            };
+            let mut spdx_package = json!({
+                "name": package.name,
+                "SPDXID": format!("SPDXRef-Package-{}", i + 1),
+                "downloadLocation": "NOASSERTION",
+                "licenseConcluded": declared,
+                "licenseDeclared": declared,
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": purl(package),
+                }],
+            });
+            if let Some(version) = &package.version {
+                spdx_package["versionInfo"] = json!(version);
+            }
+            if let Some(license) = package.effective_license.as_deref().filter(|l| !is_spdx_like(l)) {
+                spdx_package["licenseComments"] = json!(format!("Detected license: {}", license));
+            }
+            spdx_package
+        })
+        .collect();
+
+    let document = json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": format!("{}-sbom", TOOL_NAME),
+        "documentNamespace": format!("https://spdx.org/spdxdocs/{}-{}", TOOL_NAME, namespace_id),
+        "creationInfo": {
+            "created": created.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            "creators": [format!("Tool: {}-{}", TOOL_NAME, TOOL_VERSION)],
+        },
+        "packages": spdx_packages,
+    });
+
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// SBOM formats accepted by `check --sbom`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SbomInput {
+    CycloneDx,
+    SpdxJson,
+    SpdxTagValue,
+}
+
+/// Tell the format from the content: CycloneDX JSON has `bomFormat`, SPDX JSON `spdxVersion`,
+/// SPDX tag-value starts with an `SPDXVersion:` line
+pub fn detect_sbom_format(content: &str) -> Option<SbomInput> {
+    let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+    if trimmed.starts_with('{') {
+        let document: Value = serde_json::from_str(trimmed).ok()?;
+        if document.get("bomFormat").and_then(Value::as_str) == Some("CycloneDX") {
+            return Some(SbomInput::CycloneDx);
+        }
+        return document.get("spdxVersion").map(|_| SbomInput::SpdxJson);
+    }
+    trimmed.lines().any(|line| line.trim().starts_with("SPDXVersion:")).then_some(SbomInput::SpdxTagValue)
+}
+
+fn sbom_package(name: &str, version: Option<&str>, license: Option<String>, source: &str) -> PackageLicense {
+    let effective_license = license.as_deref().map(normalize_license_name);
+    PackageLicense {
+        name: name.to_string(),
+        version: version.map(str::to_string),
+        detection_status: if effective_license.is_some() { DetectionStatus::Found } else { DetectionStatus::NoLicenseField },
+        effective_license,
+        license_classifiers: vec![],
+        metadata_source: source.to_string(),
+        license_expression: None,
+        warnings: vec![],
+        annotation: None,
+        raw_license: license,
+        metadata_name: None,
+        license_confidence: None,
+        license_url: None,
+    }
+}
+
+/// SPDX placeholders that carry no license information
+fn spdx_license(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|license| !license.is_empty() && !matches!(*license, "NOASSERTION" | "NONE")).map(str::to_string)
+}
+
+/// A CycloneDX `licenses` array: an expression, or license ids/names joined with AND
+fn cyclonedx_license(component: &Value) -> Option<String> {
+    let entries = component.get("licenses")?.as_array()?;
+    let licenses: Vec<&str> = entries
+        .iter()
+        .filter_map(|entry| {
+            entry.get("expression").and_then(Value::as_str).or_else(|| {
+                let license = entry.get("license")?;
+                license.get("id").or_else(|| license.get("name")).and_then(Value::as_str)
+            })
+        })
+        .collect();
+    match licenses.as_slice() {
+        [] => None,
+        [license] => Some(license.to_string()),
+        _ => Some(licenses.iter().map(|license| format!("({})", license)).collect::<Vec<_>>().join(" AND ")),
+    }
+}
+
+fn read_cyclonedx(document: &Value, packages: &mut Vec<PackageLicense>) {
+    let Some(components) = document.get("components").and_then(Value::as_array) else { return };
+    for component in components {
+        if let Some(name) = component.get("name").and_then(Value::as_str) {
+            let version = component.get("version").and_then(Value::as_str);
+            packages.push(sbom_package(name, version, cyclonedx_license(component), "cyclonedx"));
+        }
+        // Nested components (e.g. the contents of a bundled application)
+        read_cyclonedx(component, packages);
+    }
+}
+
+fn read_spdx_json(document: &Value, packages: &mut Vec<PackageLicense>) {
+    // The package the document describes is the project itself, not a dependency
+    let described: Vec<&str> = document
+        .get("documentDescribes")
+        .and_then(Value::as_array)
+        .map(|ids| ids.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let Some(spdx_packages) = document.get("packages").and_then(Value::as_array) else { return };
+    for package in spdx_packages {
+        let Some(name) = package.get("name").and_then(Value::as_str) else { continue };
+        if package.get("SPDXID").and_then(Value::as_str).is_some_and(|id| described.contains(&id)) {
+            continue;
+        }
+        let field = |key: &str| spdx_license(package.get(key).and_then(Value::as_str));
+        // `format_spdx` keeps non-SPDX licenses in licenseComments
+        let commented = package
+            .get("licenseComments")
+            .and_then(Value::as_str)
+            .and_then(|comment| comment.strip_prefix("Detected license: "))
+            .map(str::to_string);
+        let license = field("licenseDeclared").or_else(|| field("licenseConcluded")).or(commented);
+        let version = package.get("versionInfo").and_then(Value::as_str);
+        packages.push(sbom_package(name, version, license, "spdx"));
+    }
+}
+
+fn read_spdx_tag_value(content: &str, packages: &mut Vec<PackageLicense>) {
+    #[derive(Default)]
+    struct Entry {
+        name: String,
+        version: Option<String>,
+        declared: Option<String>,
+        concluded: Option<String>,
+    }
+    let mut entries: Vec<Entry> = Vec::new();
+    for line in content.lines() {
+        let Some((tag, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        if tag.trim() == "PackageName" {
+            entries.push(Entry { name: value.to_string(), ..Entry::default() });
+            continue;
+        }
+        let Some(entry) = entries.last_mut() else { continue };
+        match tag.trim() {
+            "PackageVersion" => entry.version = Some(value.to_string()),
+            "PackageLicenseDeclared" => entry.declared = spdx_license(Some(value)),
+            "PackageLicenseConcluded" => entry.concluded = spdx_license(Some(value)),
+            _ => {}
+        }
+    }
+    for entry in entries {
+        let license = entry.declared.or(entry.concluded);
+        packages.push(sbom_package(&entry.name, entry.version.as_deref(), license, "spdx"));
+    }
+}
+
+/// `check --sbom`: the components of a CycloneDX JSON or SPDX (JSON or tag-value) document
+pub fn read_sbom(path: &Path, include_unknown: bool) -> Result<Vec<PackageLicense>> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read SBOM {}", path.display()))?;
+    let format = detect_sbom_format(&content)
+        .with_context(|| format!("{} is not a CycloneDX JSON or SPDX document", path.display()))?;
+
+    let mut packages = Vec::new();
+    match format {
+        SbomInput::CycloneDx | SbomInput::SpdxJson => {
+            let document: Value = serde_json::from_str(content.trim_start_matches('\u{feff}'))
+                .with_context(|| format!("Failed to parse SBOM {}", path.display()))?;
+            if format == SbomInput::CycloneDx {
+                read_cyclonedx(&document, &mut packages);
+            } else {
+                read_spdx_json(&document, &mut packages);
+            }
+        }
+        SbomInput::SpdxTagValue => read_spdx_tag_value(&content, &mut packages),
+    }
+
+    packages.retain(|package| include_unknown || package.effective_license.is_some());
+    Ok(packages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::create_report;
+
+    fn package(name: &str, version: &str, license: &str) -> PackageLicense {
+        PackageLicense {
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            effective_license: Some(license.to_string()),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn sample_report() -> LicenseReport {
+        create_report(vec![
+            package("requests", "2.31.0", "Apache-2.0"),
+            package("Click", "8.1.7", "BSD-3-Clause"),
+            package("attrs", "23.1.0", "MIT"),
+        ])
+    }
+
+    fn component_names(document: &str, key: &str) -> Vec<String> {
+        let value: Value = serde_json::from_str(document).unwrap();
+        value[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_cyclonedx_components_sorted_by_name() {
+        let output = format_cyclonedx(&sample_report(), &SbomOptions::default()).unwrap();
+        assert_eq!(component_names(&output, "components"), vec!["attrs", "Click", "requests"]);
+    }
+
+    #[test]
+    fn test_same_name_components_sorted_by_parsed_version() {
+        let report = create_report(vec![
+            package("numpy", "2.0.0rc1", "BSD-3-Clause"),
+            package("numpy", "1.10.0", "BSD-3-Clause"),
+            package("numpy", "1.9.2", "BSD-3-Clause"),
+        ]);
+        let ordered: Vec<_> = ordered_packages(&report, SbomSort::Name).iter().map(|p| p.version.as_deref().unwrap()).collect();
+        assert_eq!(ordered, vec!["1.9.2", "1.10.0", "2.0.0rc1"]);
+    }
+
+    #[test]
+    fn test_sbom_sort_none_keeps_insertion_order() {
+        let options = SbomOptions { sort: SbomSort::None, deterministic: false };
+        let output = format_spdx(&sample_report(), &options).unwrap();
+        assert_eq!(component_names(&output, "packages"), vec!["requests", "Click", "attrs"]);
+    }
+
+    #[test]
+    fn test_deterministic_output_is_stable() {
+        let options = SbomOptions { sort: SbomSort::Name, deterministic: true };
+        let report = sample_report();
+
+        assert_eq!(format_cyclonedx(&report, &options).unwrap(), format_cyclonedx(&report, &options).unwrap());
+        assert_eq!(format_spdx(&report, &options).unwrap(), format_spdx(&report, &options).unwrap());
+
+        let cyclonedx: Value = serde_json::from_str(&format_cyclonedx(&report, &options).unwrap()).unwrap();
+        assert!(cyclonedx["metadata"].get("timestamp").is_none());
+    }
+
+    #[test]
+    fn test_non_spdx_license_is_not_used_as_id() {
+        let report = create_report(vec![package("odd", "1.0", "Some Custom License")]);
+        let output = format_spdx(&report, &SbomOptions::default()).unwrap();
+        let value: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["packages"][0]["licenseDeclared"], "NOASSERTION");
+    }
+
+    #[test]
+    fn test_read_sbom_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut report = sample_report();
+        report.packages.push(package("odd", "1.0", "Some Custom License"));
+        let options = SbomOptions::default();
+
+        for (file, document) in [
+            ("bom.cdx.json", format_cyclonedx(&report, &options).unwrap()),
+            ("bom.spdx.json", format_spdx(&report, &options).unwrap()),
+        ] {
+            let path = temp_dir.path().join(file);
+            std::fs::write(&path, document).unwrap();
+            let packages = read_sbom(&path, false).unwrap();
+            let licenses: Vec<(&str, Option<&str>)> = packages
+                .iter()
+                .map(|package| (package.name.as_str(), package.effective_license.as_deref()))
+                .collect();
+            assert_eq!(licenses, vec![
+                ("attrs", Some("MIT")),
+                ("Click", Some("BSD-3-Clause")),
+                ("odd", Some("Some Custom License")),
+                ("requests", Some("Apache-2.0")),
+            ], "{}", file);
+        }
+
+        let tag_value = concat!(
+            "SPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\n\n",
+            "PackageName: six\nPackageVersion: 1.16.0\nPackageLicenseDeclared: NOASSERTION\nPackageLicenseConcluded: MIT\n\n",
+            "PackageName: mystery\nPackageVersion: 0.1\nPackageLicenseDeclared: NONE\n",
+        );
+        assert_eq!(detect_sbom_format(tag_value), Some(SbomInput::SpdxTagValue));
+        let path = temp_dir.path().join("bom.spdx");
+        std::fs::write(&path, tag_value).unwrap();
+        let packages = read_sbom(&path, true).unwrap();
+        assert_eq!(packages[0].effective_license.as_deref(), Some("MIT"));
+        assert_eq!(packages[1].detection_status, DetectionStatus::NoLicenseField);
+
+        assert_eq!(detect_sbom_format("{\"packages\": []}"), None);
+    }
+}
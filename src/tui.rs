@@ -0,0 +1,413 @@
+//! Interactive report browser for `check --tui` (requires the `tui` feature)
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use crate::exceptions::{default_expiry, expand_reason, new_exception, Exception};
+use crate::license::{LicenseReport, PackageLicense};
+use crate::policy::{Violation, ViolationLevel};
+
+/// Status filter, cycled with Tab
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusFilter {
+    All,
+    Issues,
+    Forbidden,
+    ReviewRequired,
+    Unknown,
+}
+
+impl StatusFilter {
+    fn next(self) -> Self {
+        match self {
+            StatusFilter::All => StatusFilter::Issues,
+            StatusFilter::Issues => StatusFilter::Forbidden,
+            StatusFilter::Forbidden => StatusFilter::ReviewRequired,
+            StatusFilter::ReviewRequired => StatusFilter::Unknown,
+            StatusFilter::Unknown => StatusFilter::All,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StatusFilter::All => "all",
+            StatusFilter::Issues => "issues",
+            StatusFilter::Forbidden => "forbidden",
+            StatusFilter::ReviewRequired => "review required",
+            StatusFilter::Unknown => "unknown",
+        }
+    }
+
+    fn matches(self, row: &Row) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Issues => row.level.is_some() || row.package.effective_license.is_none(),
+            StatusFilter::Forbidden => row.level == Some(ViolationLevel::Forbidden),
+            StatusFilter::ReviewRequired => row.level == Some(ViolationLevel::ReviewRequired),
+            StatusFilter::Unknown => {
+                row.level == Some(ViolationLevel::Unknown) || row.package.effective_license.is_none()
+            }
+        }
+    }
+}
+
+/// What the keyboard currently edits
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    Browse,
+    /// Typing a license filter after `/`
+    LicenseFilter,
+    /// Typing the reason for an exception after `x`
+    ExceptionReason,
+}
+
+struct Row {
+    package: PackageLicense,
+    level: Option<ViolationLevel>,
+    violation: Option<Violation>,
+}
+
+/// Browser state, kept apart from the terminal so key handling is testable
+pub struct TuiState {
+    rows: Vec<Row>,
+    pub status_filter: StatusFilter,
+    pub license_filter: String,
+    pub mode: Mode,
+    pub input: String,
+    pub selected: usize,
+    /// Exceptions added during the session, saved on exit
+    pub new_exceptions: Vec<Exception>,
+    pub message: Option<String>,
+    pub quit: bool,
+}
+
+impl TuiState {
+    pub fn new(report: &LicenseReport) -> Self {
+        let details = report.violations.as_ref().map(|v| v.details.as_slice()).unwrap_or_default();
+        let rows = report
+            .packages
+            .iter()
+            .map(|package| {
+                let violation = details
+                    .iter()
+                    .find(|v| v.package_name == package.name && v.package_version == package.version)
+                    .cloned();
+                Row {
+                    package: package.clone(),
+                    level: violation.as_ref().map(|v| v.violation_level.clone()),
+                    violation,
+                }
+            })
+            .collect();
+
+        TuiState {
+            rows,
+            status_filter: StatusFilter::All,
+            license_filter: String::new(),
+            mode: Mode::Browse,
+            input: String::new(),
+            selected: 0,
+            new_exceptions: Vec::new(),
+            message: None,
+            quit: false,
+        }
+    }
+
+    fn visible(&self) -> Vec<&Row> {
+        let license_filter = self.license_filter.to_lowercase();
+        self.rows
+            .iter()
+            .filter(|row| self.status_filter.matches(row))
+            .filter(|row| {
+                license_filter.is_empty()
+                    || row.package.effective_license.as_deref().unwrap_or("unknown").to_lowercase().contains(&license_filter)
+            })
+            .collect()
+    }
+
+    /// Names of the packages currently listed
+    pub fn visible_names(&self) -> Vec<&str> {
+        self.visible().iter().map(|row| row.package.name.as_str()).collect()
+    }
+
+    fn selected_row(&self) -> Option<&Row> {
+        self.visible().get(self.selected).copied()
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible().len();
+        self.selected = self.selected.min(len.saturating_sub(1));
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match self.mode {
+            Mode::Browse => self.handle_browse_key(key),
+            Mode::LicenseFilter | Mode::ExceptionReason => self.handle_input_key(key),
+        }
+    }
+
+    fn handle_browse_key(&mut self, key: KeyCode) {
+        self.message = None;
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.visible().len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Tab | KeyCode::Char('s') => {
+                self.status_filter = self.status_filter.next();
+                self.selected = 0;
+            }
+            KeyCode::Char('/') => {
+                self.mode = Mode::LicenseFilter;
+                self.input = self.license_filter.clone();
+            }
+            KeyCode::Char('x') => match self.selected_row() {
+                Some(row) if self.is_excepted(&row.package) => {
+                    self.message = Some(format!("{} already has an exception in this session", row.package.name));
+                }
+                Some(_) => {
+                    self.mode = Mode::ExceptionReason;
+                    self.input.clear();
+                }
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn handle_input_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.mode = Mode::Browse;
+                self.input.clear();
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter => {
+                let input = std::mem::take(&mut self.input);
+                match self.mode {
+                    Mode::LicenseFilter => {
+                        self.license_filter = input.trim().to_string();
+                        self.selected = 0;
+                    }
+                    Mode::ExceptionReason => self.add_exception(&input),
+                    Mode::Browse => {}
+                }
+                self.mode = Mode::Browse;
+            }
+            _ => {}
+        }
+    }
+
+    fn is_excepted(&self, package: &PackageLicense) -> bool {
+        self.new_exceptions
+            .iter()
+            .any(|e| e.name == package.name && e.version == package.version)
+    }
+
+    fn add_exception(&mut self, reason: &str) {
+        let Some(row) = self.selected_row() else { return };
+        let expires = default_expiry();
+        let exception = new_exception(&row.package.name, row.package.version.as_deref(), expand_reason(reason), Some(expires));
+        self.message = Some(format!("Exception added for {} (expires {}), saved on exit", exception.name, expires));
+        self.new_exceptions.push(exception);
+        self.clamp_selection();
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let [main, footer] = Layout::vertical([Constraint::Min(3), Constraint::Length(2)]).areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(main);
+
+        let visible = self.visible();
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|row| {
+                let (marker, color) = match row.level {
+                    Some(ViolationLevel::Forbidden) => ("🚫", Color::Red),
+                    Some(ViolationLevel::ReviewRequired) => ("⚠️", Color::Yellow),
+                    Some(ViolationLevel::Unknown) => ("❓", Color::Magenta),
+                    _ if row.package.effective_license.is_none() => ("❓", Color::Magenta),
+                    _ => ("✅", Color::Green),
+                };
+                let excepted = if self.is_excepted(&row.package) { " (excepted)" } else { "" };
+                ListItem::new(format!(
+                    "{} {} {} — {}{}",
+                    marker,
+                    row.package.name,
+                    row.package.version.as_deref().unwrap_or("-"),
+                    row.package.effective_license.as_deref().unwrap_or("unknown"),
+                    excepted
+                ))
+                .style(Style::default().fg(color))
+            })
+            .collect();
+
+        let title = format!(
+            " Packages {}/{} · status: {}{} ",
+            visible.len(),
+            self.rows.len(),
+            self.status_filter.label(),
+            if self.license_filter.is_empty() { String::new() } else { format!(" · license: {}", self.license_filter) }
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("▶ ");
+        let mut list_state = ListState::default().with_selected((!visible.is_empty()).then_some(self.selected));
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+
+        let detail = match visible.get(self.selected) {
+            Some(row) => detail_lines(row),
+            None => vec![Line::from("No packages match the current filters")],
+        };
+        frame.render_widget(
+            Paragraph::new(detail)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(" Details ")),
+            detail_area,
+        );
+
+        let footer_text = match self.mode {
+            Mode::LicenseFilter => format!("License filter: {}▏ (Enter apply, Esc cancel)", self.input),
+            Mode::ExceptionReason => format!(
+                "Exception reason [research/migration/legacy]: {}▏ (Enter add, Esc cancel)",
+                self.input
+            ),
+            Mode::Browse => self.message.clone().unwrap_or_else(|| {
+                "↑/↓ move · Tab status filter · / license filter · x add exception · q quit".to_string()
+            }),
+        };
+        frame.render_widget(Paragraph::new(footer_text), footer);
+    }
+}
+
+fn detail_lines(row: &Row) -> Vec<Line<'static>> {
+    let package = &row.package;
+    let mut lines = vec![
+        Line::from(format!("Package:  {}", package.name)),
+        Line::from(format!("Version:  {}", package.version.as_deref().unwrap_or("unknown"))),
+        Line::from(format!("License:  {}", package.effective_license.as_deref().unwrap_or("unknown"))),
+        Line::from(format!("Source:   {}", package.metadata_source)),
+    ];
+    for classifier in &package.license_classifiers {
+        lines.push(Line::from(format!("          {}", classifier)));
+    }
+    if let Some(violation) = &row.violation {
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Violation: {:?}", violation.violation_level)));
+        lines.push(Line::from(format!("Message:   {}", violation.message)));
+        if let Some(rule) = &violation.matched_rule {
+            lines.push(Line::from(format!("Rule:      {}", rule)));
+        }
+    }
+    for warning in &package.warnings {
+        lines.push(Line::from(format!("⚠ {}", warning)));
+    }
+    if let Some(annotation) = &package.annotation {
+        lines.push(Line::from(format!("Note:     {}", annotation)));
+    }
+    lines
+}
+
+/// Browse the report until `q`; returns the exceptions added during the session
+pub fn run_tui(report: &LicenseReport) -> Result<Vec<Exception>> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, TuiState::new(report));
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut state: TuiState) -> Result<Vec<Exception>> {
+    while !state.quit {
+        terminal.draw(|frame| state.render(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                state.handle_key(key.code);
+            }
+        }
+    }
+    Ok(state.new_exceptions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::create_report;
+    use crate::policy::ViolationSummary;
+
+    fn package(name: &str, license: Option<&str>) -> PackageLicense {
+        PackageLicense {
+            name: name.to_string(),
+            version: Some("1.0".to_string()),
+            effective_license: license.map(str::to_string),
+            metadata_source: "METADATA".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn state() -> TuiState {
+        let mut report = create_report(vec![
+            package("requests", Some("Apache-2.0")),
+            package("gpl-lib", Some("GPL-3.0")),
+            package("mystery", None),
+        ]);
+        report.violations = Some(ViolationSummary::from_details(vec![Violation {
+            package_name: "gpl-lib".to_string(),
+            package_version: Some("1.0".to_string()),
+            license: Some("GPL-3.0".to_string()),
+            violation_level: ViolationLevel::Forbidden,
+            matched_rule: Some("exact: GPL-3.0".to_string()),
+            message: "License 'GPL-3.0' is forbidden by policy".to_string(),
+            ..Default::default()
+        }]));
+        TuiState::new(&report)
+    }
+
+    #[test]
+    fn test_status_and_license_filters() {
+        let mut state = state();
+        assert_eq!(state.visible_names().len(), 3);
+
+        state.handle_key(KeyCode::Tab);
+        assert_eq!(state.visible_names(), vec!["gpl-lib", "mystery"]);
+        state.handle_key(KeyCode::Tab);
+        assert_eq!(state.visible_names(), vec!["gpl-lib"]);
+
+        state.status_filter = StatusFilter::All;
+        for key in [KeyCode::Char('/'), KeyCode::Char('a'), KeyCode::Char('p'), KeyCode::Enter] {
+            state.handle_key(key);
+        }
+        assert_eq!(state.license_filter, "ap");
+        assert_eq!(state.visible_names(), vec!["requests"]);
+    }
+
+    #[test]
+    fn test_add_exception_for_selected_package() {
+        let mut state = state();
+        state.handle_key(KeyCode::Down);
+        state.handle_key(KeyCode::Char('x'));
+        assert_eq!(state.mode, Mode::ExceptionReason);
+        for c in "legacy".chars() {
+            state.handle_key(KeyCode::Char(c));
+        }
+        state.handle_key(KeyCode::Enter);
+
+        assert_eq!(state.mode, Mode::Browse);
+        assert_eq!(state.new_exceptions.len(), 1);
+        assert_eq!(state.new_exceptions[0].name, "gpl-lib");
+        assert_eq!(state.new_exceptions[0].reason, "legacy compatibility");
+
+        // A second `x` on the same package does not duplicate it
+        state.handle_key(KeyCode::Char('x'));
+        assert_eq!(state.mode, Mode::Browse);
+        assert_eq!(state.new_exceptions.len(), 1);
+    }
+}
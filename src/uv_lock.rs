@@ -29,6 +29,55 @@ pub struct UvSource {
     pub registry: Option<String>,
     pub git: Option<String>,
     pub path: Option<String>,
+    #[serde(rename = "virtual")]
+    pub virtual_path: Option<String>,
+    pub editable: Option<String>,
+}
+
+/// Per-package facts from uv.lock that are not part of the license metadata
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LockedPackageInfo {
+    /// Download size of the first wheel (or the sdist)
+    pub size: Option<u64>,
+    /// Declared directly by the project (a dependency of a virtual/editable root)
+    pub direct: bool,
+}
+
+/// PyPI-style name normalization used for lookups across uv.lock and site-packages
+pub fn normalize_package_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+/// Version normalization for equality checks across uv.lock, dist-info names and METADATA:
+/// case-insensitive, and PEP 440 local segments compare equal whatever their separators
+/// (`2.1.0+CU-118` == `2.1.0+cu.118`; dist-info names spell `-` as `_`)
+pub fn normalize_version(version: &str) -> String {
+    let version = version.trim().to_lowercase();
+    match version.split_once('+') {
+        Some((public, local)) => format!("{}+{}", public, local.replace(['-', '_'], ".")),
+        None => version,
+    }
+}
+
+impl UvDependency {
+    pub fn name(&self) -> &str {
+        match self {
+            UvDependency::Simple(name) => name,
+            UvDependency::Complex { name, .. } => name,
+        }
+    }
+}
+
+impl UvPackage {
+    /// The project itself (virtual or editable source) rather than a dependency
+    pub fn is_project_root(&self) -> bool {
+        self.source.as_ref().is_some_and(|source| source.virtual_path.is_some() || source.editable.is_some())
+    }
+
+    /// The scanned project's own package: a virtual root, or the project installed editable from "."
+    pub fn is_self(&self) -> bool {
+        self.source.as_ref().is_some_and(|source| source.virtual_path.is_some() || source.editable.as_deref() == Some("."))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -49,6 +98,9 @@ pub struct UvDistribution {
 
 pub struct UvLockParser;
 
+/// Package count above which a uv.lock is reported as suspicious
+pub const PACKAGE_COUNT_WARNING: usize = 50_000;
+
 impl UvLockParser {
     /// Parse uv.lock file and return structured data
     pub fn parse_uv_lock<P: AsRef<Path>>(path: P) -> Result<UvLockFile> {
@@ -82,10 +134,29 @@ impl UvLockParser {
         Ok(lock_file)
     }
 
-    /// Extract package names and versions from uv.lock
-    pub fn extract_packages(lock_file: &UvLockFile) -> Vec<(String, String)> {
+    /// Parse uv.lock content that did not come from a file (e.g. `git show`)
+    pub fn parse_uv_lock_str(content: &str) -> Result<UvLockFile> {
+        toml::from_str(content).context("Failed to parse uv.lock content as TOML")
+    }
+
+    /// Fail when uv.lock lists more than `max_packages` packages (`--max-packages`)
+    pub fn check_package_count(lock_file: &UvLockFile, max_packages: usize) -> Result<()> {
+        let count = lock_file.packages.len();
+        if count > max_packages {
+            anyhow::bail!(
+                "uv.lock lists {} packages, more than --max-packages {}; refusing to scan a possibly corrupted or malicious lockfile",
+                count, max_packages
+            );
+        }
+        Ok(())
+    }
+
+    /// Extract package names and versions from uv.lock. The project's own package is
+    /// left out unless `include_self`: it is not a dependency and usually has no license.
+    pub fn extract_packages(lock_file: &UvLockFile, include_self: bool) -> Vec<(String, String)> {
         lock_file.packages
             .iter()
+            .filter(|pkg| include_self || !pkg.is_self())
             .filter_map(|pkg| {
                 // Validate package name and version
                 if pkg.name.trim().is_empty() {
@@ -101,6 +172,28 @@ impl UvLockParser {
             .collect()
     }
 
+    /// Size and direct-dependency facts keyed by normalized package name
+    pub fn locked_package_info(lock_file: &UvLockFile) -> std::collections::HashMap<String, LockedPackageInfo> {
+        let direct: std::collections::HashSet<String> = lock_file.packages
+            .iter()
+            .filter(|pkg| pkg.is_project_root())
+            .flat_map(|pkg| pkg.dependencies.iter().flatten())
+            .map(|dep| normalize_package_name(dep.name()))
+            .collect();
+
+        lock_file.packages
+            .iter()
+            .map(|pkg| {
+                let size = pkg.wheels.as_ref()
+                    .and_then(|wheels| wheels.iter().find_map(|wheel| wheel.size))
+                    .or_else(|| pkg.sdist.as_ref().and_then(|sdist| sdist.size));
+                let name = normalize_package_name(&pkg.name);
+                let info = LockedPackageInfo { size, direct: direct.contains(&name) };
+                (name, info)
+            })
+            .collect()
+    }
+
     /// Find uv.lock file in current directory or parent directories
     pub fn find_uv_lock() -> Option<std::path::PathBuf> {
         let mut current = std::env::current_dir().ok()?;
@@ -194,10 +287,72 @@ dependencies = [
             ],
         };
 
-        let packages = UvLockParser::extract_packages(&lock_file);
+        let packages = UvLockParser::extract_packages(&lock_file, false);
         assert_eq!(packages.len(), 2);
         assert_eq!(packages[0], ("requests".to_string(), "2.31.0".to_string()));
         assert_eq!(packages[1], ("click".to_string(), "8.1.7".to_string()));
+
+        assert!(UvLockParser::check_package_count(&lock_file, 2).is_ok());
+        let error = UvLockParser::check_package_count(&lock_file, 1).unwrap_err().to_string();
+        assert!(error.contains("uv.lock lists 2 packages, more than --max-packages 1"));
+    }
+
+    #[test]
+    fn test_locked_package_info() {
+        let lock_file = UvLockParser::parse_uv_lock_str(r#"
+version = 1
+
+[[package]]
+name = "proj"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "Requests" }]
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+source = { registry = "https://pypi.org/simple" }
+dependencies = [{ name = "idna" }]
+wheels = [{ url = "https://example.invalid/requests.whl", hash = "sha256:aa", size = 62574 }]
+
+[[package]]
+name = "idna"
+version = "3.6"
+source = { registry = "https://pypi.org/simple" }
+sdist = { url = "https://example.invalid/idna.tar.gz", hash = "sha256:bb", size = 175426 }
+"#).unwrap();
+
+        let info = UvLockParser::locked_package_info(&lock_file);
+        assert_eq!(info["requests"], LockedPackageInfo { size: Some(62574), direct: true });
+        assert_eq!(info["idna"], LockedPackageInfo { size: Some(175426), direct: false });
+        assert!(lock_file.packages[0].is_project_root());
+    }
+
+    #[test]
+    fn test_extract_packages_excludes_self() {
+        let lock_file = UvLockParser::parse_uv_lock_str(r#"
+version = 1
+
+[[package]]
+name = "proj"
+version = "0.1.0"
+source = { virtual = "." }
+dependencies = [{ name = "requests" }, { name = "shared" }]
+
+[[package]]
+name = "shared"
+version = "0.2.0"
+source = { editable = "packages/shared" }
+
+[[package]]
+name = "requests"
+version = "2.31.0"
+source = { registry = "https://pypi.org/simple" }
+"#).unwrap();
+
+        let names = |packages: Vec<(String, String)>| packages.into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names(UvLockParser::extract_packages(&lock_file, false)), vec!["shared", "requests"]);
+        assert_eq!(names(UvLockParser::extract_packages(&lock_file, true)), vec!["proj", "shared", "requests"]);
     }
 
     #[test]
@@ -228,7 +383,7 @@ source = { registry = "https://pypi.org/simple" }
         fs::create_dir(&site_packages).unwrap();
         
         // Test that the function handles missing packages gracefully
-        let result = extract_licenses_from_uv_lock(Some(uv_lock_path), Some(site_packages), false);
+        let result = extract_licenses_from_uv_lock(Some(uv_lock_path), Some(site_packages), false, false);
         assert!(result.is_ok());
         
         let packages = result.unwrap();
@@ -0,0 +1,145 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::Stdio;
+use std::time::Duration;
+
+/// Delivery attempts before giving up (`check --webhook`)
+const ATTEMPTS: u32 = 3;
+/// Per-attempt timeout in seconds
+const TIMEOUT_SECS: u32 = 30;
+
+/// Parse a `--webhook-header` value (`Name: value`)
+pub fn parse_header(header: &str) -> Result<(String, String)> {
+    match header.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() && !name.trim().contains(char::is_whitespace) => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => anyhow::bail!("Invalid webhook header '{}' (expected 'Name: value')", header),
+    }
+}
+
+/// Validate a `--webhook` URL: only http(s) endpoints are accepted
+pub fn check_url(url: &str) -> Result<()> {
+    crate::curl::ensure_http_url(url).context("Invalid --webhook URL")
+}
+
+/// Quote a value for a curl config file
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r"))
+}
+
+/// curl config for one delivery. It is passed on stdin so header values such as
+/// tokens never appear in the process list.
+fn curl_config(url: &str, headers: &[(String, String)], body_path: &str) -> String {
+    let mut lines = vec![
+        format!("url = {}", quote(url)),
+        "request = \"POST\"".to_string(),
+        format!("max-time = {}", TIMEOUT_SECS),
+        "header = \"Content-Type: application/json\"".to_string(),
+    ];
+    lines.extend(headers.iter().map(|(name, value)| format!("header = {}", quote(&format!("{}: {}", name, value)))));
+    lines.push(format!("data-binary = {}", quote(&format!("@{}", body_path))));
+    lines.join("\n") + "\n"
+}
+
+/// Why a delivery attempt failed
+#[derive(Debug)]
+enum Failure {
+    /// Connection error, timeout or 5xx: worth another attempt
+    Transient(String),
+    /// 4xx or a local problem: retrying cannot help
+    Permanent(String),
+}
+
+/// Classify the HTTP status of a completed request (`None` on success). Redirects are
+/// not followed, so a 3xx means the report was not delivered.
+fn status_failure(status: u16) -> Option<Failure> {
+    match status {
+        200..=299 => None,
+        300..=399 => Some(Failure::Permanent(format!("HTTP {} (redirects are not followed; use the final URL)", status))),
+        400..=499 => Some(Failure::Permanent(format!("HTTP {}", status))),
+        _ => Some(Failure::Transient(format!("HTTP {}", status))),
+    }
+}
+
+fn post_once(config: &str) -> std::result::Result<(), Failure> {
+    let mut child = crate::curl::command()
+        .args(["--output", "/dev/null", "--write-out", "%{http_code}", "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| Failure::Permanent(format!("Failed to run curl (is it installed?): {}", error)))?;
+    let written = child.stdin.take().expect("stdin is piped").write_all(config.as_bytes());
+    let output = child.wait_with_output().map_err(|error| Failure::Permanent(error.to_string()))?;
+    written.map_err(|error| Failure::Permanent(error.to_string()))?;
+
+    if !output.status.success() {
+        // No HTTP response at all: DNS, connection or timeout errors
+        return Err(Failure::Transient(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    let status = String::from_utf8_lossy(&output.stdout).trim().parse().unwrap_or(0);
+    status_failure(status).map_or(Ok(()), Err)
+}
+
+/// POST a JSON report to `url`, retrying connection errors, timeouts and 5xx responses
+/// with a growing delay. A 4xx response fails immediately.
+pub fn post_report(url: &str, headers: &[(String, String)], body: &str) -> Result<()> {
+    check_url(url)?;
+    // Created 0600 with O_EXCL and removed on drop: no predictable path, not readable by others
+    let mut body_file = tempfile::Builder::new()
+        .prefix("py-license-auditor-webhook-")
+        .suffix(".json")
+        .tempfile()
+        .context("Failed to create a temporary file for the webhook body")?;
+    body_file.write_all(body.as_bytes())?;
+    body_file.flush()?;
+    let config = curl_config(url, headers, &body_file.path().to_string_lossy());
+
+    let mut attempt = 1;
+    let failure = loop {
+        match post_once(&config) {
+            Ok(()) => return Ok(()),
+            Err(Failure::Transient(_)) if attempt < ATTEMPTS => {
+                std::thread::sleep(Duration::from_secs(attempt as u64));
+                attempt += 1;
+            }
+            Err(Failure::Transient(message)) => {
+                break format!("{} (after {} attempts)", message, attempt);
+            }
+            Err(Failure::Permanent(message)) => break message,
+        }
+    };
+    anyhow::bail!("Webhook delivery to {} failed: {}", url, failure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_headers_and_config() {
+        let header = parse_header("Authorization: Bearer abc\"123").unwrap();
+        assert_eq!(header, ("Authorization".to_string(), "Bearer abc\"123".to_string()));
+        assert!(parse_header("no colon").is_err());
+        assert!(parse_header("Bad Name: x").is_err());
+        assert!(check_url("file:///tmp/report.json").is_err());
+
+        let config = curl_config("https://example.com/hook", &[header], "/tmp/report.json");
+        assert!(config.contains("url = \"https://example.com/hook\"\n"));
+        assert!(config.contains("header = \"Authorization: Bearer abc\\\"123\"\n"));
+        assert!(config.ends_with("data-binary = \"@/tmp/report.json\"\n"));
+    }
+
+    #[test]
+    fn test_only_server_errors_are_retried() {
+        assert!(status_failure(204).is_none());
+        assert!(matches!(status_failure(301), Some(Failure::Permanent(_))));
+        assert!(matches!(status_failure(307), Some(Failure::Permanent(_))));
+        assert!(matches!(status_failure(404), Some(Failure::Permanent(_))));
+        assert!(matches!(status_failure(401), Some(Failure::Permanent(_))));
+        assert!(matches!(status_failure(503), Some(Failure::Transient(_))));
+        // curl prints 000 when no response arrived
+        assert!(matches!(status_failure(0), Some(Failure::Transient(_))));
+    }
+}
@@ -6,6 +6,12 @@ pub struct TestProject {
     pub binary_path: String,
 }
 
+impl Default for TestProject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TestProject {
     pub fn new() -> Self {
         let dir = TempDir::new().expect("Failed to create temp dir");